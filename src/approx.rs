@@ -0,0 +1,206 @@
+use std::{cmp::Ordering, f64::consts::SQRT_2};
+
+use crate::{Dyadic, Quad, RootTwo};
+
+const LAMBDA: f64 = 1.0 + SQRT_2;
+
+/// The product of `a + b√2` and `c + d√2`, as pairs of `i128`s.
+fn mul_wide((a, b): (i128, i128), (c, d): (i128, i128)) -> (i128, i128) {
+    (a * c + 2 * b * d, a * d + b * c)
+}
+
+/// Every `v` in ℤ[√2] with `v ∈ [x0, x1]` and `v• ∈ [y0, y1]`, along with
+/// some points just outside these intervals.
+///
+/// Multiplying by `λⁿ` scales `v` by `λⁿ` and `v•` by `(-λ)⁻ⁿ`, so a
+/// suitable `n` makes both intervals about `√(|x|·|y|)` wide. Each
+/// candidate `b` then comes from `v - v• = 2b√2`, and each `a` is bounded
+/// by both intervals. The scaled intervals are computed in floating point,
+/// so they are widened slightly.
+fn grid_points(x: (f64, f64), y: (f64, f64)) -> Vec<RootTwo<i64>> {
+    let n = (((y.1 - y.0) / (x.1 - x.0)).ln() / (2.0 * LAMBDA.ln())).round() as i32;
+    let scale_x = LAMBDA.powi(n);
+    let scale_y = (-LAMBDA).powi(-n);
+    let (x0, x1) = (x.0 * scale_x, x.1 * scale_x);
+    let (y0, y1) = if scale_y > 0.0 {
+        (y.0 * scale_y, y.1 * scale_y)
+    } else {
+        (y.1 * scale_y, y.0 * scale_y)
+    };
+    let eps = 1e-12 * (1.0 + x0.abs().max(x1.abs()).max(y0.abs()).max(y1.abs()));
+    // λ⁻ⁿ, to undo the scaling exactly
+    let (unit, m) = if n > 0 {
+        ((-1, 1), n as u32)
+    } else {
+        ((1, 1), n.unsigned_abs())
+    };
+    let inverse = (0..m).fold((1, 0), |acc, _| mul_wide(acc, unit));
+    let mut points = Vec::new();
+    let b_min = ((x0 - y1) / (2.0 * SQRT_2) - eps).ceil() as i64;
+    let b_max = ((x1 - y0) / (2.0 * SQRT_2) + eps).floor() as i64;
+    for b in b_min..=b_max {
+        let root = b as f64 * SQRT_2;
+        let lo = (x0 - root).max(y0 + root);
+        let hi = (x1 - root).min(y1 + root);
+        for a in (lo - eps).ceil() as i64..=(hi + eps).floor() as i64 {
+            let v = mul_wide((a as i128, b as i128), inverse);
+            if let (Ok(a), Ok(b)) = (i64::try_from(v.0), i64::try_from(v.1)) {
+                points.push(Quad(a, b));
+            }
+        }
+    }
+    points
+}
+
+/// Whether `v` is strictly closer to `x` than `w` is, computed exactly.
+/// Points on opposite sides compare `2x` with `v + w`.
+fn closer(v: RootTwo<i64>, w: RootTwo<i64>, x: f64) -> bool {
+    match (v.cmp_f64(x), w.cmp_f64(x)) {
+        (Ordering::Equal, other) => other != Ordering::Equal,
+        (_, Ordering::Equal) => false,
+        (Ordering::Less, Ordering::Less) => v > w,
+        (Ordering::Greater, Ordering::Greater) => v < w,
+        (side, _) => (v + w).cmp_f64(2.0 * x) == side.reverse(),
+    }
+}
+
+/// Whether [`RootTwo::<Dyadic>::approximate`] accepts `x` and `k`: a finite
+/// `x` with `|x|·2ᵏ < 2⁶²` and `k ≤ 40`. The bindings check this to raise an
+/// error instead.
+pub(crate) fn approximable(x: f64, k: u32) -> bool {
+    x.is_finite() && k <= 40 && x.abs() * ((1u64 << k) as f64) < 2f64.powi(62)
+}
+
+impl RootTwo<Dyadic> {
+    /// The closest `u = (a + b√2)/2ᵏ` to `x` with `|u•| ≤ 1`, together with
+    /// the error `x - u`, which is exact because every finite float is
+    /// dyadic.
+    ///
+    /// Without the bound on the conjugate these points would be dense. With
+    /// it, this is the one-dimensional grid problem that opens Ross–Selinger
+    /// style synthesis, and there are about `2ᵏ/√2` candidates per unit
+    /// length. The search grows a window around `x` until it contains one,
+    /// and compares the candidates exactly. Ties go to the first candidate
+    /// found.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is not finite, if `k > 40`, past which the floating
+    /// point search loses the precision it needs, or if `|x|·2ᵏ ≥ 2⁶²`,
+    /// where the numerators of `u` would overflow. Also panics if the error
+    /// has no `i64` numerator, which needs `x` to have nonzero bits more
+    /// than 60 places below `2⁻ᵏ`.
+    pub fn approximate(x: f64, k: u32) -> (RootTwo<Dyadic>, RootTwo<Dyadic>) {
+        let exact = <Dyadic>::try_from(x).expect("x should be finite");
+        assert!(k <= 40, "approximation needs k ≤ 40");
+        assert!(approximable(x, k), "approximation needs |x|·2ᵏ < 2⁶²");
+        // look for v = 2ᵏu near 2ᵏx, shifted by an integer c towards zero
+        let r = (1u64 << k) as f64;
+        let scaled = x * r;
+        let c = scaled.round();
+        let mut delta = 1.0 / r;
+        let v = loop {
+            let window = (scaled - c - delta, scaled - c + delta);
+            let best = grid_points(window, (-r - c, r - c))
+                .into_iter()
+                .map(|v| v + Quad(c as i64, 0))
+                .filter(|v| RootTwo::from_normalized(v.conj(), k).abs() <= 1)
+                .reduce(|v, w| if closer(w, v, scaled) { w } else { v });
+            if let Some(v) = best {
+                break v;
+            }
+            delta *= 2.0;
+        };
+        let u = RootTwo::from_normalized(v, k);
+        let rational = exact
+            .checked_sub(u.0)
+            .expect("approximation error overflows");
+        (u, Quad(rational, -u.1))
+    }
+}
+
+#[cfg(test)]
+mod approx_tests {
+    use super::*;
+    use num::Zero;
+
+    /// The closest point by brute force over every `b`.
+    fn brute_force(x: f64, k: u32) -> RootTwo<Dyadic> {
+        let target = RootTwo::new(<Dyadic>::try_from(x).unwrap(), Dyadic::zero());
+        let r = 1i64 << k;
+        let bound = ((x.abs() * r as f64 + r as f64) / (2.0 * SQRT_2)).ceil() as i64 + 1;
+        (-bound..=bound)
+            .flat_map(|b| {
+                let a = (x * r as f64 - b as f64 * SQRT_2).round() as i64;
+                (a - 2..=a + 2).map(move |a| RootTwo::from_normalized(Quad(a, b), k))
+            })
+            .filter(|u| u.conj().abs() <= 1)
+            .min_by(|&u, &w| (target - u).abs().cmp(&(target - w).abs()))
+            .unwrap()
+    }
+
+    #[test]
+    fn closest_points() {
+        for (x, k) in [
+            (0.3, 0),
+            (0.3, 3),
+            (-0.77, 5),
+            (0.999, 4),
+            (2.5, 2),
+            (0.1, 8),
+        ] {
+            let (u, err) = RootTwo::<Dyadic>::approximate(x, k);
+            assert!(u.conj().abs() <= 1);
+            assert!(u.common_exponent() <= k);
+            assert_eq!(
+                u + err,
+                RootTwo::new(<Dyadic>::try_from(x).unwrap(), Dyadic(0, 0))
+            );
+            assert_eq!(
+                err.abs(),
+                (RootTwo::new(<Dyadic>::try_from(x).unwrap(), Dyadic(0, 0)) - brute_force(x, k))
+                    .abs()
+            );
+        }
+    }
+
+    #[test]
+    fn error_shrinks_with_k() {
+        let x = 0.123_456_789;
+        let mut last = f64::INFINITY;
+        for k in [4, 10, 16, 24, 32, 40] {
+            let (_, err) = RootTwo::<Dyadic>::approximate(x, k);
+            let err = f64::from(err).abs();
+            assert!(err <= last);
+            // about 2ᵏ/√2 points per unit length
+            assert!(err < 8.0 / (1u64 << k) as f64);
+            last = err;
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn approximate_nan() {
+        RootTwo::<Dyadic>::approximate(f64::NAN, 3);
+    }
+
+    #[test]
+    fn large_inputs() {
+        // just below 2⁶² after scaling, where the error is about 2⁻²ᵏ
+        for x in [4_194_303.5, -4_194_303.5, 3e9 / (1 << 20) as f64] {
+            let (u, err) = RootTwo::<Dyadic>::approximate(x, 40);
+            assert!(u.conj().abs() <= 1);
+            assert!(f64::from(err).abs() < 1e-20);
+        }
+        assert!(approximable(3e9, 20));
+        assert!(!approximable(1e9, 40));
+        assert!(!approximable(1e12, 40));
+        assert!(!approximable(f64::INFINITY, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn approximate_too_large() {
+        RootTwo::<Dyadic>::approximate(1e9, 40);
+    }
+}
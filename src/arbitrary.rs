@@ -0,0 +1,140 @@
+//! Proptest strategies for the `proptest` feature.
+//!
+//! The [`Arbitrary`] impls for `Dyadic` keep numerators below 2¹⁰ in
+//! magnitude and exponents at most 8, so that products of three elements of
+//! D[√2] or D[ω] and sums of those products never overflow. The impls for
+//! extension rings take their coefficients from the coefficient type's impl
+//! and shrink each coefficient towards zero. The functions here build the
+//! same strategies with other bounds.
+
+use std::{fmt, ops::RangeInclusive};
+
+use num::ToPrimitive;
+
+use proptest::{
+    arbitrary::{any_with, Arbitrary},
+    strategy::{Map, Strategy},
+};
+
+use crate::{Dyadic, Omega, Quad};
+
+/// Reduced dyadics `n/2ᵏ` for `n` in `numer` and `k ≤ max_exp`, shrinking
+/// towards integers near zero.
+pub fn dyadics(numer: RangeInclusive<i64>, max_exp: u32) -> impl Strategy<Value = Dyadic> + Clone {
+    (numer, 0..=max_exp).prop_map(|(n, k)| Dyadic::new(n, k))
+}
+
+/// `a + b√D` with both coefficients drawn from `coeff`.
+pub fn quads<T: fmt::Debug + ToPrimitive, const D: i64>(
+    coeff: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = Quad<T, D>> + Clone {
+    (coeff.clone(), coeff).prop_map(|(a, b)| Quad(a, b))
+}
+
+/// `a + bω + cω² + dω³` with every coefficient drawn from `coeff`.
+pub fn omegas<T: fmt::Debug>(
+    coeff: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = Omega<T>> + Clone {
+    (coeff.clone(), coeff.clone(), coeff.clone(), coeff).prop_map(|(a, b, c, d)| Omega(a, b, c, d))
+}
+
+impl Arbitrary for Dyadic {
+    type Parameters = ();
+    type Strategy = Map<(RangeInclusive<i64>, RangeInclusive<u32>), fn((i64, u32)) -> Dyadic>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (-1023..=1023i64, 0..=8u32).prop_map(|(n, k)| Dyadic::new(n, k))
+    }
+}
+
+impl<T: Arbitrary + ToPrimitive, const D: i64> Arbitrary for Quad<T, D>
+where
+    T::Parameters: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy = Map<(T::Strategy, T::Strategy), fn((T, T)) -> Quad<T, D>>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        (any_with::<T>(args.clone()), any_with::<T>(args)).prop_map(|(a, b)| Quad(a, b))
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Omega<T>
+where
+    T::Parameters: Clone,
+{
+    type Parameters = T::Parameters;
+    type Strategy =
+        Map<(T::Strategy, T::Strategy, T::Strategy, T::Strategy), fn((T, T, T, T)) -> Omega<T>>;
+
+    fn arbitrary_with(args: T::Parameters) -> Self::Strategy {
+        (
+            any_with::<T>(args.clone()),
+            any_with::<T>(args.clone()),
+            any_with::<T>(args.clone()),
+            any_with::<T>(args),
+        )
+            .prop_map(|(a, b, c, d)| Omega(a, b, c, d))
+    }
+}
+
+#[cfg(test)]
+mod arbitrary_tests {
+    use super::*;
+    use crate::{Adj2, DOmega, RootTwo, ZOmega};
+    use proptest::prelude::*;
+
+    fn small() -> RangeInclusive<i64> {
+        -(1 << 16)..=1 << 16
+    }
+
+    proptest! {
+        #[test]
+        fn dyadic_ring_axioms(x: Dyadic, y: Dyadic, z: Dyadic) {
+            prop_assert_eq!((x + y) + z, x + (y + z));
+            prop_assert_eq!((x * y) * z, x * (y * z));
+            prop_assert_eq!(x * (y + z), x * y + x * z);
+            prop_assert_eq!(x - y + y, x);
+        }
+
+        #[test]
+        fn simplify_is_idempotent(n in any::<i64>(), k in 0..70u32) {
+            let x = Dyadic::simplify(n, k);
+            prop_assert_eq!(Dyadic::simplify(x.0, x.1), x);
+            prop_assert!(x.1 == 0 || x.0 % 2 != 0);
+        }
+
+        #[test]
+        fn roottwo_ring_axioms(
+            x in quads(small()),
+            y in quads(small()),
+            z in quads(small()),
+        ) {
+            let (x, y, z): (RootTwo<i64>, RootTwo<i64>, RootTwo<i64>) = (x, y, z);
+            prop_assert_eq!((x * y) * z, x * (y * z));
+            prop_assert_eq!(x * (y + z), x * y + x * z);
+            prop_assert_eq!(x * y, y * x);
+        }
+
+        #[test]
+        fn droottwo_ring_axioms(x: RootTwo<Dyadic>, y: RootTwo<Dyadic>, z: RootTwo<Dyadic>) {
+            prop_assert_eq!((x + y) + z, x + (y + z));
+            prop_assert_eq!((x * y) * z, x * (y * z));
+            prop_assert_eq!(x * (y + z), x * y + x * z);
+        }
+
+        #[test]
+        fn adj2_is_a_homomorphism(x: RootTwo<Dyadic>, y: RootTwo<Dyadic>, z: DOmega, w: DOmega) {
+            prop_assert_eq!((x + y).adj2(), x.adj2() + y.adj2());
+            prop_assert_eq!((x * y).adj2(), x.adj2() * y.adj2());
+            prop_assert_eq!((z * w).adj2(), z.adj2() * w.adj2());
+            prop_assert_eq!(x.adj2().adj2(), x);
+        }
+
+        #[test]
+        fn zomega_distributes(x in omegas(small()), y in omegas(small()), z in omegas(small())) {
+            let (x, y, z): (ZOmega, ZOmega, ZOmega) = (x, y, z);
+            prop_assert_eq!(x * (y + z), x * y + x * z);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Zero-copy archives via rkyv. Every ring type derives `Archive`,
+//! `Serialize` and `Deserialize`, and each archived type compares equal to
+//! the value it was made from. A table such as `Vec<RootTwo<Dyadic>>` can be
+//! written once with `rkyv::to_bytes` and read back in place with
+//! `rkyv::access`, converting single entries to native values as needed.
+
+use crate::{dyadic::ArchivedDyadic, quad::ArchivedQuad, Dyadic, Quad};
+
+impl From<&ArchivedDyadic<i64>> for Dyadic {
+    fn from(val: &ArchivedDyadic<i64>) -> Self {
+        Dyadic(val.0.to_native(), val.1.to_native())
+    }
+}
+
+impl<const D: i64> From<&ArchivedQuad<i64, D>> for Quad<i64, D> {
+    fn from(val: &ArchivedQuad<i64, D>) -> Self {
+        Quad(val.0.to_native(), val.1.to_native())
+    }
+}
+
+impl<const D: i64> From<&ArchivedQuad<Dyadic, D>> for Quad<Dyadic, D> {
+    fn from(val: &ArchivedQuad<Dyadic, D>) -> Self {
+        Quad(Dyadic::from(&val.0), Dyadic::from(&val.1))
+    }
+}
+
+#[cfg(test)]
+mod archive_tests {
+    use super::*;
+    use crate::{Omega, RootTwo, ZOmega};
+    use rkyv::{rancor::Error, Archived};
+
+    #[test]
+    fn tables_in_place() {
+        let table: Vec<RootTwo<Dyadic>> = (0..100)
+            .map(|i| RootTwo::new(Dyadic::new(i - 50, 3), Dyadic::new(3 * i, 1)))
+            .collect();
+        let bytes = rkyv::to_bytes::<Error>(&table).unwrap();
+        let archived = rkyv::access::<Archived<Vec<RootTwo<Dyadic>>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), table.len());
+        for (x, y) in archived.iter().zip(&table) {
+            assert!(*x == *y);
+            assert_eq!(RootTwo::<Dyadic>::from(x), *y);
+        }
+        let back: Vec<RootTwo<Dyadic>> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert_eq!(back, table);
+    }
+
+    #[test]
+    fn other_rings_round_trip() {
+        let z: ZOmega = Omega(1, -2, 3, i64::MIN);
+        let bytes = rkyv::to_bytes::<Error>(&z).unwrap();
+        let archived = rkyv::access::<Archived<ZOmega>, Error>(&bytes).unwrap();
+        assert!(*archived == z);
+        assert_eq!(rkyv::deserialize::<ZOmega, Error>(archived).unwrap(), z);
+        let x = RootTwo::new(7, -5);
+        let bytes = rkyv::to_bytes::<Error>(&x).unwrap();
+        let archived = rkyv::access::<Archived<RootTwo<i64>>, Error>(&bytes).unwrap();
+        assert_eq!(RootTwo::from(archived), x);
+    }
+}
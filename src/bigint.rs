@@ -0,0 +1,330 @@
+//! Arbitrary-precision coefficients via [`BigInt`], for computations whose
+//! numerators or denominator exponents outgrow `i64`.
+
+use std::ops;
+
+use num::{BigInt, Integer, One, Signed, ToPrimitive, Zero};
+
+use crate::{
+    encoding::{read_varint, write_varint},
+    quad::{round_to_f64, round_to_odd},
+    ring::rounddiv,
+    Adj, Adj2, CommutativeRing, DecodeError, Dyadic, Encode, EuclideanDomain, QRootTwo, Quad, Ring,
+    RootTwo, ToLatex,
+};
+
+impl Ring for BigInt {}
+impl CommutativeRing for BigInt {}
+
+impl EuclideanDomain for BigInt {
+    /// Saturates at `u64::MAX`, which only matters for comparing ranks of
+    /// integers wider than 64 bits.
+    fn rank(&self) -> u64 {
+        u64::try_from(self.abs()).unwrap_or(u64::MAX)
+    }
+
+    fn div_rem(self, rhs: BigInt) -> (BigInt, BigInt) {
+        num::Integer::div_rem(&self, &rhs)
+    }
+}
+
+impl EuclideanDomain for RootTwo<BigInt> {
+    /// Saturates at `u64::MAX`, like the rank of `BigInt`.
+    fn rank(&self) -> u64 {
+        self.clone().norm().rank()
+    }
+
+    fn div_rem(self, rhs: RootTwo<BigInt>) -> (RootTwo<BigInt>, RootTwo<BigInt>) {
+        let norm = rhs.clone().norm();
+        if norm.is_zero() {
+            panic!("attempted to divide by zero");
+        }
+        let Quad(x, y) = self.clone() * rhs.clone().adj2();
+        let q = Quad(rounddiv(x, &norm), rounddiv(y, &norm));
+        let r = self - q.clone() * rhs;
+        (q, r)
+    }
+}
+
+impl Adj for BigInt {
+    fn adj(self) -> Self {
+        self
+    }
+}
+
+impl ToLatex for BigInt {
+    fn to_latex(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// A varint holding twice the number of magnitude bytes, plus one if
+/// negative, then the magnitude itself in little-endian order.
+impl Encode for BigInt {
+    fn encode(&self, out: &mut Vec<u8>) {
+        let (sign, magnitude) = self.to_bytes_le();
+        let len = if self.is_zero() {
+            0
+        } else {
+            magnitude.len() as u64
+        };
+        write_varint(out, len << 1 | (sign == num::bigint::Sign::Minus) as u64);
+        if len > 0 {
+            out.extend(magnitude);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let header = read_varint(input)?;
+        let len = usize::try_from(header >> 1).map_err(|_| DecodeError::Overflow)?;
+        if len > input.len() {
+            return Err(DecodeError::Truncated);
+        }
+        let (magnitude, rest) = input.split_at(len);
+        *input = rest;
+        let x = BigInt::from_bytes_le(num::bigint::Sign::Plus, magnitude);
+        Ok(if header & 1 == 1 { -x } else { x })
+    }
+}
+
+impl ops::Mul<i64> for Dyadic<BigInt> {
+    type Output = Dyadic<BigInt>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        self * BigInt::from(rhs)
+    }
+}
+
+impl From<Dyadic> for Dyadic<BigInt> {
+    fn from(val: Dyadic) -> Self {
+        Dyadic(BigInt::from(val.0), val.1)
+    }
+}
+
+impl<const D: i64> From<Quad<i64, D>> for Quad<BigInt, D> {
+    fn from(val: Quad<i64, D>) -> Self {
+        Quad(BigInt::from(val.0), BigInt::from(val.1))
+    }
+}
+
+impl<const D: i64> From<Quad<Dyadic, D>> for Quad<Dyadic<BigInt>, D> {
+    fn from(val: Quad<Dyadic, D>) -> Self {
+        Quad(Dyadic::from(val.0), Dyadic::from(val.1))
+    }
+}
+
+impl From<RootTwo<BigInt>> for RootTwo<Dyadic<BigInt>> {
+    fn from(val: RootTwo<BigInt>) -> Self {
+        Quad(Dyadic::from(val.0), Dyadic::from(val.1))
+    }
+}
+
+/// Conversion to binary floating point of any precision, for the numerical
+/// bounds that exact algorithms need beyond what `f64` can hold. The result
+/// is a [`Dyadic<BigInt>`], which represents every such float exactly.
+pub trait ToReal {
+    /// The nearest number with at most `precision` significant bits, with
+    /// ties to even, and with no bound on the exponent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `precision` is zero.
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt>;
+}
+
+/// `(a + b√2)/c` for `c > 0`, rounded to `precision` bits by way of two more
+/// bits rounded to odd.
+fn round_to_real(a: BigInt, b: BigInt, c: &BigInt, precision: u32) -> Dyadic<BigInt> {
+    assert!(precision > 0, "precision must be positive");
+    let Some((m, e)) = round_to_odd(a, b, c, precision as u64 + 2) else {
+        return Dyadic::zero();
+    };
+    let (negative, m) = (m.is_negative(), m.abs());
+    let low = (&m & BigInt::from(3)).to_u8().unwrap();
+    let mut m = m >> 2u32;
+    if low == 3 || (low == 2 && m.is_odd()) {
+        m += 1;
+    }
+    let m = if negative { -m } else { m };
+    let e = e + 2;
+    if e >= 0 {
+        Dyadic::new(m << e, 0)
+    } else {
+        Dyadic::new(m, u32::try_from(-e).expect("exponent overflows u32"))
+    }
+}
+
+impl ToReal for Dyadic {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        Dyadic::<BigInt>::from(*self).to_real(precision)
+    }
+}
+
+impl ToReal for Dyadic<BigInt> {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        let c = BigInt::one() << self.1;
+        round_to_real(self.0.clone(), BigInt::zero(), &c, precision)
+    }
+}
+
+impl ToReal for RootTwo<i64> {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        RootTwo::<BigInt>::from(*self).to_real(precision)
+    }
+}
+
+impl ToReal for RootTwo<Dyadic> {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        let (a, b, k) = self.common_denom();
+        round_to_real(a, b, &(BigInt::one() << k), precision)
+    }
+}
+
+impl ToReal for QRootTwo {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        let (a, b, c) = self.common_denom();
+        round_to_real(a, b, &c, precision)
+    }
+}
+
+impl ToReal for RootTwo<BigInt> {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        round_to_real(self.0.clone(), self.1.clone(), &BigInt::one(), precision)
+    }
+}
+
+impl ToReal for RootTwo<Dyadic<BigInt>> {
+    fn to_real(&self, precision: u32) -> Dyadic<BigInt> {
+        let (a, b, k) = self.clone().common_denom();
+        round_to_real(a, b, &(BigInt::one() << k), precision)
+    }
+}
+
+impl RootTwo<Dyadic<BigInt>> {
+    /// The numerators `(a, b)` of `self` over a common denominator `2ᵏ`.
+    fn common_denom(self) -> (BigInt, BigInt, u32) {
+        let Quad(Dyadic(a, j), Dyadic(b, k)) = self;
+        let e = j.max(k);
+        (a << (e - j), b << (e - k), e)
+    }
+}
+
+/// Correctly rounded, like the conversion from `RootTwo<i64>`.
+impl From<RootTwo<BigInt>> for f64 {
+    fn from(val: RootTwo<BigInt>) -> Self {
+        round_to_f64(val.0, val.1, &BigInt::one())
+    }
+}
+
+/// Correctly rounded, like the conversion from `RootTwo<Dyadic>`.
+impl From<RootTwo<Dyadic<BigInt>>> for f64 {
+    fn from(val: RootTwo<Dyadic<BigInt>>) -> Self {
+        let (a, b, k) = val.common_denom();
+        round_to_f64(a, b, &(BigInt::one() << k))
+    }
+}
+
+#[cfg(test)]
+mod bigint_tests {
+    use super::*;
+    use num::pow::Pow;
+    #[test]
+    fn arithmetic_past_i64() {
+        let lambda = RootTwo::<BigInt>::from(RootTwo::new(1, 1));
+        // λ⁶⁰ has coefficients around 10²³, well beyond i64
+        let big = lambda.clone().pow(60);
+        assert!(big.0 > BigInt::from(i64::MAX));
+        let conj = RootTwo::<BigInt>::from(RootTwo::new(-1, 1)).pow(60);
+        assert_eq!(&big * &conj, RootTwo::one());
+        assert_eq!(big.clone().norm(), BigInt::one());
+        assert_eq!(big.clone() - big, RootTwo::zero());
+    }
+
+    #[test]
+    fn big_dyadics() {
+        let x = Dyadic::<BigInt>::from(Dyadic(3, 2));
+        let tiny = Dyadic(BigInt::one(), 200);
+        let sum = x.clone() + tiny.clone();
+        assert_eq!(sum.1, 200);
+        assert_eq!(sum - tiny, x);
+        let y = RootTwo::<Dyadic<BigInt>>::from(RootTwo::new(Dyadic(1, 1), Dyadic(-1, 3)));
+        assert_eq!(
+            y.clone() * y,
+            RootTwo::from(RootTwo::new(Dyadic(9, 5), Dyadic(-1, 3)))
+        );
+    }
+
+    #[test]
+    fn gcd_past_i64() {
+        // λ⁶⁰·(3 + √2) and λ⁶¹·(3 - √2)·(3 + √2) share the factor λ⁶⁰·(3 + √2)
+        let lambda = RootTwo::<BigInt>::from(RootTwo::new(1, 1));
+        let p = RootTwo::<BigInt>::from(RootTwo::new(3, 1));
+        let x = lambda.clone().pow(60) * p.clone();
+        let y = lambda.pow(61) * p.clone() * p.clone().adj2();
+        let (g, s, t) = x.clone().ext_gcd(y.clone());
+        assert_eq!(g.rank(), 7);
+        assert_eq!(s * x.clone() + t * y.clone(), g.clone());
+        assert_eq!(x.clone().div_rem(g.clone()).1, RootTwo::zero());
+        assert_eq!(y.div_rem(g).1, RootTwo::zero());
+        assert_eq!(x.clone().gcd(RootTwo::zero()), x);
+    }
+
+    #[test]
+    fn real_approximations() {
+        // 11 = 0b1011 is halfway between 10 and 12 at three bits
+        assert_eq!(Dyadic(11, 0).to_real(3), Dyadic::from(Dyadic(12, 0)));
+        assert_eq!(Dyadic(-13, 4).to_real(2), Dyadic::from(Dyadic(-3, 2)));
+        assert_eq!(Dyadic(5, 3).to_real(10), Dyadic::from(Dyadic(5, 3)));
+        assert_eq!(RootTwo::<i64>::zero().to_real(7), Dyadic::zero());
+        // n/2ᵏ is the nearest to √2 when (2n - 1)² < 8·4ᵏ < (2n + 1)²
+        let Dyadic(n, k) = RootTwo::new(0, 1).to_real(200);
+        assert_eq!(n.bits(), 200);
+        let target = BigInt::from(8) << (2 * k);
+        let (below, above): (BigInt, BigInt) = (&n * 2 - 1, &n * 2 + 1);
+        assert!(&below * &below < target && target < &above * &above);
+        // λ⁻¹⁰⁰ = (1 - √2)¹⁰⁰ is far below the precision of its coefficients
+        let lambda = RootTwo::<BigInt>::from(RootTwo::new(1, 1));
+        let tiny = lambda.clone().adj2().pow(100);
+        let Dyadic(m, _) = tiny.to_real(64);
+        assert_eq!(m.bits(), 64);
+        let product = tiny.to_real(300) * lambda.pow(100).to_real(300);
+        assert_eq!(product.to_real(64), Dyadic::from(Dyadic(1, 0)));
+        for x in [
+            RootTwo::new(Dyadic(-4_478_554_083, 3), Dyadic(3_166_815_962, 3)),
+            RootTwo::new(Dyadic(7, 70), Dyadic(-1, 0)),
+        ] {
+            let nearest = <Dyadic>::try_from(f64::from(x)).unwrap();
+            assert_eq!(x.to_real(53), Dyadic::from(nearest));
+        }
+        let q = QRootTwo::new(num::Rational64::new(1, 3), num::Rational64::new(0, 1));
+        assert_eq!(q.to_real(4), Dyadic::from(Dyadic(11, 5)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn real_without_precision() {
+        RootTwo::new(1, 1).to_real(0);
+    }
+
+    #[test]
+    fn conversions_and_display() {
+        let x = Dyadic::<BigInt>::from(Dyadic(-7, 5));
+        assert_eq!(f64::from(x.clone()), -7.0 / 32.0);
+        assert_eq!(x.to_string(), "-7/2^5");
+        let z = RootTwo::<BigInt>::from(RootTwo::new(3, -2));
+        assert_eq!(f64::from(z.clone()), f64::from(RootTwo::new(3, -2)));
+        assert_eq!(z.to_string(), "3 - 2√2");
+        assert_eq!(z.to_latex(), "3 - 2\\sqrt{2}");
+    }
+
+    #[test]
+    fn encode_bigints() {
+        assert_eq!(BigInt::zero().to_bytes(), [1, 0]);
+        assert_eq!(BigInt::from(-300).to_bytes(), [1, 5, 0x2c, 0x01]);
+        let x = RootTwo::new(
+            Dyadic::new(BigInt::one() << 100u32, 3),
+            Dyadic::new(-(BigInt::one() << 70u32) + 1, 200),
+        );
+        assert_eq!(RootTwo::<Dyadic<BigInt>>::from_bytes(&x.to_bytes()), Ok(x));
+        assert_eq!(BigInt::from_bytes(&[1, 7, 1]), Err(DecodeError::Truncated));
+    }
+}
@@ -0,0 +1,275 @@
+use num::{integer::Roots, Integer, Rational64};
+
+use crate::{QRootTwo, RootTwo};
+
+/// A simple continued fraction `[a₀; a₁, a₂, ...]`, expanded lazily from its
+/// first complete quotient. It is finite exactly when the value is rational;
+/// for a quadratic irrational it is eventually periodic, but the period can
+/// be very long, so it is only found on request.
+#[derive(Debug, Clone)]
+pub struct ContinuedFraction {
+    /// The complete quotient `(p + √d)/q`, or `p/q` when `d` is zero.
+    p: i128,
+    d: i128,
+    q: i128,
+    /// `⌊√d⌋`
+    root: i128,
+}
+
+impl ContinuedFraction {
+    /// The partial quotients before the period, which are all of them for a
+    /// rational.
+    pub fn head(&self) -> Vec<i64> {
+        self.steps()
+            .take_while(|&(_, state)| !self.is_reduced(state))
+            .map(|(a, _)| a)
+            .collect()
+    }
+
+    /// The period, or `None` if it is longer than `max_len` or the expansion
+    /// overflows. It is empty exactly when the value is rational.
+    pub fn period(&self, max_len: usize) -> Option<Vec<i64>> {
+        if self.d == 0 {
+            return Some(Vec::new());
+        }
+        // a quadratic irrational is purely periodic exactly from its first
+        // reduced complete quotient
+        let mut steps = self
+            .steps()
+            .skip_while(|&(_, state)| !self.is_reduced(state));
+        let (a, start) = steps.next()?;
+        let mut period = vec![a];
+        for (a, state) in steps {
+            if state == start {
+                return Some(period);
+            }
+            if period.len() == max_len {
+                return None;
+            }
+            period.push(a);
+        }
+        None
+    }
+
+    /// The partial quotients, which go on forever for irrationals. They end
+    /// early if a term doesn't fit an `i64` or the expansion overflows.
+    pub fn terms(&self) -> impl Iterator<Item = i64> + '_ {
+        self.steps().map(|(a, _)| a)
+    }
+
+    /// The convergents `pₙ/qₙ`, ending once they overflow. They are
+    /// alternately below and above the value, so consecutive convergents
+    /// bracket it with error at most `1/(qₙqₙ₊₁)`.
+    pub fn convergents(&self) -> impl Iterator<Item = Rational64> + '_ {
+        // (pₙ₋₁, qₙ₋₁) and (pₙ₋₂, qₙ₋₂)
+        let mut state = Some(((1i64, 0i64), (0i64, 1i64)));
+        self.terms().map_while(move |a| {
+            let ((p1, q1), (p2, q2)) = state?;
+            let p = a.checked_mul(p1)?.checked_add(p2)?;
+            let q = a.checked_mul(q1)?.checked_add(q2)?;
+            state = Some(((p, q), (p1, q1)));
+            Some(Rational64::new_raw(p, q))
+        })
+    }
+
+    /// Each partial quotient with the complete quotient `(p, q)` it came from.
+    fn steps(&self) -> impl Iterator<Item = (i64, (i128, i128))> + '_ {
+        let mut state = Some((self.p, self.q));
+        std::iter::from_fn(move || {
+            let current = state.take()?;
+            let (a, next) = self.step(current)?;
+            state = Some(next);
+            Some((a, current))
+        })
+    }
+
+    /// The partial quotient of `(p, q)` and the next complete quotient.
+    ///
+    /// For a rational this is the Euclidean algorithm. Otherwise `x = a + 1/x'`
+    /// gives `p' = aq - p` and `q' = (d - p'²)/q`, which is exact since
+    /// `q | d - p²` is kept throughout.
+    fn step(&self, (p, q): (i128, i128)) -> Option<(i64, (i128, i128))> {
+        if self.d == 0 {
+            if q == 0 {
+                return None;
+            }
+            let (a, r) = p.div_mod_floor(&q);
+            return Some((i64::try_from(a).ok()?, (q, r)));
+        }
+        // ⌊(p + √d)/q⌋, using that √d is irrational
+        let top = p.checked_add(self.root)?;
+        let a = if q > 0 {
+            Integer::div_floor(&top, &q)
+        } else {
+            -Integer::div_floor(&top, &-q) - 1
+        };
+        let p = a.checked_mul(q)?.checked_sub(p)?;
+        let q = self.d.checked_sub(p.checked_mul(p)?)? / q;
+        Some((i64::try_from(a).ok()?, (p, q)))
+    }
+
+    /// Whether `(p + √d)/q` is reduced, that is `x > 1` and `-1 < x̄ < 0`.
+    fn is_reduced(&self, (p, q): (i128, i128)) -> bool {
+        self.d != 0
+            && q > 0
+            && p <= self.root
+            && p.saturating_add(q) > self.root
+            && q <= p + self.root
+    }
+
+    /// The expansion of the rational `p/q`.
+    fn rational(p: i128, q: i128) -> ContinuedFraction {
+        ContinuedFraction {
+            p,
+            d: 0,
+            q,
+            root: 0,
+        }
+    }
+
+    /// The expansion of `(p + √d)/q` for a nonsquare `d > 0`, scaled first so
+    /// that `q | d - p²`, or `None` if that overflows.
+    fn quadratic(mut p: i128, mut d: i128, mut q: i128) -> Option<ContinuedFraction> {
+        if d.checked_sub(p.checked_mul(p)?)? % q != 0 {
+            p = p.checked_mul(q.abs())?;
+            d = d.checked_mul(q.checked_mul(q)?)?;
+            q = q.checked_mul(q.abs())?;
+        }
+        let root = d.sqrt();
+        Some(ContinuedFraction { p, d, q, root })
+    }
+}
+
+impl RootTwo<i64> {
+    /// The continued fraction of `a + b√2`, or `None` if `2b²` overflows an
+    /// `i128`.
+    pub fn continued_fraction(self) -> Option<ContinuedFraction> {
+        let (a, b) = (self.0 as i128, self.1 as i128);
+        let d = b.checked_mul(b)?.checked_mul(2)?;
+        match b.signum() {
+            0 => Some(ContinuedFraction::rational(a, 1)),
+            1 => ContinuedFraction::quadratic(a, d, 1),
+            _ => ContinuedFraction::quadratic(-a, d, -1),
+        }
+    }
+}
+
+impl QRootTwo {
+    /// The continued fraction of `r + s√2`, or `None` if the denominators are
+    /// so large that the exact computation overflows an `i128`.
+    pub fn continued_fraction(self) -> Option<ContinuedFraction> {
+        let (r, s) = (self.0, self.1);
+        // r + s√2 == (a + b√2)/c
+        let c = (*r.denom() as i128).lcm(&(*s.denom() as i128));
+        let a = *r.numer() as i128 * (c / *r.denom() as i128);
+        let b = *s.numer() as i128 * (c / *s.denom() as i128);
+        let d = b.checked_mul(b)?.checked_mul(2)?;
+        match b.signum() {
+            0 => Some(ContinuedFraction::rational(a, c)),
+            1 => ContinuedFraction::quadratic(a, d, c),
+            _ => ContinuedFraction::quadratic(-a, d, -c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod contfrac_tests {
+    use super::*;
+
+    #[test]
+    fn quadratic_irrationals() {
+        let root_two = RootTwo::new(0, 1).continued_fraction().unwrap();
+        assert_eq!(root_two.head(), [1]);
+        assert_eq!(root_two.period(10), Some(vec![2]));
+        let lambda = RootTwo::new(1, 1).continued_fraction().unwrap();
+        assert_eq!((lambda.head(), lambda.period(10)), (vec![], Some(vec![2])));
+        let half = QRootTwo::new(Rational64::new(0, 1), Rational64::new(1, 2));
+        let cf = half.continued_fraction().unwrap();
+        assert_eq!(cf.terms().take(6).collect::<Vec<_>>(), [0, 1, 2, 2, 2, 2]);
+        for x in [
+            RootTwo::new(3, -1),
+            RootTwo::new(-7, 5),
+            RootTwo::new(100, -71),
+        ] {
+            let value = f64::from(x);
+            let cf = x.continued_fraction().unwrap();
+            assert!(!cf.period(100).unwrap().is_empty());
+            let last = cf.convergents().take(25).last().unwrap();
+            assert!((*last.numer() as f64 / *last.denom() as f64 - value).abs() < 1e-9);
+        }
+        let q = QRootTwo::new(Rational64::new(-1, 3), Rational64::new(-2, 5));
+        let approx = q
+            .continued_fraction()
+            .unwrap()
+            .convergents()
+            .take(25)
+            .last()
+            .unwrap();
+        let value = -1.0 / 3.0 - 0.4 * 2f64.sqrt();
+        assert!((*approx.numer() as f64 / *approx.denom() as f64 - value).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rationals_terminate() {
+        let cf = RootTwo::new(-3, 0).continued_fraction().unwrap();
+        assert_eq!((cf.head(), cf.period(0)), (vec![-3], Some(vec![])));
+        let q = QRootTwo::new(Rational64::new(-43, 19), Rational64::new(0, 1));
+        let cf = q.continued_fraction().unwrap();
+        assert_eq!(cf.head(), [-3, 1, 2, 1, 4]);
+        assert_eq!(cf.convergents().last(), Some(Rational64::new(-43, 19)));
+    }
+
+    #[test]
+    fn convergents_bracket_the_value() {
+        let cf = RootTwo::new(0, 1).continued_fraction().unwrap();
+        let convergents: Vec<_> = cf.convergents().take(5).collect();
+        assert_eq!(
+            convergents,
+            [(1, 1), (3, 2), (7, 5), (17, 12), (41, 29)].map(|(p, q)| Rational64::new(p, q))
+        );
+        let two = Rational64::from_integer(2);
+        for pair in convergents.windows(2) {
+            let (x, y) = (pair[0], pair[1]);
+            assert!((x * x - two) * (y * y - two) < Rational64::from_integer(0));
+        }
+        // the convergents of √2 stop before overflowing
+        assert!(cf.convergents().count() > 40);
+    }
+
+    #[test]
+    fn long_periods() {
+        // √2·(10⁸ + 7) has a period of millions of terms
+        let x = RootTwo::new(0, 100_000_007);
+        let cf = x.continued_fraction().unwrap();
+        assert_eq!(cf.head(), [141_421_366]);
+        assert_eq!(cf.period(1 << 16), None);
+        let last = cf.convergents().take(4).last().unwrap();
+        let value = f64::from(x);
+        assert!((*last.numer() as f64 / *last.denom() as f64 - value).abs() / value < 1e-12);
+        // preperiodic terms and scaling are found without the period
+        let q = QRootTwo::new(Rational64::new(5, 7), Rational64::new(-3, 11));
+        let cf = q.continued_fraction().unwrap();
+        let head = cf.head();
+        let period = cf.period(1000).unwrap();
+        let terms: Vec<_> = cf.terms().take(head.len() + 2 * period.len()).collect();
+        let expected: Vec<_> = head.iter().chain(&period).chain(&period).copied().collect();
+        assert_eq!(terms, expected);
+    }
+
+    #[test]
+    fn overflow() {
+        assert!(RootTwo::new(0, i64::MIN).continued_fraction().is_none());
+        let max = Rational64::new(1, i64::MAX);
+        assert!(QRootTwo::new(max, Rational64::new(1, i64::MAX - 1))
+            .continued_fraction()
+            .is_none());
+        // the first partial quotient of this doesn't fit an i64
+        let cf = RootTwo::new(i64::MAX, i64::MAX)
+            .continued_fraction()
+            .unwrap();
+        assert_eq!(cf.terms().next(), None);
+        let cf = RootTwo::new(1, i64::MAX / 2).continued_fraction().unwrap();
+        assert_eq!(cf.terms().next(), Some(6_521_908_912_666_391_105));
+        assert_eq!(cf.terms().take(100).count(), 100);
+    }
+}
@@ -0,0 +1,273 @@
+use std::ops;
+
+use num::{Complex, One, Zero};
+
+use crate::{Adj, Adj2, DOmega, Dyadic, Omega, RootTwo, ZI};
+
+/// A complex number `re + im·i` whose parts live in any of the crate's real
+/// rings, e.g. `Cplx<RootTwo<Dyadic>>`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Cplx<T>(pub(crate) T, pub(crate) T);
+
+impl<T> Cplx<T> {
+    pub fn new(re: T, im: T) -> Cplx<T> {
+        Cplx(re, im)
+    }
+
+    pub fn re(self) -> T {
+        self.0
+    }
+
+    pub fn im(self) -> T {
+        self.1
+    }
+
+    /// The complex conjugate. `conj(a + bi) == a - bi`
+    pub fn conj(self) -> Cplx<T>
+    where
+        T: ops::Neg<Output = T>,
+    {
+        Cplx(self.0, -self.1)
+    }
+
+    /// The squared modulus `re² + im²`, which lies in the underlying ring.
+    pub fn norm_sqr(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T> + Copy,
+    {
+        self.0 * self.0 + self.1 * self.1
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####         Traits for Cplx         ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Cplx<T> {
+    type Output = Cplx<T>;
+    fn add(self, rhs: Cplx<T>) -> Self::Output {
+        Cplx(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Cplx<T> {
+    type Output = Cplx<T>;
+    fn sub(self, rhs: Cplx<T>) -> Self::Output {
+        Cplx(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Cplx<T> {
+    type Output = Cplx<T>;
+    fn neg(self) -> Self::Output {
+        Cplx(-self.0, -self.1)
+    }
+}
+
+impl<T: ops::AddAssign> ops::AddAssign for Cplx<T> {
+    fn add_assign(&mut self, rhs: Cplx<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<T: ops::SubAssign> ops::SubAssign for Cplx<T> {
+    fn sub_assign(&mut self, rhs: Cplx<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl<T> ops::Mul for Cplx<T>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+{
+    type Output = Cplx<T>;
+    fn mul(self, rhs: Cplx<T>) -> Self::Output {
+        Cplx(
+            self.0 * rhs.0 - self.1 * rhs.1,
+            self.0 * rhs.1 + self.1 * rhs.0,
+        )
+    }
+}
+
+impl<T: ops::Mul<i64, Output = T>> ops::Mul<i64> for Cplx<T> {
+    type Output = Cplx<T>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Cplx(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<T> ops::MulAssign for Cplx<T>
+where
+    Cplx<T>: ops::Mul<Output = Cplx<T>> + Clone,
+{
+    fn mul_assign(&mut self, rhs: Cplx<T>) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<'a, T> ops::Add for &'a Cplx<T>
+where
+    &'a T: ops::Add<Output = T>,
+{
+    type Output = Cplx<T>;
+    fn add(self, rhs: &'a Cplx<T>) -> Self::Output {
+        Cplx(&self.0 + &rhs.0, &self.1 + &rhs.1)
+    }
+}
+
+impl<'a, T> ops::Sub for &'a Cplx<T>
+where
+    &'a T: ops::Sub<Output = T>,
+{
+    type Output = Cplx<T>;
+    fn sub(self, rhs: &'a Cplx<T>) -> Self::Output {
+        Cplx(&self.0 - &rhs.0, &self.1 - &rhs.1)
+    }
+}
+
+impl<'a, T> ops::Neg for &'a Cplx<T>
+where
+    &'a T: ops::Neg<Output = T>,
+{
+    type Output = Cplx<T>;
+    fn neg(self) -> Self::Output {
+        Cplx(-&self.0, -&self.1)
+    }
+}
+
+impl<'a, T> ops::Mul for &'a Cplx<T>
+where
+    &'a T: ops::Mul<Output = T>,
+    T: ops::Add<Output = T> + ops::Sub<Output = T>,
+{
+    type Output = Cplx<T>;
+    fn mul(self, rhs: &'a Cplx<T>) -> Self::Output {
+        Cplx(
+            &self.0 * &rhs.0 - &self.1 * &rhs.1,
+            &self.0 * &rhs.1 + &self.1 * &rhs.0,
+        )
+    }
+}
+
+impl<T: ops::MulAssign<i64>> ops::MulAssign<i64> for Cplx<T> {
+    fn mul_assign(&mut self, rhs: i64) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+    }
+}
+
+impl<T: Adj2> Adj2 for Cplx<T> {
+    fn adj2(self) -> Self {
+        Cplx(self.0.adj2(), self.1.adj2())
+    }
+}
+
+impl<T: Zero> Zero for Cplx<T> {
+    fn zero() -> Self {
+        Cplx(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<T> One for Cplx<T>
+where
+    T: Zero + One + PartialEq,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+    fn one() -> Self {
+        Cplx(T::one(), T::zero())
+    }
+
+    fn is_one(&self) -> bool {
+        self.0.is_one() && self.1.is_zero()
+    }
+}
+
+impl<T: Adj + ops::Neg<Output = T>> Adj for Cplx<T> {
+    fn adj(self) -> Self {
+        Cplx(self.0.adj(), -self.1.adj())
+    }
+}
+
+impl<T: Into<f64>> From<Cplx<T>> for Complex<f64> {
+    fn from(val: Cplx<T>) -> Self {
+        Complex::new(val.0.into(), val.1.into())
+    }
+}
+
+impl From<ZI> for Cplx<i64> {
+    fn from(val: ZI) -> Self {
+        Cplx(val.0, val.1)
+    }
+}
+
+impl From<DOmega> for Cplx<RootTwo<Dyadic>> {
+    /// Splits `a + bω + cω² + dω³` into real and imaginary parts using
+    /// `ω = (1 + i)/√2 = (1 + i)√2/2`.
+    fn from(val: DOmega) -> Self {
+        let Omega(a, b, c, d) = val;
+        let half = Dyadic(1, 1);
+        Cplx(
+            RootTwo::new(a, (b - d) * half),
+            RootTwo::new(c, (b + d) * half),
+        )
+    }
+}
+
+impl From<Cplx<RootTwo<Dyadic>>> for DOmega {
+    /// Recombines the parts using `√2 = ω - ω³` and `i√2 = ω + ω³`.
+    fn from(val: Cplx<RootTwo<Dyadic>>) -> Self {
+        let (re, im) = (val.0, val.1);
+        Omega(re.0, re.1 + im.1, im.0, im.1 - re.1)
+    }
+}
+
+#[cfg(test)]
+mod cplx_tests {
+    use super::*;
+    #[test]
+    fn basic_arithmetic_cplx() {
+        let first = Cplx(RootTwo::new(1, 1), RootTwo::new(0, 1));
+        let second = Cplx(RootTwo::new(2, 0), RootTwo::new(1, -1));
+        assert_eq!(first + second, Cplx(RootTwo::new(3, 1), RootTwo::new(1, 0)));
+        assert_eq!(
+            first - second,
+            Cplx(RootTwo::new(-1, 1), RootTwo::new(-1, 2))
+        );
+        // (1 + √2 + √2i)(2 + (1 - √2)i)
+        assert_eq!(
+            first * second,
+            Cplx(RootTwo::new(4, 1), RootTwo::new(-1, 2))
+        );
+    }
+
+    #[test]
+    fn conj_and_norm_sqr() {
+        let x = Cplx(RootTwo::new(1, 1), RootTwo::new(0, 1));
+        assert_eq!(x.conj(), Cplx(RootTwo::new(1, 1), RootTwo::new(0, -1)));
+        assert_eq!(x.norm_sqr(), RootTwo::new(5, 2));
+        assert_eq!(x * x.conj(), Cplx(x.norm_sqr(), RootTwo::new(0, 0)));
+    }
+
+    #[test]
+    fn domega_round_trip() {
+        let x = Omega(Dyadic(1, 1), Dyadic(3, 0), Dyadic(-1, 2), Dyadic(1, 0));
+        let split = Cplx::from(x);
+        assert_eq!(DOmega::from(split), x);
+        let approx: Complex<f64> = x.into();
+        let split_approx: Complex<f64> = split.into();
+        assert!((approx - split_approx).norm() < 1e-12);
+    }
+}
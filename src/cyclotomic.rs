@@ -0,0 +1,302 @@
+use std::{f64::consts::PI, ops};
+
+use num::{Complex, One, Zero};
+
+use crate::{Adj, Eisenstein, Omega, ZOmega, ZI};
+
+/// An element `c₀ + c₁ζ + ... + cₖζᵏ` of ℤ[ζ], where `ζ = e^(2πi/N)` is a
+/// primitive `N`-th root of unity.
+///
+/// Coefficients are stored in the power basis `1, ζ, ..., ζ^(φ(N)-1)`, so
+/// every entry from index `φ(N)` onward is zero. Arithmetic reduces modulo the
+/// `N`-th cyclotomic polynomial, which keeps that representation unique. For
+/// `N = 8` this is the same ring as [`ZOmega`], which remains the faster
+/// choice there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Cyclotomic<const N: usize>(pub(crate) [i64; N]);
+
+/// The coefficients of the `n`-th cyclotomic polynomial, lowest degree first.
+///
+/// Computed as `(xⁿ - 1) / ∏ Φ_d(x)` over the proper divisors `d` of `n`.
+pub(crate) fn cyclotomic_poly(n: usize) -> Vec<i64> {
+    let mut poly = vec![0; n + 1];
+    poly[0] = -1;
+    poly[n] = 1;
+    for d in (1..n).filter(|&d| n.is_multiple_of(d)) {
+        poly = div_monic(&poly, &cyclotomic_poly(d));
+    }
+    poly
+}
+
+/// Exact quotient of `num` by the monic polynomial `den`.
+fn div_monic(num: &[i64], den: &[i64]) -> Vec<i64> {
+    let m = den.len() - 1;
+    let mut rem = num.to_vec();
+    let mut quot = vec![0; num.len() - m];
+    for i in (m..num.len()).rev() {
+        let c = rem[i];
+        quot[i - m] = c;
+        for (j, d) in den.iter().enumerate() {
+            rem[i - m + j] -= c * d;
+        }
+    }
+    quot
+}
+
+impl<const N: usize> Cyclotomic<N> {
+    /// Builds `Σ coeffs[k]·ζᵏ`, reducing it into the power basis.
+    pub fn new(coeffs: [i64; N]) -> Cyclotomic<N> {
+        let mut x = Cyclotomic(coeffs);
+        x.reduce();
+        x
+    }
+
+    /// The generator `ζ`.
+    pub fn zeta() -> Cyclotomic<N> {
+        let mut coeffs = [0; N];
+        coeffs[1 % N] = 1;
+        Cyclotomic::new(coeffs)
+    }
+
+    /// The degree `φ(N)` of ℤ[ζ] over ℤ.
+    pub fn degree() -> usize {
+        cyclotomic_poly(N).len() - 1
+    }
+
+    pub fn coeffs(self) -> [i64; N] {
+        self.0
+    }
+
+    /// The complex conjugate, sending `ζ` to `ζ⁻¹ = ζ^(N-1)`.
+    pub fn conj(self) -> Cyclotomic<N> {
+        let mut coeffs = [0; N];
+        for (k, c) in self.0.iter().enumerate() {
+            coeffs[(N - k) % N] += c;
+        }
+        Cyclotomic::new(coeffs)
+    }
+
+    /// The field norm, i.e. the product of the `φ(N)` Galois conjugates
+    /// `ζ ↦ ζᵏ` for `k` coprime to `N`.
+    pub fn norm(self) -> i64 {
+        let mut product = Cyclotomic::one();
+        for k in (1..=N).filter(|&k| num::integer::gcd(k, N) == 1) {
+            let mut coeffs = [0; N];
+            for (j, c) in self.0.iter().enumerate() {
+                coeffs[(j * k) % N] += c;
+            }
+            product *= Cyclotomic::new(coeffs);
+        }
+        product.0[0]
+    }
+
+    fn reduce(&mut self) {
+        let phi = cyclotomic_poly(N);
+        let m = phi.len() - 1;
+        for i in (m..N).rev() {
+            let c = self.0[i];
+            if c != 0 {
+                for (j, p) in phi.iter().enumerate() {
+                    self.0[i - m + j] -= c * p;
+                }
+            }
+        }
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####      Traits for Cyclotomic      ####
+// #######                           #######
+// #########################################
+
+impl<const N: usize> ops::Add for Cyclotomic<N> {
+    type Output = Cyclotomic<N>;
+    fn add(self, rhs: Cyclotomic<N>) -> Self::Output {
+        Cyclotomic(std::array::from_fn(|k| self.0[k] + rhs.0[k]))
+    }
+}
+
+impl<const N: usize> ops::Sub for Cyclotomic<N> {
+    type Output = Cyclotomic<N>;
+    fn sub(self, rhs: Cyclotomic<N>) -> Self::Output {
+        Cyclotomic(std::array::from_fn(|k| self.0[k] - rhs.0[k]))
+    }
+}
+
+impl<const N: usize> ops::Neg for Cyclotomic<N> {
+    type Output = Cyclotomic<N>;
+    fn neg(self) -> Self::Output {
+        Cyclotomic(self.0.map(|c| -c))
+    }
+}
+
+impl<const N: usize> ops::AddAssign for Cyclotomic<N> {
+    fn add_assign(&mut self, rhs: Cyclotomic<N>) {
+        for (c, r) in self.0.iter_mut().zip(rhs.0) {
+            *c += r;
+        }
+    }
+}
+
+impl<const N: usize> ops::SubAssign for Cyclotomic<N> {
+    fn sub_assign(&mut self, rhs: Cyclotomic<N>) {
+        for (c, r) in self.0.iter_mut().zip(rhs.0) {
+            *c -= r;
+        }
+    }
+}
+
+impl<const N: usize> ops::Mul for Cyclotomic<N> {
+    type Output = Cyclotomic<N>;
+    fn mul(self, rhs: Cyclotomic<N>) -> Self::Output {
+        // multiply modulo ζᴺ = 1 first, then reduce by the cyclotomic polynomial
+        let mut coeffs = [0; N];
+        for (i, a) in self.0.iter().enumerate() {
+            for (j, b) in rhs.0.iter().enumerate() {
+                coeffs[(i + j) % N] += a * b;
+            }
+        }
+        Cyclotomic::new(coeffs)
+    }
+}
+
+impl<const N: usize> ops::Mul<i64> for Cyclotomic<N> {
+    type Output = Cyclotomic<N>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Cyclotomic(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<const N: usize> ops::MulAssign for Cyclotomic<N> {
+    fn mul_assign(&mut self, rhs: Cyclotomic<N>) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const N: usize> ops::MulAssign<i64> for Cyclotomic<N> {
+    fn mul_assign(&mut self, rhs: i64) {
+        for c in self.0.iter_mut() {
+            *c *= rhs;
+        }
+    }
+}
+
+impl<const N: usize> Zero for Cyclotomic<N> {
+    fn zero() -> Self {
+        Cyclotomic([0; N])
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.iter().all(|&c| c == 0)
+    }
+}
+
+impl<const N: usize> One for Cyclotomic<N> {
+    fn one() -> Self {
+        Cyclotomic::new(std::array::from_fn(|k| (k == 0) as i64))
+    }
+
+    fn is_one(&self) -> bool {
+        *self == Cyclotomic::one()
+    }
+}
+
+impl<const N: usize> Adj for Cyclotomic<N> {
+    fn adj(self) -> Self {
+        self.conj()
+    }
+}
+
+impl<const N: usize> From<Cyclotomic<N>> for Complex<f64> {
+    fn from(val: Cyclotomic<N>) -> Self {
+        val.0
+            .iter()
+            .enumerate()
+            .map(|(k, &c)| Complex::from_polar(c as f64, 2.0 * PI * k as f64 / N as f64))
+            .sum()
+    }
+}
+
+impl From<ZOmega> for Cyclotomic<8> {
+    fn from(val: ZOmega) -> Self {
+        Cyclotomic([val.0, val.1, val.2, val.3, 0, 0, 0, 0])
+    }
+}
+
+impl From<Cyclotomic<8>> for ZOmega {
+    fn from(val: Cyclotomic<8>) -> Self {
+        Omega(val.0[0], val.0[1], val.0[2], val.0[3])
+    }
+}
+
+impl From<ZI> for Cyclotomic<4> {
+    fn from(val: ZI) -> Self {
+        Cyclotomic([val.0, val.1, 0, 0])
+    }
+}
+
+impl From<Eisenstein<i64>> for Cyclotomic<3> {
+    fn from(val: Eisenstein<i64>) -> Self {
+        Cyclotomic([val.0, val.1, 0])
+    }
+}
+
+#[cfg(test)]
+mod cyclotomic_tests {
+    use super::*;
+    #[test]
+    fn cyclotomic_polys() {
+        assert_eq!(cyclotomic_poly(1), vec![-1, 1]);
+        assert_eq!(cyclotomic_poly(8), vec![1, 0, 0, 0, 1]);
+        assert_eq!(cyclotomic_poly(12), vec![1, 0, -1, 0, 1]);
+        assert_eq!(cyclotomic_poly(5), vec![1, 1, 1, 1, 1]);
+        assert_eq!(Cyclotomic::<16>::degree(), 8);
+    }
+
+    #[test]
+    fn zeta_has_order_n() {
+        fn check<const N: usize>() {
+            let zeta = Cyclotomic::<N>::zeta();
+            let one = Cyclotomic::one();
+            let mut power = zeta;
+            for _ in 1..N {
+                assert_ne!(power, one);
+                power *= zeta;
+            }
+            assert_eq!(power, one);
+        }
+        check::<3>();
+        check::<5>();
+        check::<12>();
+        check::<16>();
+    }
+
+    #[test]
+    fn agrees_with_zomega() {
+        let first = Omega(1, 2, 3, 4);
+        let second = Omega(5, -6, 7, 8);
+        let product = Cyclotomic::from(first) * Cyclotomic::from(second);
+        assert_eq!(ZOmega::from(product), first * second);
+        assert_eq!(
+            Cyclotomic::from(ZI::new(1, 2)) * Cyclotomic::from(ZI::new(3, -4)),
+            Cyclotomic::from(ZI::new(11, 2))
+        );
+    }
+
+    #[test]
+    fn conj_and_complex() {
+        let x = Cyclotomic::<5>::new([1, 2, 0, -1, 3]);
+        let z: Complex<f64> = x.into();
+        let zc: Complex<f64> = x.conj().into();
+        assert!((z.conj() - zc).norm() < 1e-12);
+        let norm: Complex<f64> = (x * x.conj()).into();
+        assert!(norm.im.abs() < 1e-12);
+        assert!((norm.re - z.norm_sqr()).abs() < 1e-9);
+    }
+}
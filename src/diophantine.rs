@@ -0,0 +1,306 @@
+//! The norm equation `t†t = ξ` over ℤ[ω], which is the number-theoretic
+//! step of Ross–Selinger style approximate synthesis.
+
+use num::{pow, pow::Pow, Zero};
+
+use crate::{
+    factor::{first_quadrant, split_prime},
+    numtheory, Adj, EuclideanDomain, Omega, Quad, RootTwo, ZOmega,
+};
+
+/// The real element `a + b√2` of ℤ[ω] as an element of ℤ[√2].
+fn real(x: ZOmega) -> RootTwo<i64> {
+    Quad(x.0, x.1)
+}
+
+/// A solution `t` of `t†t = π^e·u` for a prime `π` of ℤ[√2] and some unit
+/// `u`, or `None` if there is none.
+///
+/// Writing `p` for the rational prime below `π`:
+/// - `√2 = δ†δ·λ⁻¹` where `δ = 1 + ω`.
+/// - An inert `p ≡ 5 (mod 8)` is `x² + y²`, and `p ≡ 3 (mod 8)` is
+///   `x² + 2y²`. Either way `p = τ†τ` for `τ = gcd(p, r + s)`, where `s` is
+///   `i` or `√-2` respectively and `r² ≡ s² (mod p)`.
+/// - A split `p ≡ 1 (mod 8)` splits again in ℤ[ω], into `π = τ†τ` with
+///   `τ = gcd(π, r + i)` and `r² ≡ -1 (mod p)`.
+/// - A split `p ≡ 7 (mod 8)` stays prime in ℤ[ω], so only even powers of
+///   `π = π†` are norms.
+fn solve_prime_power(pi: RootTwo<i64>, e: u32) -> Option<ZOmega> {
+    let tau = if pi.1 == 0 {
+        // p is inert, and pi = ±p
+        let p = pi.0.unsigned_abs();
+        let (s, minus_s2) = if p % 8 == 5 {
+            (Omega(0, 0, 1, 0), p - 1)
+        } else {
+            (Omega(0, 1, 0, 1), p - 2)
+        };
+        let r = numtheory::sqrt_mod_p(minus_s2, p)? as i64;
+        ZOmega::from(Quad(p as i64, 0)).gcd(s + Omega(r, 0, 0, 0))
+    } else {
+        let p = pi.norm().unsigned_abs();
+        match p % 8 {
+            2 => Omega(1, 1, 0, 0),
+            1 => {
+                let r = numtheory::sqrt_mod_p(p - 1, p)? as i64;
+                ZOmega::from(pi).gcd(Omega(r, 0, 1, 0))
+            }
+            _ if e.is_multiple_of(2) => return Some(pow::pow(ZOmega::from(pi), e as usize / 2)),
+            _ => return None,
+        }
+    };
+    Some(pow::pow(tau, e as usize))
+}
+
+/// Finds `t` in ℤ[ω] with `t†t = ξ`, or returns `None` if there is none.
+///
+/// Such `t` can only exist when `ξ` is doubly positive, i.e. both `ξ` and
+/// `ξ•` are positive, or zero. The equation is solved one prime factor of
+/// `ξ` at a time, and the product is corrected by a unit at the end. The
+/// only expensive step is factoring the norm of `ξ`, which always succeeds
+/// once it fits in an `i64`; a larger norm also gives `None`.
+pub fn solve_norm_equation(xi: RootTwo<i64>) -> Option<ZOmega> {
+    solve_norm_equation_within(xi, u64::MAX)
+}
+
+/// [`solve_norm_equation`] within an effort budget, which gives up with
+/// `None` once factoring the norm of `ξ` takes more than `effort` steps of
+/// Pollard's rho. Synthesis only needs some candidate `ξ` to succeed, so it
+/// can move on from one whose norm is hard to factor.
+pub fn solve_norm_equation_within(xi: RootTwo<i64>, effort: u64) -> Option<ZOmega> {
+    if xi.is_zero() {
+        return Some(ZOmega::zero());
+    }
+    if xi.sign() <= 0 || xi.conj().sign() <= 0 {
+        return None;
+    }
+    xi.wide_norm().and_then(|n| i64::try_from(n).ok())?;
+    let factors = xi.factor_within(effort)?;
+    let mut t: ZOmega = Omega(1, 0, 0, 0);
+    for &(pi, e) in factors.factors() {
+        t *= solve_prime_power(pi, e)?;
+    }
+    // t†t = ξ·u for a doubly positive unit u, which is an even power of λ
+    let (sign, k) = real(t.adj() * t).try_div(xi)?.unit_decomposition()?;
+    if sign < 0 || k % 2 != 0 {
+        return None;
+    }
+    let t = t * ZOmega::from(RootTwo::lambda().pow(-k / 2)?);
+    (t.adj() * t == ZOmega::from(xi)).then_some(t)
+}
+
+/// The solutions of `x² - 2y² = norm` with `x, y ≥ 0` in increasing order,
+/// for `norm = ±1`, as elements `x + y√2`. These are the powers `λ²ᵏ` and
+/// `λ²ᵏ⁺¹` respectively, and the iterator ends before they overflow.
+///
+/// # Panics
+///
+/// Panics if `norm` is not `±1`.
+pub fn pell_units(norm: i64) -> impl Iterator<Item = RootTwo<i64>> {
+    let first = match norm {
+        1 => RootTwo::new(1, 0),
+        -1 => RootTwo::lambda(),
+        _ => panic!("pell_units needs a norm of ±1"),
+    };
+    pell_orbit(first)
+}
+
+/// The elements `x·λ²ᵏ` for `k ≥ 0`, which all have the norm of `x`, ending
+/// before they overflow. Starting from each of [`fundamental_solutions`]
+/// enumerates every solution with nonnegative coefficients.
+pub fn pell_orbit(x: RootTwo<i64>) -> impl Iterator<Item = RootTwo<i64>> {
+    let lambda2 = RootTwo::new(3, 2);
+    std::iter::successors(Some(x), move |x| x.checked_mul(lambda2))
+}
+
+/// `x·y` moved into the first quadrant by [`first_quadrant`], or `None` on
+/// overflow. Reducing after every product keeps the coefficients near the
+/// square root of the norm, so only products whose norm overflows fail.
+fn mul_reduced(x: RootTwo<i64>, y: RootTwo<i64>) -> Option<RootTwo<i64>> {
+    Some(first_quadrant(x.checked_mul(y)?, RootTwo::new(3, 2)))
+}
+
+fn pow_reduced(x: RootTwo<i64>, e: u32) -> Option<RootTwo<i64>> {
+    (0..e).try_fold(RootTwo::new(1, 0), |acc, _| mul_reduced(acc, x))
+}
+
+/// One solution of `x² - 2y² = n` from each orbit under multiplication by
+/// `±λ²`, the units of norm `1`, sorted by `x`. Each has `x, y ≥ 0` and is
+/// the least such solution in its orbit; for `n = 0` the only solution is
+/// zero.
+///
+/// Solutions are products of the primes above each `p` dividing `n`: powers
+/// of `√2` for `p = 2`, even powers of `p` itself for `p ≡ ±3 (mod 8)`,
+/// and every split `πᵃ·π•ᵇ` with `a + b = e` for `p ≡ ±1 (mod 8)`. The sign
+/// of the norm is fixed by a factor of `λ`.
+pub fn fundamental_solutions(n: i64) -> Vec<RootTwo<i64>> {
+    if n == 0 {
+        return vec![RootTwo::zero()];
+    }
+    let mut solutions = vec![RootTwo::new(1, 0)];
+    for (p, e) in numtheory::factor(n.unsigned_abs()) {
+        let choices: Vec<RootTwo<i64>> = match p % 8 {
+            2 => pow_reduced(RootTwo::new(0, 1), e).into_iter().collect(),
+            3 | 5 if e % 2 == 0 => vec![RootTwo::new((p as i64).pow(e / 2), 0)],
+            3 | 5 => return Vec::new(),
+            _ => {
+                let pi = split_prime(p);
+                (0..=e)
+                    .filter_map(|a| {
+                        mul_reduced(pow_reduced(pi, a)?, pow_reduced(pi.conj(), e - a)?)
+                    })
+                    .collect()
+            }
+        };
+        solutions = solutions
+            .iter()
+            .flat_map(|&x| choices.iter().filter_map(move |&y| mul_reduced(x, y)))
+            .collect();
+    }
+    let mut solutions: Vec<RootTwo<i64>> = solutions
+        .into_iter()
+        .filter_map(|x| {
+            let x = if x.wide_norm() == Some(n.into()) {
+                x
+            } else {
+                x.checked_mul(RootTwo::lambda())?
+            };
+            Some(first_quadrant(x, RootTwo::new(3, 2)))
+        })
+        .collect();
+    solutions.sort_by_key(|x| (x.0, x.1));
+    solutions
+}
+
+#[cfg(test)]
+mod diophantine_tests {
+    use super::*;
+
+    fn check(xi: RootTwo<i64>) {
+        let t = solve_norm_equation(xi).unwrap();
+        assert_eq!(t.adj() * t, ZOmega::from(xi));
+    }
+
+    #[test]
+    fn solvable_norm_equations() {
+        // one of each kind of prime: 2 + √2 = √2·λ, 3 = 1² + 2·1², 5 = 1² + 2²,
+        // 49 = (3 + √2)²(3 - √2)² and 5 + 2√2 with norm 17
+        for xi in [
+            RootTwo::new(1, 0),
+            RootTwo::new(2, 1),
+            RootTwo::new(3, 0),
+            RootTwo::new(5, 0),
+            RootTwo::new(49, 0),
+            RootTwo::new(5, 2),
+            RootTwo::new(17, 0),
+            RootTwo::new(3, 2),
+        ] {
+            check(xi);
+        }
+        check(RootTwo::new(2, 0));
+        check(RootTwo::new(2 * 3 * 5 * 17 * 41, 0));
+        // every t†t is doubly positive and solvable
+        for t in [Omega(1, 1, 2, -3), Omega(123, -45, 67, 89)] {
+            check(real(t.adj() * t));
+        }
+        assert_eq!(solve_norm_equation(RootTwo::zero()), Some(ZOmega::zero()));
+    }
+
+    #[test]
+    fn large_norm_equations() {
+        // norms near 10¹², a prime ≡ 1 (mod 8) and 2 times a prime ≡ 1 (mod 8),
+        // where gcds in ℤ[ω] see norms beyond an i128
+        check(RootTwo::new(1_568_953, -676_764));
+        check(RootTwo::new(1_680_650, 588_129));
+        // inert p ≡ 5 (mod 8) and p ≡ 3 (mod 8) past 2³¹, with norms near 10¹⁹
+        check(RootTwo::new(3_000_000_037, 0));
+        check(RootTwo::new(3_000_000_019, 0));
+    }
+
+    #[test]
+    fn effort_budget() {
+        // the norm 40009² · 40129² needs some steps of Pollard's rho to split
+        let xi = RootTwo::new(40_009 * 40_129, 0);
+        assert_eq!(solve_norm_equation_within(xi, 1), None);
+        let t = solve_norm_equation_within(xi, 1_000_000).unwrap();
+        assert_eq!(t.adj() * t, ZOmega::from(xi));
+        // prime norms need no steps at all
+        let xi = RootTwo::new(1_568_953, -676_764);
+        assert!(solve_norm_equation_within(xi, 0).is_some());
+    }
+
+    #[test]
+    fn pell_equations() {
+        let plus: Vec<_> = pell_units(1).take(4).collect();
+        assert_eq!(plus, [Quad(1, 0), Quad(3, 2), Quad(17, 12), Quad(99, 70)]);
+        let minus: Vec<_> = pell_units(-1).take(3).collect();
+        assert_eq!(minus, [Quad(1, 1), Quad(7, 5), Quad(41, 29)]);
+        // λ, λ³, ..., λ⁴⁹ fit in an i64
+        assert_eq!(pell_units(-1).count(), 25);
+        assert!(pell_units(1).all(|x| x.inv() == Some(x.conj())));
+    }
+
+    #[test]
+    #[should_panic]
+    fn pell_units_other_norm() {
+        let _ = pell_units(2);
+    }
+
+    #[test]
+    fn fundamental_solutions_cover_every_solution() {
+        assert_eq!(fundamental_solutions(7), [Quad(3, 1), Quad(5, 3)]);
+        assert_eq!(fundamental_solutions(-7), [Quad(1, 2), Quad(5, 4)]);
+        assert_eq!(fundamental_solutions(1), [Quad(1, 0)]);
+        assert_eq!(fundamental_solutions(-1), [Quad(1, 1)]);
+        assert_eq!(fundamental_solutions(9), [Quad(3, 0)]);
+        assert!(fundamental_solutions(3).is_empty());
+        assert_eq!(fundamental_solutions(0), [Quad(0, 0)]);
+        // high prime powers, whose unreduced products overflow an i64
+        for (p, e) in [(7i64, 20u32), (41, 11), (23, 13), (-7, 21), (17, 15)] {
+            let n = p.pow(e);
+            let solutions = fundamental_solutions(n);
+            assert_eq!(solutions.len(), e as usize + 1, "{n}");
+            for x in solutions {
+                assert_eq!(x.wide_norm(), Some(n.into()));
+                assert!(x.0 >= 0 && x.1 >= 0);
+            }
+        }
+        assert_eq!(fundamental_solutions(1 << 62).len(), 1);
+        assert_eq!(fundamental_solutions(3i64.pow(38)), [Quad(3i64.pow(19), 0)]);
+        for n in -60..=60i64 {
+            let fundamental = fundamental_solutions(n);
+            for &x in &fundamental {
+                assert_eq!(x.norm(), n);
+            }
+            // brute force every small solution and find its orbit
+            for x in 0..300 {
+                for y in 0..300 {
+                    let z = RootTwo::new(x, y);
+                    if z.norm() == n && n != 0 {
+                        let start = first_quadrant(z, RootTwo::new(3, 2));
+                        assert!(fundamental.contains(&start), "{z:?}");
+                        assert!(pell_orbit(start).any(|w| w == z));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn unsolvable_norm_equations() {
+        // 7 = (3 + √2)(3 - √2) with both factors inert in ℤ[ω]
+        assert_eq!(solve_norm_equation(RootTwo::new(7, 0)), None);
+        assert_eq!(solve_norm_equation(RootTwo::new(3, 1)), None);
+        // the prime factor 27 - √2 appears once and has norm 727 ≡ 7 (mod 8)
+        assert_eq!(solve_norm_equation(RootTwo::new(1_000_003, 12_345)), None);
+        // not doubly positive
+        assert_eq!(solve_norm_equation(RootTwo::new(-1, 0)), None);
+        assert_eq!(solve_norm_equation(RootTwo::new(1, 1)), None);
+        // norms beyond an i64, even beyond an i128 for 2·i64::MIN²
+        assert_eq!(solve_norm_equation(RootTwo::new(i64::MAX, 1)), None);
+        assert_eq!(solve_norm_equation(RootTwo::new(i64::MAX, -1)), None);
+        assert_eq!(
+            solve_norm_equation(RootTwo::new(i64::MAX, i64::MIN / 2)),
+            None
+        );
+    }
+}
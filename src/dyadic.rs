@@ -0,0 +1,1635 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter,
+    num::IntErrorKind,
+    ops, str,
+};
+
+use num::{
+    pow, rational::Ratio, BigInt, BigRational, CheckedAdd, CheckedMul, CheckedSub, Integer, Num,
+    One, Signed, ToPrimitive, Zero,
+};
+
+use crate::{
+    quad::{round_scaled_f64, scale_f64},
+    ring::exact_sqrt,
+    Adj, DenomExp, EuclideanDomain, FromFloatError, FromRatioError, ParseRingError, ToLatex,
+    WholePart,
+};
+
+/// A dyadic rational `n / 2ᵏ`, stored as the numerator `n` and the exponent
+/// `k`. The numerator defaults to `i64`, but any signed integer type works,
+/// which makes room for `i128` or arbitrary precision once exponents grow.
+#[derive(Clone, Copy)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Dyadic<T = i64>(pub(crate) T, pub(crate) u32);
+
+// #########################################
+// #######                           #######
+// ####        Traits for Dyadic        ####
+// #######                           #######
+// #########################################
+
+impl<T: Integer> Dyadic<T> {
+    /// The dyadic `n / 2ᵏ` in canonical form, where the numerator is odd or
+    /// the exponent is zero. In particular zero is always `0 / 2⁰`.
+    pub fn new(n: T, k: u32) -> Dyadic<T> {
+        Dyadic::simplify(n, k)
+    }
+
+    pub fn numer(&self) -> &T {
+        &self.0
+    }
+
+    /// The exponent `k` of the denominator `2ᵏ`.
+    pub fn exponent(&self) -> u32 {
+        self.1
+    }
+
+    pub(crate) fn simplify(mut x: T, mut k: u32) -> Dyadic<T> {
+        // keep dividing by 2 while numerator is even
+        //
+        // TODO: consider adding an enum, then returning i64 if k is 0
+        while k > 0 && x.is_even() {
+            x = x / (T::one() + T::one());
+            k -= 1;
+        }
+        Dyadic(x, k)
+    }
+
+    /// The value rounded towards zero.
+    fn truncated(&self) -> T
+    where
+        T: Clone,
+    {
+        let mut n = self.0.clone();
+        for _ in 0..self.1 {
+            if n.is_zero() {
+                break;
+            }
+            n = n / (T::one() + T::one());
+        }
+        n
+    }
+
+    /// Division by `2ᵏ`, which is always exact.
+    pub fn div_pow2(self, k: u32) -> Dyadic<T> {
+        Dyadic::simplify(self.0, self.1 + k)
+    }
+
+    /// Both numerators over the larger of the two denominators.
+    fn align(self, rhs: Dyadic<T>) -> (T, T, u32)
+    where
+        T: ops::Shl<u32, Output = T>,
+    {
+        match self.1.cmp(&rhs.1) {
+            std::cmp::Ordering::Less => (self.0 << (rhs.1 - self.1), rhs.0, rhs.1),
+            std::cmp::Ordering::Equal => (self.0, rhs.0, self.1),
+            std::cmp::Ordering::Greater => (self.0, rhs.0 << (self.1 - rhs.1), self.1),
+        }
+    }
+
+    /// The exact decimal expansion, which always terminates since
+    /// `n/2ᵏ = n·5ᵏ/10ᵏ`. It has exactly `k` fractional digits in canonical
+    /// form, e.g. `0.75` for `3/2^2`.
+    pub fn to_decimal_string(&self) -> String
+    where
+        T: Clone + Into<BigInt>,
+    {
+        self.to_decimal_string_max(usize::MAX).0
+    }
+
+    /// The decimal expansion cut off after at most `max_digits` fractional
+    /// digits, rounding towards zero, together with whether any nonzero
+    /// digits were cut off.
+    pub fn to_decimal_string_max(&self, max_digits: usize) -> (String, bool)
+    where
+        T: Clone + Into<BigInt>,
+    {
+        let Dyadic(n, k) = Dyadic::new(self.0.clone().into(), self.1);
+        let k = k as usize;
+        let digits = (n.abs() * pow(BigInt::from(5), k)).to_string();
+        let digits = format!("{digits:0>width$}", width = k + 1);
+        let (whole, frac) = digits.split_at(digits.len() - k);
+        let keep = k.min(max_digits);
+        let sign = if n.is_negative() { "-" } else { "" };
+        let s = match keep {
+            0 => format!("{sign}{whole}"),
+            _ => format!("{sign}{whole}.{}", &frac[..keep]),
+        };
+        (s, keep < k)
+    }
+}
+
+impl<T> Dyadic<T>
+where
+    T: Integer + Signed + ops::Shl<u32, Output = T> + Clone,
+{
+    /// Whether `self` is `±2ʲ` for some integer `j`.
+    pub fn is_unit(&self) -> bool {
+        self.clone().inv().is_some()
+    }
+
+    /// The inverse of a unit `±2ʲ`, which is `±2⁻ʲ`.
+    pub fn inv(self) -> Option<Dyadic<T>> {
+        let Dyadic(n, k) = Dyadic::simplify(self.0, self.1);
+        if k > 0 {
+            if !n.abs().is_one() {
+                return None;
+            }
+            return Some(Dyadic(n.signum() << k, 0));
+        }
+        let (mut odd, mut j) = (n.abs(), 0);
+        while !odd.is_zero() && odd.is_even() {
+            odd = odd / (T::one() + T::one());
+            j += 1;
+        }
+        if !odd.is_one() {
+            return None;
+        }
+        Some(Dyadic(n.signum(), j))
+    }
+}
+
+/// `n / 2ˢ` rounded to the nearest integer, with ties away from zero.
+fn round_shift(n: i64, s: u32) -> i64 {
+    match s {
+        0 => n,
+        // |n / 2ˢ| < 1/2
+        65.. => 0,
+        _ => {
+            let half = 1i128 << (s - 1);
+            let m = ((n.unsigned_abs() as i128 + half) >> s) as i64;
+            m * n.signum()
+        }
+    }
+}
+
+impl Dyadic {
+    /// The greatest integer at most `self`.
+    pub fn floor(self) -> i64 {
+        // arithmetic shifts round towards negative infinity
+        self.0 >> self.1.min(63)
+    }
+
+    /// The least integer at least `self`.
+    pub fn ceil(self) -> i64 {
+        let exact = match self.1 {
+            0..=63 => self.0 as u64 & ((1 << self.1) - 1) == 0,
+            // otherwise |self| < 1
+            _ => self.0 == 0,
+        };
+        self.floor() + i64::from(!exact)
+    }
+
+    /// The integer part, rounding towards zero.
+    pub fn trunc(self) -> i64 {
+        if self.0 < 0 {
+            self.ceil()
+        } else {
+            self.floor()
+        }
+    }
+
+    /// The nearest integer, rounding ties away from zero like [`f64::round`].
+    pub fn round(self) -> i64 {
+        round_shift(self.0, self.1)
+    }
+
+    /// Multiplication by `2ᵏ` for either sign of `k`. This only moves the
+    /// exponent, and shifts the numerator once the result is an integer.
+    /// Returns `None` if the numerator or the exponent overflows.
+    pub fn mul_pow2(self, k: i32) -> Option<Dyadic> {
+        let Dyadic(n, j) = self;
+        if n == 0 {
+            return Some(Dyadic(0, 0));
+        }
+        if k < 0 {
+            return Some(Dyadic::simplify(n, j.checked_add(k.unsigned_abs())?));
+        }
+        let k = k as u32;
+        if k <= j {
+            Some(Dyadic::simplify(n, j - k))
+        } else {
+            let s = k - j;
+            let m = n.checked_shl(s).filter(|&m| m >> s == n)?;
+            Some(Dyadic(m, 0))
+        }
+    }
+
+    /// Splits `self` into its floor and the remaining fraction in `[0, 1)`,
+    /// which add back up to `self`. The fraction is the low `k` bits of the
+    /// numerator, or `None` if that needs more than 63 bits, which only
+    /// happens for negative values with an exponent of 64 or more.
+    pub fn split(self) -> Option<(i64, Dyadic)> {
+        let Dyadic(n, k) = Dyadic::simplify(self.0, self.1);
+        let frac = match k {
+            0..=63 => Dyadic::simplify((n as u64 & ((1 << k) - 1)) as i64, k),
+            _ if n >= 0 => self,
+            _ => return None,
+        };
+        Some((self.floor(), frac))
+    }
+
+    /// The nearest multiple of `2⁻ᵏ`, rounding ties away from zero. Values
+    /// already on that grid are unchanged.
+    pub fn round_to_exponent(self, k: u32) -> Dyadic {
+        if self.1 <= k {
+            return self;
+        }
+        Dyadic::simplify(round_shift(self.0, self.1 - k), k)
+    }
+
+    fn cmp_i64(self, n: i64) -> Ordering {
+        cmp_scaled(self.0, -(self.1 as i64), n, 0)
+    }
+
+    /// Compares with a float exactly, by writing every finite float as a
+    /// dyadic rational `m·2ᵉ`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is NaN.
+    pub fn cmp_f64(&self, x: f64) -> Ordering {
+        if let Some(ord) = cmp_nonfinite(x) {
+            return ord;
+        }
+        let (m, e) = float_parts(x);
+        cmp_scaled(self.0, -(self.1 as i64), m, e)
+    }
+
+    /// The sum or difference as an `i128` numerator over `2ᵏ`, with each
+    /// numerator shifted into place modulo `2¹²⁸`, and whether that lost
+    /// information.
+    ///
+    /// After simplifying both operands, at most one of them is shifted, and
+    /// the result is odd whenever one is. So the low 64 bits of the numerator
+    /// are always exact, and all of it is when the flag is clear.
+    fn wide_sum(self, rhs: Dyadic, subtract: bool) -> (Dyadic<i128>, bool) {
+        let (x, y) = (
+            Dyadic::simplify(self.0, self.1),
+            Dyadic::simplify(rhs.0, rhs.1),
+        );
+        let k = x.1.max(y.1);
+        let mut lost = false;
+        let mut shift = |n: i64, s: u32| match (n, s) {
+            (0, _) => 0,
+            // nonzero numerators up to n·2⁶⁴ fit in an i128
+            (_, 0..=64) => (n as i128) << s,
+            _ => {
+                lost = true;
+                0
+            }
+        };
+        let (a, b) = (shift(x.0, k - x.1), shift(y.0, k - y.1));
+        let (n, carry) = if subtract {
+            a.overflowing_sub(b)
+        } else {
+            a.overflowing_add(b)
+        };
+        (Dyadic::simplify(n, k), lost || carry)
+    }
+
+    /// The exact quotient `self / rhs`, or `None` if it is not dyadic or its
+    /// numerator overflows. Writing `rhs = m·2ʲ` with `m` odd, the quotient
+    /// is dyadic exactly when `m` divides the numerator of `self`.
+    pub fn try_div(self, rhs: Dyadic) -> Option<Dyadic> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let j = rhs.0.trailing_zeros();
+        let m = rhs.0 >> j;
+        if self.0.checked_rem(m)? != 0 {
+            return None;
+        }
+        let n = self.0.checked_div(m)?;
+        // self / rhs = n · 2^(rhs.1 - self.1 - j)
+        let k = self.1 as i64 + j as i64 - rhs.1 as i64;
+        if k >= 0 {
+            Some(Dyadic::simplify(n, k.try_into().ok()?))
+        } else {
+            let shift: u32 = (-k).try_into().ok()?;
+            let n = n.checked_mul(1i64.checked_shl(shift).filter(|&p| p > 0)?)?;
+            Some(Dyadic(n, 0))
+        }
+    }
+
+    /// The nonnegative square root, if `self` is the square of a dyadic
+    /// rational. This needs an even exponent and a square numerator.
+    pub fn sqrt(self) -> Option<Dyadic> {
+        let Dyadic(n, k) = Dyadic::simplify(self.0, self.1);
+        if k % 2 != 0 {
+            return None;
+        }
+        Some(Dyadic(exact_sqrt(n as i128)? as i64, k / 2))
+    }
+
+    pub fn is_square(self) -> bool {
+        self.sqrt().is_some()
+    }
+
+    /// Addition that returns `None` if the numerator of the sum overflows.
+    pub fn checked_add(self, rhs: Dyadic) -> Option<Dyadic> {
+        match self.wide_sum(rhs, false) {
+            (Dyadic(n, k), false) => Some(Dyadic(n.try_into().ok()?, k)),
+            _ => None,
+        }
+    }
+
+    /// Subtraction that returns `None` if the numerator of the difference
+    /// overflows.
+    pub fn checked_sub(self, rhs: Dyadic) -> Option<Dyadic> {
+        match self.wide_sum(rhs, true) {
+            (Dyadic(n, k), false) => Some(Dyadic(n.try_into().ok()?, k)),
+            _ => None,
+        }
+    }
+
+    /// Negation that returns `None` if the numerator is `i64::MIN`.
+    pub fn checked_neg(self) -> Option<Dyadic> {
+        Some(Dyadic(self.0.checked_neg()?, self.1))
+    }
+
+    /// Multiplication that returns `None` if the numerator or the exponent
+    /// overflows.
+    pub fn checked_mul(self, rhs: Dyadic) -> Option<Dyadic> {
+        let (a, b, j, k) = self.cancel_twos(rhs);
+        Some(Dyadic::simplify(a.checked_mul(b)?, j.checked_add(k)?))
+    }
+
+    /// Both factors with the powers of two in one numerator cancelled against
+    /// the other denominator, so that a product which fits never overflows
+    /// along the way. Returns the numerators and then the exponents.
+    fn cancel_twos(self, rhs: Dyadic) -> (i64, i64, u32, u32) {
+        if self.0 == 0 || rhs.0 == 0 {
+            return (0, 0, 0, 0);
+        }
+        let s = self.0.trailing_zeros().min(rhs.1);
+        let t = rhs.0.trailing_zeros().min(self.1);
+        (self.0 >> s, rhs.0 >> t, self.1 - t, rhs.1 - s)
+    }
+
+    /// Exponentiation by squaring that returns `None` on overflow.
+    pub fn checked_pow(self, mut power: u32) -> Option<Dyadic> {
+        let mut base = self;
+        let mut result = Dyadic::one();
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Addition in which the numerator wraps around at the boundary of `i64`.
+    pub fn wrapping_add(self, rhs: Dyadic) -> Dyadic {
+        let (Dyadic(n, k), _) = self.wide_sum(rhs, false);
+        Dyadic(n as i64, k)
+    }
+
+    /// Subtraction in which the numerator wraps around at the boundary of
+    /// `i64`.
+    pub fn wrapping_sub(self, rhs: Dyadic) -> Dyadic {
+        let (Dyadic(n, k), _) = self.wide_sum(rhs, true);
+        Dyadic(n as i64, k)
+    }
+
+    /// Multiplication in which the numerator wraps around at the boundary of
+    /// `i64` and the exponent at the boundary of `u32`.
+    pub fn wrapping_mul(self, rhs: Dyadic) -> Dyadic {
+        let (a, b, j, k) = self.cancel_twos(rhs);
+        Dyadic::simplify(a.wrapping_mul(b), j.wrapping_add(k))
+    }
+
+    /// Exponentiation by squaring with wrapping multiplication.
+    pub fn wrapping_pow(self, mut power: u32) -> Dyadic {
+        let mut base = self;
+        let mut result = Dyadic::one();
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.wrapping_mul(base);
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.wrapping_mul(base);
+            }
+        }
+        result
+    }
+
+    /// The wrapped sum, together with whether it overflowed.
+    pub fn overflowing_add(self, rhs: Dyadic) -> (Dyadic, bool) {
+        (self.wrapping_add(rhs), self.checked_add(rhs).is_none())
+    }
+
+    /// The wrapped difference, together with whether it overflowed.
+    pub fn overflowing_sub(self, rhs: Dyadic) -> (Dyadic, bool) {
+        (self.wrapping_sub(rhs), self.checked_sub(rhs).is_none())
+    }
+
+    /// The wrapped product, together with whether it overflowed.
+    pub fn overflowing_mul(self, rhs: Dyadic) -> (Dyadic, bool) {
+        (self.wrapping_mul(rhs), self.checked_mul(rhs).is_none())
+    }
+
+    /// The wrapped power, together with whether it overflowed.
+    pub fn overflowing_pow(self, power: u32) -> (Dyadic, bool) {
+        (self.wrapping_pow(power), self.checked_pow(power).is_none())
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T>> ops::Add for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn add(self, rhs: Dyadic<T>) -> Self::Output {
+        let (a, b, k) = self.align(rhs);
+        Dyadic::simplify(a + b, k)
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T>> ops::Sub for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn sub(self, rhs: Dyadic<T>) -> Self::Output {
+        let (a, b, k) = self.align(rhs);
+        Dyadic::simplify(a - b, k)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn neg(self) -> Self::Output {
+        Dyadic(-self.0, self.1)
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T> + Clone> ops::AddAssign for Dyadic<T> {
+    fn add_assign(&mut self, rhs: Dyadic<T>) {
+        *self = self.clone() + rhs;
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T> + Clone> ops::SubAssign for Dyadic<T> {
+    fn sub_assign(&mut self, rhs: Dyadic<T>) {
+        *self = self.clone() - rhs;
+    }
+}
+
+impl<T: Integer> ops::Mul for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Dyadic::simplify(self.0 * rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: Integer + Clone> ops::MulAssign for Dyadic<T> {
+    fn mul_assign(&mut self, rhs: Dyadic<T>) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<'a, T: Integer + ops::Shl<u32, Output = T> + Clone> ops::Add for &'a Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn add(self, rhs: &'a Dyadic<T>) -> Self::Output {
+        self.clone() + rhs.clone()
+    }
+}
+
+impl<'a, T: Integer + ops::Shl<u32, Output = T> + Clone> ops::Sub for &'a Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn sub(self, rhs: &'a Dyadic<T>) -> Self::Output {
+        self.clone() - rhs.clone()
+    }
+}
+
+impl<T: ops::Neg<Output = T> + Clone> ops::Neg for &Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl<'a, T: Integer + Clone> ops::Mul for &'a Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn mul(self, rhs: &'a Dyadic<T>) -> Self::Output {
+        self.clone() * rhs.clone()
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T>> ops::Add<T> for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn add(self, rhs: T) -> Self::Output {
+        self + Dyadic(rhs, 0)
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T>> ops::Sub<T> for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn sub(self, rhs: T) -> Self::Output {
+        self - Dyadic(rhs, 0)
+    }
+}
+
+impl<T: Integer> ops::Mul<T> for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Dyadic::simplify(self.0 * rhs, self.1)
+    }
+}
+
+impl<T: Integer + Clone> ops::MulAssign<T> for Dyadic<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl ops::Add<Dyadic> for i64 {
+    type Output = Dyadic;
+    fn add(self, rhs: Dyadic) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl ops::Sub<Dyadic> for i64 {
+    type Output = Dyadic;
+    fn sub(self, rhs: Dyadic) -> Self::Output {
+        -rhs + self
+    }
+}
+
+impl ops::Mul<Dyadic> for i64 {
+    type Output = Dyadic;
+    fn mul(self, rhs: Dyadic) -> Self::Output {
+        Dyadic::simplify(self * rhs.0, rhs.1)
+    }
+}
+
+impl ops::Div for Dyadic {
+    type Output = Dyadic;
+    /// The Euclidean quotient, which is exact whenever `rhs` is `±2ᵏ`.
+    fn div(self, rhs: Dyadic) -> Self::Output {
+        self.div_rem(rhs).0
+    }
+}
+
+impl ops::Rem for Dyadic {
+    type Output = Dyadic;
+    fn rem(self, rhs: Dyadic) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T>> Zero for Dyadic<T> {
+    fn zero() -> Self {
+        Dyadic(T::zero(), 0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl<T: Integer + Clone> One for Dyadic<T> {
+    fn one() -> Self {
+        Dyadic(T::one(), 0)
+    }
+
+    fn is_one(&self) -> bool {
+        let Dyadic(n, k) = Dyadic::simplify(self.0.clone(), self.1);
+        k == 0 && n.is_one()
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T>> iter::Sum for Dyadic<T> {
+    fn sum<I: Iterator<Item = Dyadic<T>>>(iter: I) -> Self {
+        iter.fold(Dyadic::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, T: Integer + ops::Shl<u32, Output = T> + Clone + 'a> iter::Sum<&'a Dyadic<T>>
+    for Dyadic<T>
+{
+    fn sum<I: Iterator<Item = &'a Dyadic<T>>>(iter: I) -> Self {
+        iter.fold(Dyadic::zero(), |acc, x| acc + x.clone())
+    }
+}
+
+impl<T: Integer + Clone> iter::Product for Dyadic<T> {
+    fn product<I: Iterator<Item = Dyadic<T>>>(iter: I) -> Self {
+        iter.fold(Dyadic::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a, T: Integer + Clone + 'a> iter::Product<&'a Dyadic<T>> for Dyadic<T> {
+    fn product<I: Iterator<Item = &'a Dyadic<T>>>(iter: I) -> Self {
+        iter.fold(Dyadic::one(), |acc, x| acc * x.clone())
+    }
+}
+
+impl<T: Integer + Clone> pow::Pow<u32> for Dyadic<T> {
+    type Output = Dyadic<T>;
+    fn pow(self, power: u32) -> Self::Output {
+        Dyadic::simplify(pow::pow(self.0, power as usize), self.1 * power)
+    }
+}
+
+impl<T> pow::Pow<i32> for Dyadic<T>
+where
+    T: Integer + Signed + ops::Shl<u32, Output = T> + Clone,
+{
+    type Output = Option<Dyadic<T>>;
+
+    /// Negative powers exist exactly when `self` is a unit `±2ʲ`, and are
+    /// `None` otherwise.
+    fn pow(self, power: i32) -> Self::Output {
+        if power >= 0 {
+            return Some(self.pow(power as u32));
+        }
+        Some(self.inv()?.pow(power.unsigned_abs()))
+    }
+}
+
+impl Num for Dyadic {
+    type FromStrRadixErr = ParseRingError;
+
+    /// Parses `n`, `n/d` or `n/2^k`, where `d` must be a power of two. Every
+    /// integer is written in the given radix.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        let (num, k) = match s.split_once('/') {
+            Some((num, den)) => (num, parse_exponent(den.trim(), radix)?),
+            None => (s, 0),
+        };
+        let num = i64::from_str_radix(num.trim(), radix)?;
+        Ok(Dyadic::simplify(num, k))
+    }
+}
+
+/// The exponent `k` of a denominator written as `2^k` or as the power of two
+/// itself, which may be too large for any primitive integer.
+fn parse_exponent(den: &str, radix: u32) -> Result<u32, ParseRingError> {
+    if let Some(k) = den.strip_prefix("2^") {
+        return Ok(u32::from_str_radix(k, radix)?);
+    }
+    let den = match u64::from_str_radix(den, radix) {
+        Ok(den) => BigInt::from(den),
+        Err(err) if *err.kind() == IntErrorKind::PosOverflow => {
+            BigInt::from_str_radix(den, radix).map_err(|_| ParseRingError::Int(err))?
+        }
+        Err(err) => return Err(err.into()),
+    };
+    match den.trailing_zeros() {
+        Some(k) if den.bits() == k + 1 => Ok(k as u32),
+        _ => Err(ParseRingError::NotDyadic),
+    }
+}
+
+impl str::FromStr for Dyadic {
+    type Err = ParseRingError;
+
+    /// Parses the forms printed by `Display`, such as `-7/2^5` or `3/4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Dyadic::from_str_radix(s, 10)
+    }
+}
+
+impl str::FromStr for Dyadic<BigInt> {
+    type Err = ParseRingError;
+
+    /// Parses the same forms as `Dyadic`, with a numerator of any size.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (num, k) = match s.split_once('/') {
+            Some((num, den)) => (num, parse_exponent(den.trim(), 10)?),
+            None => (s, 0),
+        };
+        let num = num.trim().parse().map_err(|_| ParseRingError::Malformed)?;
+        Ok(Dyadic::simplify(num, k))
+    }
+}
+
+impl CheckedAdd for Dyadic {
+    fn checked_add(&self, rhs: &Dyadic) -> Option<Dyadic> {
+        Dyadic::checked_add(*self, *rhs)
+    }
+}
+
+impl CheckedSub for Dyadic {
+    fn checked_sub(&self, rhs: &Dyadic) -> Option<Dyadic> {
+        Dyadic::checked_sub(*self, *rhs)
+    }
+}
+
+impl CheckedMul for Dyadic {
+    fn checked_mul(&self, rhs: &Dyadic) -> Option<Dyadic> {
+        Dyadic::checked_mul(*self, *rhs)
+    }
+}
+
+impl Signed for Dyadic {
+    fn abs(&self) -> Self {
+        Dyadic(self.0.abs(), self.1)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        let diff = *self - *other;
+        if diff.0 > 0 {
+            diff
+        } else {
+            Dyadic::zero()
+        }
+    }
+
+    fn signum(&self) -> Self {
+        Dyadic(self.0.signum(), 0)
+    }
+
+    fn is_positive(&self) -> bool {
+        self.0 > 0
+    }
+
+    fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+}
+
+impl<T: Integer + Clone> PartialEq for Dyadic<T> {
+    /// Compares values rather than representations, so `Dyadic(2, 1)`
+    /// equals `Dyadic(1, 0)`.
+    fn eq(&self, other: &Self) -> bool {
+        let x = Dyadic::simplify(self.0.clone(), self.1);
+        let y = Dyadic::simplify(other.0.clone(), other.1);
+        x.0 == y.0 && x.1 == y.1
+    }
+}
+
+impl<T: Integer + Clone> Eq for Dyadic<T> {}
+
+impl<T: Integer + Clone + Hash> Hash for Dyadic<T> {
+    /// Hashes the canonical form, so that equal values hash equally.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let Dyadic(n, k) = Dyadic::simplify(self.0.clone(), self.1);
+        n.hash(state);
+        k.hash(state);
+    }
+}
+
+/// Compares `a·2ˢ` with `b·2ᵗ`. Once the exponents differ by 64 or more the
+/// side with the larger exponent dominates unless it is zero, and otherwise
+/// the shift fits in an `i128`.
+pub(crate) fn cmp_scaled(a: i64, s: i64, b: i64, t: i64) -> Ordering {
+    if s < t {
+        return cmp_scaled(b, t, a, s).reverse();
+    }
+    if a == 0 {
+        0.cmp(&b)
+    } else if s - t >= 64 {
+        a.cmp(&0)
+    } else {
+        ((a as i128) << (s - t)).cmp(&(b as i128))
+    }
+}
+
+/// Writes a finite float as `m·2ᵉ` with `|m| < 2⁵³`.
+pub(crate) fn float_parts(x: f64) -> (i64, i64) {
+    let bits = x.to_bits();
+    let exponent = ((bits >> 52) & 0x7ff) as i64;
+    let fraction = (bits & ((1 << 52) - 1)) as i64;
+    let (m, e) = if exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), exponent - 1075)
+    };
+    (if x.is_sign_negative() { -m } else { m }, e)
+}
+
+/// Writes an approximate value for the alternate `Debug` form, honouring
+/// any precision given as in `{:#.3?}`.
+pub(crate) fn write_approx(f: &mut fmt::Formatter<'_>, x: f64) -> fmt::Result {
+    match f.precision() {
+        Some(p) => write!(f, "{x:.p$}"),
+        None => write!(f, "{x}"),
+    }
+}
+
+/// The order of a finite ring value relative to an infinite float.
+///
+/// # Panics
+///
+/// Panics if `x` is NaN.
+pub(crate) fn cmp_nonfinite(x: f64) -> Option<Ordering> {
+    if x.is_nan() {
+        panic!("attempted to compare with NaN");
+    }
+    x.is_infinite().then(|| 0.0.partial_cmp(&x).unwrap())
+}
+
+impl PartialOrd for Dyadic {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Dyadic {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_scaled(self.0, -(self.1 as i64), other.0, -(other.1 as i64))
+    }
+}
+
+impl PartialEq<i64> for Dyadic {
+    fn eq(&self, other: &i64) -> bool {
+        self.cmp_i64(*other) == Ordering::Equal
+    }
+}
+
+impl PartialEq<Dyadic> for i64 {
+    fn eq(&self, other: &Dyadic) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<i64> for Dyadic {
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        Some(self.cmp_i64(*other))
+    }
+}
+
+impl PartialOrd<Dyadic> for i64 {
+    fn partial_cmp(&self, other: &Dyadic) -> Option<Ordering> {
+        Some(other.cmp_i64(*self).reverse())
+    }
+}
+
+impl<T> Adj for Dyadic<T> {
+    fn adj(self) -> Self {
+        self
+    }
+}
+
+impl WholePart for Dyadic {
+    type Whole = i64;
+
+    fn from_whole(whole: i64) -> Self {
+        Dyadic(whole, 0)
+    }
+
+    fn whole_part(self) -> i64 {
+        self.floor()
+    }
+
+    fn frac_part(self) -> Option<Self> {
+        self.split().map(|(_, frac)| frac)
+    }
+}
+
+impl DenomExp for Dyadic {
+    fn denomexp(&self) -> u32 {
+        if self.0 == 0 {
+            return 0;
+        }
+        self.1.saturating_sub(self.0.trailing_zeros())
+    }
+}
+
+impl<T> From<T> for Dyadic<T> {
+    fn from(val: T) -> Self {
+        Dyadic(val, 0)
+    }
+}
+
+impl TryFrom<f64> for Dyadic {
+    type Error = FromFloatError;
+
+    /// The exact value of a finite float, which is always dyadic. Only
+    /// values outside `[-2⁶³, 2⁶³)` fail, since their numerators overflow.
+    fn try_from(x: f64) -> Result<Self, Self::Error> {
+        if !x.is_finite() {
+            return Err(FromFloatError::NotFinite);
+        }
+        let (m, e) = float_parts(x);
+        if e < 0 {
+            return Ok(Dyadic::new(m, (-e) as u32));
+        }
+        (e < 64)
+            .then(|| m << e)
+            .filter(|&n| n >> e == m)
+            .map(Dyadic::from)
+            .ok_or(FromFloatError::Overflow)
+    }
+}
+
+impl From<Dyadic> for BigRational {
+    fn from(val: Dyadic) -> Self {
+        Ratio::new(val.0.into(), BigInt::one() << val.1)
+    }
+}
+
+impl TryFrom<BigRational> for Dyadic {
+    type Error = FromRatioError;
+
+    /// The same value, if its reduced denominator is a power of two and its
+    /// numerator fits in an `i64`.
+    fn try_from(x: BigRational) -> Result<Self, Self::Error> {
+        let d = x.denom();
+        let k = d.trailing_zeros().unwrap_or(0);
+        if d.bits() != k + 1 {
+            return Err(FromRatioError::NotDyadic);
+        }
+        let n = x.numer().to_i64().ok_or(FromRatioError::Overflow)?;
+        let k = u32::try_from(k).map_err(|_| FromRatioError::Overflow)?;
+        Ok(Dyadic(n, k))
+    }
+}
+
+/// Correctly rounded, including in the subnormal range, whenever the
+/// numerator fits in an `i128`.
+impl<T: ToPrimitive> From<Dyadic<T>> for f64 {
+    fn from(val: Dyadic<T>) -> Self {
+        let e = -(val.1 as i64);
+        match val.0.to_i128() {
+            Some(n) => round_scaled_f64(n.into(), e),
+            None => scale_f64(val.0.to_f64().unwrap_or(f64::NAN), e),
+        }
+    }
+}
+
+impl<T: Integer + ToPrimitive + Clone> ToPrimitive for Dyadic<T> {
+    /// Truncates towards zero, like the conversions from primitive floats.
+    fn to_i64(&self) -> Option<i64> {
+        self.truncated().to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.truncated().to_u64()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(f64::from(self.clone()))
+    }
+}
+
+/// Prints `Dyadic(n, k)` like a derived implementation. The alternate form
+/// `{:#?}` keeps to one line and adds the value, as in `Dyadic(3, 2) ≈ 0.75`.
+impl<T: fmt::Debug + ToPrimitive> fmt::Debug for Dyadic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Dyadic({:?}, {:?})", self.0, self.1)?;
+        if f.alternate() {
+            let n = self.0.to_f64().unwrap_or(f64::NAN);
+            write!(f, " ≈ ")?;
+            write_approx(f, n / 2f64.powi(self.1 as i32))?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints `n/2^k`, or just `n` when `k` is zero. The alternate form `{:#}`
+/// writes the denominator out in full instead, as in `3/4`.
+impl<T: fmt::Display> fmt::Display for Dyadic<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.1 == 0 {
+            write!(f, "{}", self.0)
+        } else if f.alternate() {
+            write!(f, "{}/{}", self.0, BigInt::one() << self.1)
+        } else {
+            write!(f, "{}/2^{}", self.0, self.1)
+        }
+    }
+}
+
+impl<T: fmt::Display> ToLatex for Dyadic<T> {
+    /// `\frac{n}{2^{k}}` with the sign pulled out, or just `n` when `k` is
+    /// zero.
+    fn to_latex(&self) -> String {
+        let n = self.0.to_string();
+        match (self.1, n.strip_prefix('-')) {
+            (0, _) => n,
+            (k, Some(n)) => format!("-\\frac{{{n}}}{{2^{{{k}}}}}"),
+            (k, None) => format!("\\frac{{{n}}}{{2^{{{k}}}}}"),
+        }
+    }
+}
+
+impl<T: Integer + ops::Shl<u32, Output = T> + Clone> From<Dyadic<T>> for Ratio<T> {
+    fn from(val: Dyadic<T>) -> Self {
+        Ratio::new(val.0, T::one() << val.1)
+    }
+}
+
+#[cfg(test)]
+mod dyadic_tests {
+    use super::*;
+    use crate::RootTwo;
+    #[test]
+    fn big_rationals() {
+        let ratio = |n: i64, d: i64| BigRational::new(n.into(), d.into());
+        assert_eq!(BigRational::from(Dyadic(-7, 3)), ratio(-7, 8));
+        assert_eq!(BigRational::from(Dyadic(5, 0)), ratio(5, 1));
+        assert_eq!(<Dyadic>::try_from(ratio(6, 16)), Ok(Dyadic(3, 3)));
+        assert_eq!(<Dyadic>::try_from(ratio(0, 5)), Ok(Dyadic(0, 0)));
+        assert_eq!(
+            <Dyadic>::try_from(ratio(-1, 12)),
+            Err(FromRatioError::NotDyadic)
+        );
+        let huge = BigRational::from_integer(BigInt::one() << 70u32);
+        assert_eq!(<Dyadic>::try_from(huge), Err(FromRatioError::Overflow));
+        let tiny = BigRational::new(BigInt::one(), BigInt::one() << 200u32);
+        assert_eq!(<Dyadic>::try_from(tiny.clone()), Ok(Dyadic(1, 200)));
+        assert_eq!(BigRational::from(Dyadic(1, 200)), tiny);
+    }
+
+    #[test]
+    fn basic_add_dyadic() {
+        let first = Dyadic(3, 2);
+        let second = Dyadic(1, 2);
+        assert_eq!(first + second, Dyadic(1, 0));
+        assert_eq!(-Dyadic(3, 2), Dyadic(-3, 2));
+    }
+
+    #[test]
+    fn simplify_keeps_value() {
+        assert_eq!(Dyadic(1, 2) + Dyadic(0, 2), Dyadic(1, 2));
+        assert_eq!(Dyadic(3, 2) * Dyadic(2, 1), Dyadic(3, 2));
+        assert_eq!(Dyadic(-3, 2) + Dyadic(1, 2), Dyadic(-1, 1));
+        assert_eq!(Dyadic(1, 2) - Dyadic(1, 2), Dyadic(0, 0));
+    }
+
+    #[test]
+    fn into_float_works() {
+        assert_eq!(Into::<f64>::into(Dyadic(3, 2)), 0.75);
+    }
+
+    #[test]
+    fn whole_and_frac_parts() {
+        assert_eq!(Dyadic(7, 2).whole_part(), 1);
+        assert_eq!(Dyadic(7, 2).frac_part(), Some(Dyadic(3, 2)));
+        assert_eq!(Dyadic(-7, 2).whole_part(), -2);
+        assert_eq!(Dyadic(-7, 2).frac_part(), Some(Dyadic(1, 2)));
+        assert_eq!(Dyadic(-3, 0).whole_part(), -3);
+        assert_eq!(Dyadic(-3, 0).frac_part(), Some(Dyadic(0, 0)));
+        assert_eq!(Dyadic(-1, 70).whole_part(), -1);
+        assert_eq!(Dyadic(-1, 70).frac_part(), None);
+    }
+
+    #[test]
+    fn denomexp_ignores_representation() {
+        assert_eq!(Dyadic(3, 2).denomexp(), 2);
+        assert_eq!(Dyadic(6, 2).denomexp(), 1);
+        assert_eq!(Dyadic(-8, 2).denomexp(), 0);
+        assert_eq!(Dyadic(0, 5).denomexp(), 0);
+    }
+
+    #[test]
+    fn zero_and_one() {
+        assert!(Dyadic::<i64>::zero().is_zero());
+        assert!(Dyadic(0, 3).is_zero());
+        assert!(Dyadic::<i64>::one().is_one());
+        assert!(Dyadic(4, 2).is_one());
+        assert!(!Dyadic(1, 1).is_one());
+        assert_eq!(Dyadic(3, 2) * Dyadic::one() + Dyadic::zero(), Dyadic(3, 2));
+    }
+
+    #[test]
+    fn euclidean_division() {
+        assert_eq!(Dyadic(3, 0) / Dyadic(2, 0), Dyadic(3, 1));
+        assert_eq!(Dyadic(3, 0) % Dyadic(2, 0), Dyadic(0, 0));
+        assert_eq!(Dyadic(7, 0) / Dyadic(3, 0), Dyadic(2, 0));
+        assert_eq!(Dyadic(7, 0) % Dyadic(3, 0), Dyadic(1, 0));
+        let pairs = [
+            (Dyadic(7, 2), Dyadic(3, 1)),
+            (Dyadic(-45, 3), Dyadic(20, 0)),
+            (Dyadic(5, 7), Dyadic(-9, 4)),
+        ];
+        for (a, b) in pairs {
+            let (q, r) = a.div_rem(b);
+            assert_eq!(q * b + r, a);
+            assert!(r.rank() < b.rank());
+        }
+    }
+
+    #[test]
+    fn from_str_radix() {
+        assert_eq!(Dyadic::from_str_radix("-3/4", 10), Ok(Dyadic(-3, 2)));
+        assert_eq!(Dyadic::from_str_radix("6/4", 10), Ok(Dyadic(3, 1)));
+        assert_eq!(Dyadic::from_str_radix("ff/100", 16), Ok(Dyadic(255, 8)));
+        assert_eq!(Dyadic::from_str_radix("12", 10), Ok(Dyadic(12, 0)));
+        assert_eq!(
+            Dyadic::from_str_radix("1/3", 10),
+            Err(ParseRingError::NotDyadic)
+        );
+        assert!(matches!(
+            Dyadic::from_str_radix("x/2", 10),
+            Err(ParseRingError::Int(_))
+        ));
+        assert!(matches!(
+            Dyadic::from_str_radix("1/-4", 10),
+            Err(ParseRingError::Int(_))
+        ));
+        assert_eq!(Dyadic::from_str_radix("0/1", 10), Ok(Dyadic(0, 0)));
+        assert_eq!(
+            Dyadic::from_str_radix("1/0", 10),
+            Err(ParseRingError::NotDyadic)
+        );
+    }
+
+    #[test]
+    fn decimal_expansions() {
+        assert_eq!(Dyadic(3, 2).to_decimal_string(), "0.75");
+        assert_eq!(Dyadic(-5, 0).to_decimal_string(), "-5");
+        assert_eq!(Dyadic(-1, 1).to_decimal_string(), "-0.5");
+        assert_eq!(Dyadic(6, 3).to_decimal_string(), "0.75");
+        assert_eq!(Dyadic(-45, 4).to_decimal_string(), "-2.8125");
+        assert_eq!(Dyadic(1, 10).to_decimal_string(), "0.0009765625");
+        assert_eq!(Dyadic::<i128>(1, 100).to_decimal_string().len(), 102);
+        assert_eq!(
+            Dyadic(1, 10).to_decimal_string_max(5),
+            ("0.00097".to_string(), true)
+        );
+        assert_eq!(
+            Dyadic(-45, 4).to_decimal_string_max(2),
+            ("-2.81".to_string(), true)
+        );
+        assert_eq!(
+            Dyadic(-45, 4).to_decimal_string_max(0),
+            ("-2".to_string(), true)
+        );
+        assert_eq!(
+            Dyadic(3, 2).to_decimal_string_max(2),
+            ("0.75".to_string(), false)
+        );
+    }
+
+    #[test]
+    fn debug_with_values() {
+        assert_eq!(format!("{:?}", Dyadic(3, 2)), "Dyadic(3, 2)");
+        assert_eq!(format!("{:#?}", Dyadic(3, 2)), "Dyadic(3, 2) ≈ 0.75");
+        assert_eq!(format!("{:#.2?}", Dyadic(-1, 3)), "Dyadic(-1, 3) ≈ -0.12");
+    }
+
+    #[test]
+    fn latex_dyadic() {
+        assert_eq!(Dyadic(3, 2).to_latex(), "\\frac{3}{2^{2}}");
+        assert_eq!(Dyadic(-7, 15).to_latex(), "-\\frac{7}{2^{15}}");
+        assert_eq!(Dyadic(-5, 0).to_latex(), "-5");
+    }
+
+    #[test]
+    fn parse_printed_dyadics() {
+        assert_eq!("3/2^4".parse(), Ok(Dyadic(3, 4)));
+        assert_eq!("-5/8".parse(), Ok(Dyadic(-5, 3)));
+        assert_eq!(" 12 / 2^2 ".parse(), Ok(Dyadic(3, 0)));
+        assert_eq!("1/18446744073709551616".parse(), Ok(Dyadic(1, 64)));
+        assert_eq!(
+            "1/18446744073709551617".parse::<Dyadic>(),
+            Err(ParseRingError::NotDyadic)
+        );
+        for x in [Dyadic(3, 2), Dyadic(-7, 5), Dyadic(9, 0), Dyadic(1, 100)] {
+            assert_eq!(x.to_string().parse(), Ok(x));
+            assert_eq!(format!("{x:#}").parse(), Ok(x));
+        }
+    }
+
+    #[test]
+    fn signed_dyadic() {
+        assert_eq!(Dyadic(-3, 2).abs(), Dyadic(3, 2));
+        assert_eq!(Dyadic(-3, 2).signum(), Dyadic(-1, 0));
+        assert!(Dyadic(1, 60).is_positive());
+        assert!(!Dyadic::zero().is_negative());
+        assert_eq!(Dyadic(3, 1).abs_sub(&Dyadic(1, 0)), Dyadic(1, 1));
+        assert_eq!(Dyadic(1, 0).abs_sub(&Dyadic(3, 1)), Dyadic::zero());
+    }
+
+    #[test]
+    fn sum_and_product_dyadic() {
+        let xs = [Dyadic(1, 1), Dyadic(1, 2), Dyadic(1, 2), Dyadic(3, 0)];
+        assert_eq!(xs.iter().sum::<Dyadic>(), Dyadic(4, 0));
+        assert_eq!(xs.into_iter().sum::<Dyadic>(), Dyadic(4, 0));
+        assert_eq!(xs.iter().product::<Dyadic>(), Dyadic(3, 5));
+        assert_eq!(xs.into_iter().product::<Dyadic>(), Dyadic(3, 5));
+    }
+
+    #[test]
+    fn mixed_integer_arithmetic() {
+        assert_eq!(Dyadic(3, 2) + 1, Dyadic(7, 2));
+        assert_eq!(1 - Dyadic(3, 2), Dyadic(1, 2));
+        assert_eq!(2 * Dyadic(3, 2), Dyadic(3, 1));
+        assert_eq!(Dyadic(1, 1) - 1, Dyadic(-1, 1));
+    }
+
+    #[test]
+    fn from_integer() {
+        assert_eq!(Dyadic::from(-7), Dyadic(-7, 0));
+        let x: RootTwo<Dyadic> = 5.into();
+        assert_eq!(x, RootTwo::new(Dyadic::from(5), Dyadic::zero()));
+    }
+
+    #[test]
+    fn wide_numerators() {
+        let big = Dyadic::<i128>(1 << 100, 3);
+        assert_eq!(big * Dyadic(3, 2), Dyadic(3 << 95, 0));
+        assert_eq!(big + Dyadic(1, 1), Dyadic((1 << 98) + 1, 1));
+        assert!(Dyadic::<i128>(1 << 90, 90).is_one());
+        assert_eq!(f64::from(big), 2f64.powi(97));
+        assert_eq!(
+            Ratio::from(Dyadic::<i128>(-5, 100)),
+            Ratio::new(-5, 1 << 100)
+        );
+    }
+
+    #[test]
+    fn display_and_truncation() {
+        assert_eq!(Dyadic(3, 2).to_string(), "3/2^2");
+        assert_eq!(Dyadic(-5, 0).to_string(), "-5");
+        assert_eq!(format!("{:#}", Dyadic(3, 2)), "3/4");
+        assert_eq!(format!("{:#}", Dyadic(-7, 5)), "-7/32");
+        assert_eq!(format!("{:#}", Dyadic(-5, 0)), "-5");
+        assert_eq!(format!("{:#}", Dyadic(1, 70)), "1/1180591620717411303424");
+        assert_eq!(Dyadic(-7, 1).to_i64(), Some(-3));
+        assert_eq!(Dyadic(1, 90).to_u64(), Some(0));
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        assert_eq!(Dyadic(3, 2).checked_add(Dyadic(1, 1)), Some(Dyadic(5, 2)));
+        assert_eq!(Dyadic(i64::MAX, 0).checked_add(Dyadic(1, 0)), None);
+        // aligning 1 with 1/2⁶⁴ needs a 2⁶⁴ numerator
+        assert_eq!(Dyadic(1, 0).checked_add(Dyadic(1, 64)), None);
+        assert_eq!(Dyadic(0, 0).checked_add(Dyadic(1, 64)), Some(Dyadic(1, 64)));
+        assert_eq!(Dyadic(1, 0).checked_sub(Dyadic(i64::MIN, 0)), None);
+        assert_eq!(Dyadic(1, u32::MAX).checked_mul(Dyadic(1, 1)), None);
+        // 2·(n/2) fits even when 2n does not
+        let big = Dyadic(i64::MAX, 1);
+        assert_eq!(big.checked_mul(Dyadic(2, 0)), Some(Dyadic(i64::MAX, 0)));
+        assert_eq!(Dyadic(4, 0).wrapping_mul(big), Dyadic(-2, 0));
+        assert_eq!(Dyadic(3, 1).checked_pow(5), Some(Dyadic(243, 5)));
+        assert_eq!(Dyadic(3, 1).checked_pow(0), Some(Dyadic::one()));
+        assert_eq!(Dyadic(3, 0).checked_pow(40), None);
+        assert_eq!(
+            CheckedMul::checked_mul(&Dyadic(3, 1), &Dyadic(1, 1)),
+            Some(Dyadic(3, 2))
+        );
+    }
+
+    #[test]
+    fn wrapping_and_overflowing() {
+        let max = Dyadic(i64::MAX, 0);
+        assert_eq!(max.wrapping_add(Dyadic(1, 0)), Dyadic(i64::MIN, 0));
+        assert_eq!(
+            max.overflowing_add(Dyadic(1, 0)),
+            (Dyadic(i64::MIN, 0), true)
+        );
+        assert_eq!(
+            Dyadic(3, 2).overflowing_add(Dyadic(1, 1)),
+            (Dyadic(5, 2), false)
+        );
+        assert_eq!(
+            Dyadic(i64::MIN, 0).checked_add(Dyadic(0, 0)),
+            Some(Dyadic(i64::MIN, 0))
+        );
+        assert_eq!(
+            Dyadic(-1, 63).checked_add(Dyadic(1, 0)),
+            Some(Dyadic(i64::MAX, 63))
+        );
+        assert_eq!(Dyadic(1, 0).wrapping_sub(Dyadic(1, 64)), Dyadic(-1, 64));
+        assert_eq!(Dyadic(1, 0).checked_sub(Dyadic(i64::MIN, 0)), None);
+        assert_eq!(
+            Dyadic(-1, 0).checked_sub(Dyadic(i64::MIN, 0)),
+            Some(Dyadic(i64::MAX, 0))
+        );
+        let half_max = Dyadic(i64::MAX, 1);
+        assert_eq!(
+            half_max.overflowing_add(half_max),
+            (Dyadic(i64::MAX, 0), false)
+        );
+        assert_eq!(
+            Dyadic(3, 0).wrapping_pow(41),
+            Dyadic(3i64.wrapping_pow(41), 0)
+        );
+        assert!(Dyadic(3, 0).overflowing_pow(41).1);
+        assert_eq!(
+            Dyadic(1, u32::MAX).overflowing_mul(Dyadic(1, 1)),
+            (Dyadic(1, 0), true)
+        );
+    }
+
+    #[test]
+    fn units_dyadic() {
+        assert!(Dyadic(-4, 0).is_unit());
+        assert_eq!(Dyadic(-4, 0).inv(), Some(Dyadic(-1, 2)));
+        assert_eq!(Dyadic(2, 3).inv(), Some(Dyadic(4, 0)));
+        assert_eq!(Dyadic(1, 0).inv(), Some(Dyadic(1, 0)));
+        assert!(!Dyadic(3, 1).is_unit());
+        assert_eq!(Dyadic(0, 4).inv(), None);
+    }
+
+    #[test]
+    fn square_roots_dyadic() {
+        assert_eq!(Dyadic(9, 4).sqrt(), Some(Dyadic(3, 2)));
+        assert_eq!(Dyadic(18, 5).sqrt(), Some(Dyadic(3, 2)));
+        assert_eq!(Dyadic(16, 0).sqrt(), Some(Dyadic(4, 0)));
+        assert_eq!(Dyadic(0, 3).sqrt(), Some(Dyadic(0, 0)));
+        assert_eq!(Dyadic(1, 1).sqrt(), None);
+        assert_eq!(Dyadic(2, 0).sqrt(), None);
+        assert!(!Dyadic(-4, 0).is_square());
+        assert!(Dyadic(i64::MAX, 0).sqrt().is_none());
+        // squares of dyadic probabilities, including the largest odd root
+        for x in [
+            Dyadic(1, 1),
+            Dyadic(-3, 4),
+            Dyadic(3_037_000_499, 40),
+            Dyadic(5, 0),
+        ] {
+            assert_eq!((x * x).sqrt(), Some(x.abs()));
+            assert!((x * x).is_square());
+        }
+        assert_eq!(Dyadic(3_037_000_499i64.pow(2), 81).sqrt(), None);
+    }
+
+    #[test]
+    fn signed_powers() {
+        use num::pow::Pow;
+        assert_eq!(Dyadic(3, 1).pow(3u32), Dyadic(27, 3));
+        assert_eq!(Dyadic(6, 1).pow(0u32), Dyadic::one());
+        assert_eq!(Dyadic(-1, 2).pow(-3), Some(Dyadic(-64, 0)));
+        assert_eq!(Dyadic(8, 0).pow(-2), Some(Dyadic(1, 6)));
+        assert_eq!(Dyadic(-2, 0).pow(-1), Some(Dyadic(-1, 1)));
+        assert_eq!(Dyadic(4, 2).pow(-7), Some(Dyadic::one()));
+        assert_eq!(Dyadic(3, 1).pow(-1), None);
+        assert_eq!(Dyadic(0, 0).pow(-1), None);
+        assert_eq!(Dyadic(5, 0).pow(2), Some(Dyadic(25, 0)));
+    }
+
+    #[test]
+    fn exact_division() {
+        assert_eq!(Dyadic(3, 2).try_div(Dyadic(3, 0)), Some(Dyadic(1, 2)));
+        assert_eq!(Dyadic(15, 0).try_div(Dyadic(5, 3)), Some(Dyadic(24, 0)));
+        assert_eq!(Dyadic(-9, 1).try_div(Dyadic(12, 0)), Some(Dyadic(-3, 3)));
+        assert_eq!(Dyadic(1, 0).try_div(Dyadic(1, 1)), Some(Dyadic(2, 0)));
+        assert_eq!(Dyadic(1, 0).try_div(Dyadic(3, 0)), None);
+        assert_eq!(Dyadic(1, 0).try_div(Dyadic::zero()), None);
+        assert_eq!(Dyadic(0, 0).try_div(Dyadic(7, 2)), Some(Dyadic(0, 0)));
+        assert_eq!(Dyadic(1, 0).try_div(Dyadic(1, 63)), None);
+        assert_eq!(Dyadic(i64::MIN, 0).try_div(Dyadic(-1, 0)), None);
+        assert_eq!(Dyadic(6, 1).div_pow2(2), Dyadic(3, 2));
+        assert_eq!(Dyadic(5, 0).div_pow2(0), Dyadic(5, 0));
+    }
+
+    #[test]
+    fn scale_by_powers_of_two() {
+        assert_eq!(Dyadic(3, 4).mul_pow2(2), Some(Dyadic(3, 2)));
+        assert_eq!(Dyadic(3, 4).mul_pow2(4), Some(Dyadic(3, 0)));
+        assert_eq!(Dyadic(3, 4).mul_pow2(6), Some(Dyadic(12, 0)));
+        assert_eq!(Dyadic(3, 4).mul_pow2(-3), Some(Dyadic(3, 7)));
+        assert_eq!(Dyadic(-5, 0).mul_pow2(0), Some(Dyadic(-5, 0)));
+        assert_eq!(Dyadic(0, 0).mul_pow2(100), Some(Dyadic(0, 0)));
+        assert_eq!(Dyadic(-1, 0).mul_pow2(63), Some(Dyadic(i64::MIN, 0)));
+        let x = Dyadic(7, 3);
+        assert_eq!(x.mul_pow2(5).and_then(|y| y.mul_pow2(-5)), Some(x));
+        assert_eq!(x.mul_pow2(2), Some(x * Dyadic(4, 0)));
+        assert_eq!(Dyadic(1, 0).mul_pow2(63), None);
+        assert_eq!(Dyadic(1, u32::MAX).mul_pow2(-1), None);
+        assert_eq!(Dyadic(0, u32::MAX).mul_pow2(-1), Some(Dyadic(0, 0)));
+    }
+
+    #[test]
+    fn exact_from_float() {
+        assert_eq!(<Dyadic>::try_from(0.75), Ok(Dyadic(3, 2)));
+        assert_eq!(<Dyadic>::try_from(-3.0), Ok(Dyadic(-3, 0)));
+        assert_eq!(<Dyadic>::try_from(-0.0), Ok(Dyadic(0, 0)));
+        // 0.1 is really 3602879701896397 / 2⁵⁵
+        assert_eq!(
+            <Dyadic>::try_from(0.1),
+            Ok(Dyadic(3_602_879_701_896_397, 55))
+        );
+        assert_eq!(<Dyadic>::try_from(f64::from_bits(1)), Ok(Dyadic(1, 1074)));
+        assert_eq!(
+            <Dyadic>::try_from(-(2f64.powi(63))),
+            Ok(Dyadic(i64::MIN, 0))
+        );
+        assert_eq!(
+            <Dyadic>::try_from(2f64.powi(63)),
+            Err(FromFloatError::Overflow)
+        );
+        assert_eq!(<Dyadic>::try_from(1e300), Err(FromFloatError::Overflow));
+        assert_eq!(<Dyadic>::try_from(f64::NAN), Err(FromFloatError::NotFinite));
+        assert_eq!(
+            <Dyadic>::try_from(f64::NEG_INFINITY),
+            Err(FromFloatError::NotFinite)
+        );
+        for x in [0.1, -2.5e-7, 12345.678, f64::MIN_POSITIVE, 1.0 / 3.0] {
+            let d = <Dyadic>::try_from(x).unwrap();
+            assert_eq!(f64::from(d), x);
+            assert_eq!(d.cmp_f64(x), Ordering::Equal);
+        }
+    }
+
+    #[test]
+    fn subnormal_to_float() {
+        let tiny = f64::from_bits(1);
+        assert_eq!(f64::from(Dyadic(1, 1074)), tiny);
+        assert_eq!(f64::from(Dyadic(-3, 1075)), -2.0 * tiny);
+        assert_eq!(f64::from(Dyadic(5, 1076)), tiny);
+        assert_eq!(f64::from(Dyadic(1, 1075)), 0.0);
+        assert_eq!(f64::from(Dyadic(3, 1076)), tiny);
+        assert_eq!(f64::from(Dyadic(i64::MAX, 1100)), f64::from_bits(1 << 37));
+        assert_eq!(f64::from(Dyadic(7, 1030)), f64::MIN_POSITIVE * 7.0 / 256.0);
+        assert!(f64::from(Dyadic(-1, u32::MAX)).is_sign_negative());
+        assert_eq!(f64::from(Dyadic(1, 1023)), 2f64.powi(-1023));
+    }
+
+    #[test]
+    fn powers_of_two() {
+        for i in 0..20 {
+            assert_eq!(1 << i, i64::pow(2, i))
+        }
+    }
+
+    #[test]
+    fn equality_by_value() {
+        assert_eq!(Dyadic(2, 1), Dyadic(1, 0));
+        assert_eq!(Dyadic(0, 40), Dyadic(0, 0));
+        assert_eq!(Dyadic(-12, 3), Dyadic(-3, 1));
+        assert_ne!(Dyadic(3, 1), Dyadic(3, 2));
+        assert_eq!(RootTwo::new(Dyadic(4, 2), Dyadic(0, 5)), RootTwo::from(1));
+    }
+
+    #[test]
+    fn order_dyadic() {
+        let mut xs = vec![
+            Dyadic(3, 2),
+            Dyadic(-1, 0),
+            Dyadic(1, 1),
+            Dyadic(0, 3),
+            Dyadic(5, 3),
+        ];
+        xs.sort();
+        assert_eq!(
+            xs,
+            [
+                Dyadic(-1, 0),
+                Dyadic(0, 0),
+                Dyadic(1, 1),
+                Dyadic(5, 3),
+                Dyadic(3, 2)
+            ]
+        );
+        assert_eq!(Dyadic(4, 3).cmp(&Dyadic(1, 1)), Ordering::Equal);
+        // shifts past 64 bits are decided by the sign
+        assert!(Dyadic(1, 100) < Dyadic(i64::MAX, 30));
+        assert!(Dyadic(1, 100) > Dyadic(0, 0));
+        assert!(Dyadic(-1, 0) < Dyadic(i64::MIN + 1, 100));
+        assert!(Dyadic(i64::MAX, 0) > Dyadic(i64::MIN, 1));
+        assert!(Dyadic(i64::MIN, 62) < Dyadic(i64::MAX, 63));
+    }
+
+    #[test]
+    fn compare_with_integers() {
+        assert_eq!(Dyadic(4, 2), 1);
+        assert_eq!(1, Dyadic(2, 1));
+        assert_ne!(Dyadic(3, 1), 1);
+        assert!(Dyadic(3, 1) > 1 && Dyadic(3, 1) < 2);
+        assert!(-1 > Dyadic(-3, 1));
+        assert!(Dyadic(1, 200) > 0);
+        assert!(Dyadic(i64::MAX, 0) > i64::MAX - 1);
+    }
+
+    #[test]
+    fn compare_with_floats() {
+        assert_eq!(Dyadic(1, 1).cmp_f64(0.5), Ordering::Equal);
+        assert_eq!(Dyadic(1, 1).cmp_f64(0.5f64.next_up()), Ordering::Less);
+        assert_eq!(Dyadic(1, 1).cmp_f64(0.5f64.next_down()), Ordering::Greater);
+        assert_eq!(Dyadic(-3, 0).cmp_f64(-3.0), Ordering::Equal);
+        assert_eq!(Dyadic(0, 0).cmp_f64(-0.0), Ordering::Equal);
+        // the least subnormal is 2⁻¹⁰⁷⁴
+        assert_eq!(Dyadic(1, 1074).cmp_f64(f64::from_bits(1)), Ordering::Equal);
+        assert_eq!(Dyadic(1, 1075).cmp_f64(f64::from_bits(1)), Ordering::Less);
+        assert_eq!(Dyadic(i64::MAX, 0).cmp_f64(1e300), Ordering::Less);
+        assert_eq!(Dyadic(i64::MAX, 0).cmp_f64(i64::MAX as f64), Ordering::Less);
+        assert_eq!(
+            Dyadic(i64::MIN, 0).cmp_f64(i64::MIN as f64),
+            Ordering::Equal
+        );
+        assert_eq!(Dyadic(1, 0).cmp_f64(f64::INFINITY), Ordering::Less);
+        assert_eq!(Dyadic(1, 0).cmp_f64(f64::NEG_INFINITY), Ordering::Greater);
+    }
+
+    #[test]
+    #[should_panic]
+    fn compare_with_nan() {
+        Dyadic(1, 0).cmp_f64(f64::NAN);
+    }
+
+    #[test]
+    fn rounding_dyadic() {
+        let cases = [
+            (Dyadic(7, 2), (1, 2, 1, 2)),
+            (Dyadic(-7, 2), (-2, -1, -1, -2)),
+            (Dyadic(3, 1), (1, 2, 1, 2)),
+            (Dyadic(-3, 1), (-2, -1, -1, -2)),
+            (Dyadic(-5, 3), (-1, 0, 0, -1)),
+            (Dyadic(-4, 0), (-4, -4, -4, -4)),
+            (Dyadic(1, 70), (0, 1, 0, 0)),
+            (Dyadic(-1, 70), (-1, 0, 0, 0)),
+            (Dyadic(i64::MIN, 63), (-1, -1, -1, -1)),
+            (Dyadic(i64::MAX, 64), (0, 1, 0, 0)),
+            (Dyadic(i64::MIN, 64), (-1, 0, 0, -1)),
+            (
+                Dyadic(i64::MAX, 0),
+                (i64::MAX, i64::MAX, i64::MAX, i64::MAX),
+            ),
+        ];
+        for (x, expected) in cases {
+            assert_eq!(
+                (x.floor(), x.ceil(), x.trunc(), x.round()),
+                expected,
+                "{x:?}"
+            );
+        }
+        for n in -40..40 {
+            let x = Dyadic(n, 3);
+            let value = f64::from(x);
+            assert_eq!(x.floor(), value.floor() as i64);
+            assert_eq!(x.ceil(), value.ceil() as i64);
+            assert_eq!(x.trunc(), value.trunc() as i64);
+            assert_eq!(x.round(), value.round() as i64);
+        }
+    }
+
+    #[test]
+    fn round_to_coarser_grid() {
+        assert_eq!(Dyadic(13, 4).round_to_exponent(2), Dyadic(3, 2));
+        assert_eq!(Dyadic(-13, 4).round_to_exponent(2), Dyadic(-3, 2));
+        assert_eq!(Dyadic(7, 4).round_to_exponent(1), Dyadic(1, 1));
+        assert_eq!(Dyadic(7, 2).round_to_exponent(1), Dyadic(2, 0));
+        assert_eq!(Dyadic(5, 3).round_to_exponent(5), Dyadic(5, 3));
+        assert_eq!(Dyadic(1, 100).round_to_exponent(10), Dyadic(0, 0));
+        assert_eq!(Dyadic(3, 2).round_to_exponent(0), Dyadic(1, 0));
+    }
+
+    #[test]
+    fn split_dyadic() {
+        for x in [
+            Dyadic(7, 2),
+            Dyadic(-7, 2),
+            Dyadic(-4, 0),
+            Dyadic(-1, 62),
+            Dyadic(i64::MIN, 63),
+            Dyadic(i64::MIN + 1, 63),
+        ] {
+            let (n, frac) = x.split().unwrap();
+            assert_eq!(Dyadic(n, 0) + frac, x);
+            assert!((Dyadic::zero()..Dyadic::one()).contains(&frac), "{x:?}");
+        }
+        assert_eq!(Dyadic(-7, 2).split(), Some((-2, Dyadic(1, 2))));
+        assert_eq!(Dyadic(6, 1).split(), Some((3, Dyadic(0, 0))));
+        assert_eq!(Dyadic(1, 70).split(), Some((0, Dyadic(1, 70))));
+        assert_eq!(
+            Dyadic(-1 << 10, 70).split(),
+            Some((-1, Dyadic((1 << 60) - 1, 60)))
+        );
+        // 1 - 2⁻⁷⁰ has no i64 numerator
+        assert_eq!(Dyadic(-1, 70).split(), None);
+    }
+
+    #[test]
+    fn canonical_form() {
+        for (x, (n, k)) in [
+            (Dyadic::new(-4, 3), (-1, 1)),
+            (Dyadic::new(0, 5), (0, 0)),
+            (Dyadic::new(-2, 1), (-1, 0)),
+            (Dyadic::new(12, 0), (12, 0)),
+            (Dyadic::new(i64::MIN, 70), (-1, 7)),
+        ] {
+            assert_eq!((*x.numer(), x.exponent()), (n, k));
+        }
+        // every operation returns canonical values
+        let y = Dyadic(3, 2) + Dyadic(1, 2);
+        assert_eq!((y.0, y.1), (1, 0));
+        let z = Dyadic(3, 2) * Dyadic(0, 0);
+        assert_eq!((z.0, z.1), (0, 0));
+    }
+
+    #[test]
+    fn hash_by_value() {
+        use std::collections::HashMap;
+        let mut counts = HashMap::new();
+        for x in [
+            Dyadic(2, 1),
+            Dyadic(1, 0),
+            Dyadic(0, 5),
+            Dyadic(0, 0),
+            Dyadic(-6, 2),
+        ] {
+            *counts.entry(x).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&Dyadic::new(1, 0)], 2);
+        assert_eq!(counts[&Dyadic::zero()], 2);
+        assert_eq!(counts[&Dyadic(-3, 1)], 1);
+    }
+}
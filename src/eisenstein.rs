@@ -0,0 +1,250 @@
+use std::ops;
+
+use num::{Complex, One, Zero};
+
+use crate::Adj;
+
+/// An Eisenstein integer `a + bζ`, where `ζ = e^(2πi/3)` is a primitive cube
+/// root of unity satisfying `ζ² = -1 - ζ`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Eisenstein<T>(pub(crate) T, pub(crate) T);
+
+impl<T> Eisenstein<T> {
+    pub fn new(a: T, b: T) -> Eisenstein<T> {
+        Eisenstein(a, b)
+    }
+
+    /// The complex conjugate, sending `ζ` to `ζ² = -1 - ζ`.
+    pub fn conj(self) -> Eisenstein<T>
+    where
+        T: ops::Sub<Output = T> + ops::Neg<Output = T> + Copy,
+    {
+        Eisenstein(self.0 - self.1, -self.1)
+    }
+
+    /// The field norm `|a + bζ|² == a² - ab + b²`.
+    pub fn norm(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+    {
+        self.0 * self.0 - self.0 * self.1 + self.1 * self.1
+    }
+}
+
+impl Eisenstein<i64> {
+    /// The six units `±1, ±ζ, ±ζ²`, ordered by increasing argument.
+    pub fn units() -> [Eisenstein<i64>; 6] {
+        [
+            Eisenstein(1, 0),
+            Eisenstein(1, 1),
+            Eisenstein(0, 1),
+            Eisenstein(-1, 0),
+            Eisenstein(-1, -1),
+            Eisenstein(0, -1),
+        ]
+    }
+
+    pub fn is_unit(self) -> bool {
+        self.norm() == 1
+    }
+
+    /// The inverse of a unit, which is its conjugate.
+    pub fn inv(self) -> Option<Eisenstein<i64>> {
+        if self.is_unit() {
+            Some(self.conj())
+        } else {
+            None
+        }
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####      Traits for Eisenstein      ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Eisenstein<T> {
+    type Output = Eisenstein<T>;
+    fn add(self, rhs: Eisenstein<T>) -> Self::Output {
+        Eisenstein(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Eisenstein<T> {
+    type Output = Eisenstein<T>;
+    fn sub(self, rhs: Eisenstein<T>) -> Self::Output {
+        Eisenstein(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Eisenstein<T> {
+    type Output = Eisenstein<T>;
+    fn neg(self) -> Self::Output {
+        Eisenstein(-self.0, -self.1)
+    }
+}
+
+impl<T: ops::AddAssign> ops::AddAssign for Eisenstein<T> {
+    fn add_assign(&mut self, rhs: Eisenstein<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<T: ops::SubAssign> ops::SubAssign for Eisenstein<T> {
+    fn sub_assign(&mut self, rhs: Eisenstein<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl<T> ops::Mul for Eisenstein<T>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+{
+    type Output = Eisenstein<T>;
+    fn mul(self, rhs: Eisenstein<T>) -> Self::Output {
+        let bd = self.1 * rhs.1;
+        Eisenstein(self.0 * rhs.0 - bd, self.0 * rhs.1 + self.1 * rhs.0 - bd)
+    }
+}
+
+impl<T: ops::Mul<i64, Output = T>> ops::Mul<i64> for Eisenstein<T> {
+    type Output = Eisenstein<T>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Eisenstein(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<T> ops::MulAssign for Eisenstein<T>
+where
+    Eisenstein<T>: ops::Mul<Output = Eisenstein<T>> + Clone,
+{
+    fn mul_assign(&mut self, rhs: Eisenstein<T>) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<'a, T> ops::Add for &'a Eisenstein<T>
+where
+    &'a T: ops::Add<Output = T>,
+{
+    type Output = Eisenstein<T>;
+    fn add(self, rhs: &'a Eisenstein<T>) -> Self::Output {
+        Eisenstein(&self.0 + &rhs.0, &self.1 + &rhs.1)
+    }
+}
+
+impl<'a, T> ops::Sub for &'a Eisenstein<T>
+where
+    &'a T: ops::Sub<Output = T>,
+{
+    type Output = Eisenstein<T>;
+    fn sub(self, rhs: &'a Eisenstein<T>) -> Self::Output {
+        Eisenstein(&self.0 - &rhs.0, &self.1 - &rhs.1)
+    }
+}
+
+impl<'a, T> ops::Neg for &'a Eisenstein<T>
+where
+    &'a T: ops::Neg<Output = T>,
+{
+    type Output = Eisenstein<T>;
+    fn neg(self) -> Self::Output {
+        Eisenstein(-&self.0, -&self.1)
+    }
+}
+
+impl<'a, T> ops::Mul for &'a Eisenstein<T>
+where
+    &'a T: ops::Mul<Output = T>,
+    T: ops::Add<Output = T> + ops::Sub<Output = T> + Clone,
+{
+    type Output = Eisenstein<T>;
+    fn mul(self, rhs: &'a Eisenstein<T>) -> Self::Output {
+        let bd = &self.1 * &rhs.1;
+        Eisenstein(
+            &self.0 * &rhs.0 - bd.clone(),
+            &self.0 * &rhs.1 + &self.1 * &rhs.0 - bd,
+        )
+    }
+}
+
+impl<T: ops::MulAssign<i64>> ops::MulAssign<i64> for Eisenstein<T> {
+    fn mul_assign(&mut self, rhs: i64) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+    }
+}
+
+impl<T: Zero> Zero for Eisenstein<T> {
+    fn zero() -> Self {
+        Eisenstein(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<T> One for Eisenstein<T>
+where
+    T: Zero + One + PartialEq,
+    Eisenstein<T>: ops::Mul<Output = Self>,
+{
+    fn one() -> Self {
+        Eisenstein(T::one(), T::zero())
+    }
+
+    fn is_one(&self) -> bool {
+        self.0.is_one() && self.1.is_zero()
+    }
+}
+
+impl<T: ops::Sub<Output = T> + ops::Neg<Output = T> + Copy> Adj for Eisenstein<T> {
+    fn adj(self) -> Self {
+        self.conj()
+    }
+}
+
+impl<T: Into<f64>> From<Eisenstein<T>> for Complex<f64> {
+    fn from(val: Eisenstein<T>) -> Self {
+        let b = val.1.into();
+        Complex::new(val.0.into() - b / 2.0, b * 3f64.sqrt() / 2.0)
+    }
+}
+
+#[cfg(test)]
+mod eisenstein_tests {
+    use super::*;
+    #[test]
+    fn zeta_is_cube_root_of_unity() {
+        let zeta = Eisenstein(0, 1);
+        assert_eq!(zeta * zeta, Eisenstein(-1, -1));
+        assert_eq!(zeta * zeta * zeta, Eisenstein(1, 0));
+    }
+
+    #[test]
+    fn norm_and_conj() {
+        let x = Eisenstein(3, 5);
+        assert_eq!(x.norm(), 19);
+        assert_eq!(x * x.conj(), Eisenstein(19, 0));
+        assert_eq!((x * Eisenstein(2, -1)).norm(), x.norm() * 7);
+    }
+
+    #[test]
+    fn unit_handling() {
+        for u in Eisenstein::units() {
+            assert!(u.is_unit());
+            assert_eq!(u * u.inv().unwrap(), Eisenstein(1, 0));
+        }
+        assert!(!Eisenstein(2, 0).is_unit());
+        assert_eq!(Eisenstein(1, 2).inv(), None);
+    }
+}
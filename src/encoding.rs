@@ -0,0 +1,308 @@
+//! A compact binary encoding of ring elements that does not depend on serde.
+//!
+//! An encoded value is the version byte [`FORMAT_VERSION`] followed by its
+//! fields in order, with no padding or type tags:
+//! - `i64` is a zigzag LEB128 varint, so small magnitudes of either sign take
+//!   a single byte.
+//! - `Rational64` is its numerator as above, then its positive denominator
+//!   as a plain LEB128 varint.
+//! - `BigInt`, with the `bigint` feature, is a varint of twice its length in
+//!   bytes, plus one if it is negative, then its magnitude.
+//! - `Dyadic` is its numerator, then its exponent as a plain LEB128 varint.
+//! - Elements of extension rings are their coefficients in the same order as
+//!   their constructors, e.g. `a` then `b` for `a + b√2`, so `RootTwo<Dyadic>`
+//!   is two dyadics back to back.
+//!
+//! Every multibyte quantity is little-endian. The layout for a given version
+//! never changes, and decoding rejects any other version.
+
+use num::Rational64;
+
+use crate::{
+    Cplx, Cyclotomic, DecodeError, Dyadic, Eisenstein, Golden, Omega, Quad, Quaternion, ZI,
+};
+
+/// The version byte that starts every encoding.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// Conversion to and from the binary layout described in the
+/// [module documentation](self).
+pub trait Encode: Sized {
+    /// Appends the fields of `self`, without the version byte.
+    fn encode(&self, out: &mut Vec<u8>);
+
+    /// Reads the fields written by [`encode`](Self::encode) from the front of
+    /// `input`, advancing it past them.
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError>;
+
+    /// The versioned encoding of `self`.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = vec![FORMAT_VERSION];
+        self.encode(&mut out);
+        out
+    }
+
+    /// The value encoded by [`to_bytes`](Self::to_bytes), which must be
+    /// exactly the whole of `bytes`.
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (&version, mut input) = bytes.split_first().ok_or(DecodeError::Truncated)?;
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::Version(version));
+        }
+        let val = Self::decode(&mut input)?;
+        if input.is_empty() {
+            Ok(val)
+        } else {
+            Err(DecodeError::TrailingBytes)
+        }
+    }
+}
+
+pub(crate) fn write_varint(out: &mut Vec<u8>, mut x: u64) {
+    while x >= 0x80 {
+        out.push(x as u8 | 0x80);
+        x >>= 7;
+    }
+    out.push(x as u8);
+}
+
+pub(crate) fn read_varint(input: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut x = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first().ok_or(DecodeError::Truncated)?;
+        *input = rest;
+        let bits = (byte & 0x7f) as u64;
+        if shift == 63 && bits > 1 {
+            return Err(DecodeError::Overflow);
+        }
+        x |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(x);
+        }
+    }
+    Err(DecodeError::Overflow)
+}
+
+impl Encode for i64 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, ((self << 1) ^ (self >> 63)) as u64);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let x = read_varint(input)?;
+        Ok((x >> 1) as i64 ^ -((x & 1) as i64))
+    }
+}
+
+impl Encode for u32 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, *self as u64);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        u32::try_from(read_varint(input)?).map_err(|_| DecodeError::Overflow)
+    }
+}
+
+impl Encode for Rational64 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.numer().encode(out);
+        write_varint(out, *self.denom() as u64);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let n = i64::decode(input)?;
+        let d = read_varint(input)?;
+        if d == 0 {
+            return Err(DecodeError::Invalid);
+        }
+        let d = i64::try_from(d).map_err(|_| DecodeError::Overflow)?;
+        Ok(Rational64::new(n, d))
+    }
+}
+
+impl<T: Encode + num::Integer> Encode for Dyadic<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let n = T::decode(input)?;
+        Ok(Dyadic::new(n, u32::decode(input)?))
+    }
+}
+
+impl<T: Encode, const D: i64> Encode for Quad<T, D> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let a = T::decode(input)?;
+        Ok(Quad(a, T::decode(input)?))
+    }
+}
+
+impl<T: Encode> Encode for Omega<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+        self.2.encode(out);
+        self.3.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let a = T::decode(input)?;
+        let b = T::decode(input)?;
+        let c = T::decode(input)?;
+        Ok(Omega(a, b, c, T::decode(input)?))
+    }
+}
+
+impl<T: Encode> Encode for Quaternion<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+        self.2.encode(out);
+        self.3.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let a = T::decode(input)?;
+        let b = T::decode(input)?;
+        let c = T::decode(input)?;
+        Ok(Quaternion(a, b, c, T::decode(input)?))
+    }
+}
+
+impl<T: Encode> Encode for Cplx<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let re = T::decode(input)?;
+        Ok(Cplx(re, T::decode(input)?))
+    }
+}
+
+impl<T: Encode> Encode for Golden<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let a = T::decode(input)?;
+        Ok(Golden(a, T::decode(input)?))
+    }
+}
+
+impl<T: Encode> Encode for Eisenstein<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let a = T::decode(input)?;
+        Ok(Eisenstein(a, T::decode(input)?))
+    }
+}
+
+impl Encode for ZI {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.0.encode(out);
+        self.1.encode(out);
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let re = i64::decode(input)?;
+        Ok(ZI(re, i64::decode(input)?))
+    }
+}
+
+impl<const N: usize> Encode for Cyclotomic<N> {
+    /// All `N` stored coefficients, including the trailing zeros.
+    fn encode(&self, out: &mut Vec<u8>) {
+        for c in &self.0 {
+            c.encode(out);
+        }
+    }
+
+    fn decode(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let mut coeffs = [0; N];
+        for c in &mut coeffs {
+            *c = i64::decode(input)?;
+        }
+        Ok(Cyclotomic::new(coeffs))
+    }
+}
+
+#[cfg(test)]
+mod encoding_tests {
+    use super::*;
+    use crate::{DOmega, RootTwo, ZOmega};
+
+    #[test]
+    fn stable_layout() {
+        assert_eq!(0i64.to_bytes(), [1, 0]);
+        assert_eq!((-1i64).to_bytes(), [1, 1]);
+        assert_eq!(64i64.to_bytes(), [1, 0x80, 0x01]);
+        assert_eq!(Dyadic(-3i64, 2).to_bytes(), [1, 5, 2]);
+        assert_eq!(
+            RootTwo::<Dyadic>::new(Dyadic(1, 1), Dyadic(0, 0)).to_bytes(),
+            [1, 2, 1, 0, 0]
+        );
+        assert_eq!(Rational64::new(-1, 3).to_bytes(), [1, 1, 3]);
+        assert_eq!(
+            i64::MIN.to_bytes(),
+            [1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        for n in [0, 1, -1, 63, -64, 300, i64::MAX, i64::MIN] {
+            assert_eq!(i64::from_bytes(&n.to_bytes()), Ok(n));
+        }
+        let x = RootTwo::new(Dyadic(-7, 3), Dyadic(i64::MAX, u32::MAX));
+        assert_eq!(RootTwo::<Dyadic>::from_bytes(&x.to_bytes()), Ok(x));
+        let z: ZOmega = Omega(1, -2, 300, i64::MIN);
+        assert_eq!(ZOmega::from_bytes(&z.to_bytes()), Ok(z));
+        let w: DOmega = Omega(Dyadic(1, 1), Dyadic(0, 0), Dyadic(-3, 2), Dyadic(7, 0));
+        assert_eq!(DOmega::from_bytes(&w.to_bytes()), Ok(w));
+        let q = Quad::<_, 2>::new(Rational64::new(1, 2), Rational64::new(-5, 7));
+        assert_eq!(Quad::from_bytes(&q.to_bytes()), Ok(q));
+        let c = Cyclotomic::<5>::new([1, -2, 3, 4, 0]);
+        assert_eq!(Cyclotomic::<5>::from_bytes(&c.to_bytes()), Ok(c));
+        assert_eq!(ZI::from_bytes(&ZI(3, -4).to_bytes()), Ok(ZI(3, -4)));
+    }
+
+    #[test]
+    fn malformed_input() {
+        assert_eq!(i64::from_bytes(&[]), Err(DecodeError::Truncated));
+        assert_eq!(i64::from_bytes(&[2, 0]), Err(DecodeError::Version(2)));
+        assert_eq!(i64::from_bytes(&[1, 0x80]), Err(DecodeError::Truncated));
+        assert_eq!(i64::from_bytes(&[1, 0, 0]), Err(DecodeError::TrailingBytes));
+        assert_eq!(
+            i64::from_bytes(&[1, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02]),
+            Err(DecodeError::Overflow)
+        );
+        assert_eq!(
+            <Dyadic>::from_bytes(&[1, 0, 0x80, 0x80, 0x80, 0x80, 0x10]),
+            Err(DecodeError::Overflow)
+        );
+        assert_eq!(
+            Rational64::from_bytes(&[1, 2, 0]),
+            Err(DecodeError::Invalid)
+        );
+        assert_eq!(
+            RootTwo::<i64>::from_bytes(&[1, 2]),
+            Err(DecodeError::Truncated)
+        );
+    }
+}
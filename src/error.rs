@@ -0,0 +1,127 @@
+use std::{fmt, num::ParseIntError};
+
+/// The error returned when parsing a ring element from a string fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseRingError {
+    /// A numeric literal could not be parsed as an integer.
+    Int(ParseIntError),
+    /// A denominator was not a power of two.
+    NotDyadic,
+    /// The input did not have the shape of an element of the ring.
+    Malformed,
+}
+
+impl fmt::Display for ParseRingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseRingError::Int(err) => write!(f, "invalid integer literal: {err}"),
+            ParseRingError::NotDyadic => write!(f, "denominator is not a power of two"),
+            ParseRingError::Malformed => write!(f, "malformed ring element"),
+        }
+    }
+}
+
+impl std::error::Error for ParseRingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ParseRingError::Int(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseIntError> for ParseRingError {
+    fn from(err: ParseIntError) -> Self {
+        ParseRingError::Int(err)
+    }
+}
+
+/// The error returned when a float has no exact representation in a ring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromFloatError {
+    /// The float was NaN or infinite.
+    NotFinite,
+    /// The float was finite, but too large for the coefficient type.
+    Overflow,
+}
+
+impl fmt::Display for FromFloatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromFloatError::NotFinite => write!(f, "float is not finite"),
+            FromFloatError::Overflow => write!(f, "float is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for FromFloatError {}
+
+/// The error returned when a rational has no exact representation as a
+/// dyadic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromRatioError {
+    /// The reduced denominator was not a power of two.
+    NotDyadic,
+    /// The numerator or exponent did not fit.
+    Overflow,
+}
+
+impl fmt::Display for FromRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromRatioError::NotDyadic => write!(f, "denominator is not a power of two"),
+            FromRatioError::Overflow => write!(f, "rational is out of range"),
+        }
+    }
+}
+
+impl std::error::Error for FromRatioError {}
+
+/// The error returned when decoding a ring element from bytes fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input started with an unsupported format version.
+    Version(u8),
+    /// The input ended in the middle of a value.
+    Truncated,
+    /// A varint did not fit in its field.
+    Overflow,
+    /// A field held a value that no element can have, such as a zero
+    /// denominator.
+    Invalid,
+    /// The input continued past the end of the value.
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Version(v) => write!(f, "unsupported encoding version {v}"),
+            DecodeError::Truncated => write!(f, "unexpected end of input"),
+            DecodeError::Overflow => write!(f, "encoded integer is out of range"),
+            DecodeError::Invalid => write!(f, "invalid encoded value"),
+            DecodeError::TrailingBytes => write!(f, "trailing bytes after encoded value"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// The error returned when a matrix does not have the required dimension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DimensionError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {0}×{0} matrix, found {1}×{1}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for DimensionError {}
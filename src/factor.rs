@@ -0,0 +1,253 @@
+use num::integer::Roots;
+
+use crate::{numtheory, EuclideanDomain, Quad, Ring, RootTwo, ZOmega};
+
+/// A factorization `unit · p₁^e₁ ··· pₖ^eₖ` into pairwise non-associate ring
+/// primes, listed by increasing norm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Factorization<T> {
+    pub(crate) unit: T,
+    pub(crate) factors: Vec<(T, u32)>,
+}
+
+impl<T> Factorization<T> {
+    pub fn unit(&self) -> &T {
+        &self.unit
+    }
+
+    /// The primes and their multiplicities.
+    pub fn factors(&self) -> &[(T, u32)] {
+        &self.factors
+    }
+
+    /// Multiplies the factorization back out.
+    pub fn product(&self) -> T
+    where
+        T: Ring,
+    {
+        self.factors.iter().fold(self.unit.clone(), |acc, (p, e)| {
+            (0..*e).fold(acc, |acc, _| acc * p.clone())
+        })
+    }
+}
+
+/// Classifies a nonzero absolute norm: `Some((p, 1))` if it is a prime `p`,
+/// `Some((p, 2))` if it is the square of one, and `None` otherwise.
+fn prime_norm(n: u64) -> Option<(u64, u32)> {
+    if numtheory::is_prime(n) {
+        return Some((n, 1));
+    }
+    let p = n.sqrt();
+    (p * p == n && numtheory::is_prime(p)).then_some((p, 2))
+}
+
+/// The element `±x·uᵏ` with nonnegative coefficients and the least `√2`
+/// coefficient, for a unit `u > 1`. These are exactly the positive `v` with
+/// `v ≥ |v•|`, and multiplying by `u` scales `v / |v•|` by `u / |u•| > 1`.
+pub(crate) fn first_quadrant(x: RootTwo<i64>, unit: RootTwo<i64>) -> RootTwo<i64> {
+    let inverse = unit.inv().unwrap();
+    let mut x = if x.sign() < 0 { -x } else { x };
+    while x.0 < 0 || x.1 < 0 {
+        x *= unit;
+    }
+    loop {
+        let y = x * inverse;
+        if y.0 < 0 || y.1 < 0 {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// An element of norm `±p` for a prime `p ≡ ±1 (mod 8)`, chosen with
+/// nonnegative coefficients and the least `√2` coefficient.
+///
+/// If `r² ≡ 2 (mod p)` then `p` divides `(r + √2)(r - √2)` but neither
+/// factor, so `gcd(p, r + √2)` is a prime above `p`.
+pub(crate) fn split_prime(p: u64) -> RootTwo<i64> {
+    let r = numtheory::sqrt_mod_p(2, p).unwrap();
+    let g = Quad(p as i64, 0).gcd(Quad(r as i64, 1));
+    [g, g.conj()]
+        .map(|x| first_quadrant(x, RootTwo::lambda()))
+        .into_iter()
+        .min_by_key(|x| x.1)
+        .unwrap()
+}
+
+impl RootTwo<i64> {
+    /// Factors `self` into primes of ℤ[√2], or returns `None` for zero.
+    ///
+    /// Each rational prime `p` dividing the norm determines the primes above
+    /// it. `2 = (√2)²` ramifies, `p ≡ ±3 (mod 8)` stays prime, and
+    /// `p ≡ ±1 (mod 8)` splits into an element of norm `±p` and its
+    /// conjugate. Primes are normalized to have positive coefficients, except
+    /// for the conjugate of a split prime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the norm does not fit in an `i64`.
+    pub fn factor(self) -> Option<Factorization<RootTwo<i64>>> {
+        self.factor_within(u64::MAX)
+    }
+
+    /// [`RootTwo::factor`] with an effort budget for factoring the norm, as
+    /// in [`numtheory::factor_within`]. Also returns `None` if the budget
+    /// runs out.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the norm does not fit in an `i64`.
+    pub fn factor_within(self, effort: u64) -> Option<Factorization<RootTwo<i64>>> {
+        let norm = self.wide_norm().and_then(|n| i64::try_from(n).ok());
+        let norm = norm.expect("norm overflows i64");
+        if norm == 0 {
+            return None;
+        }
+        let mut rest = self;
+        let mut factors = Vec::new();
+        let mut divide_out = |p: RootTwo<i64>| {
+            let mut e = 0;
+            while let Some(q) = rest.try_div(p) {
+                rest = q;
+                e += 1;
+            }
+            if e > 0 {
+                factors.push((p, e));
+            }
+        };
+        for (p, _) in numtheory::factor_within(norm.unsigned_abs(), effort)? {
+            match p % 8 {
+                2 => divide_out(Quad(0, 1)),
+                3 | 5 => divide_out(Quad(p as i64, 0)),
+                _ => {
+                    let pi = split_prime(p);
+                    divide_out(pi);
+                    divide_out(pi.conj());
+                }
+            }
+        }
+        debug_assert!(rest.is_unit());
+        Some(Factorization {
+            unit: rest,
+            factors,
+        })
+    }
+
+    /// Whether `self` is a prime of ℤ[√2]. This holds exactly when the norm
+    /// is `±p` for a rational prime `p`, or `±p²` for an inert `p ≡ ±3
+    /// (mod 8)`, in which case `self` is an associate of `p`.
+    pub fn is_prime(self) -> bool {
+        let norm = self
+            .wide_norm()
+            .and_then(|n| u64::try_from(n.unsigned_abs()).ok());
+        match norm.and_then(prime_norm) {
+            Some((_, 1)) => true,
+            Some((p, _)) => p % 8 == 3 || p % 8 == 5,
+            None => false,
+        }
+    }
+}
+
+impl ZOmega {
+    /// Whether `self` is a prime of ℤ[ω]. The residue degree of a rational
+    /// prime `p` is `1` for `p = 2` or `p ≡ 1 (mod 8)` and `2` otherwise, so
+    /// primes have norm `p` or `p²` accordingly.
+    pub fn is_prime(self) -> bool {
+        match u64::try_from(self.wide_norm()).ok().and_then(prime_norm) {
+            Some((_, 1)) => true,
+            Some((p, _)) => p != 2 && p % 8 != 1,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod factor_tests {
+    use super::*;
+    use num::{One, Zero};
+
+    #[test]
+    fn split_primes_have_prime_norm() {
+        for p in [7u64, 17, 23, 31, 41, 47, 1_000_033, 4_294_967_279] {
+            let pi = split_prime(p);
+            assert_eq!(pi.norm().unsigned_abs(), p);
+            assert!(pi.0 >= 0 && pi.1 > 0);
+        }
+    }
+
+    #[test]
+    fn factor_zroottwo() {
+        for x in [
+            RootTwo::new(1, 0),
+            RootTwo::new(-1, 1),
+            RootTwo::new(2, 0),
+            RootTwo::new(360, 0),
+            RootTwo::new(7, 0),
+            RootTwo::new(5, -3),
+            RootTwo::new(-123, 456),
+            RootTwo::new(99_991, 12_345),
+        ] {
+            let f = x.factor().unwrap();
+            assert_eq!(f.product(), x);
+            assert!(f.unit().is_unit());
+            for (p, _) in f.factors() {
+                assert!(!p.is_unit());
+            }
+        }
+        assert_eq!(RootTwo::zero().factor(), None);
+    }
+
+    #[test]
+    fn primes_zroottwo() {
+        assert!(RootTwo::new(0, 1).is_prime());
+        assert!(RootTwo::new(3, 1).is_prime());
+        assert!(RootTwo::new(3, 0).is_prime());
+        assert!(RootTwo::new(-5, 0).is_prime());
+        assert!(!RootTwo::new(7, 0).is_prime());
+        assert!(!RootTwo::new(2, 0).is_prime());
+        assert!(!RootTwo::new(1, 1).is_prime());
+        assert!(!RootTwo::zero().is_prime());
+        assert!(!RootTwo::new(1, i64::MIN).is_prime());
+        // every factor found by factor() is prime
+        let f = RootTwo::new(-123, 456).factor().unwrap();
+        assert!(f.factors().iter().all(|(p, _)| p.is_prime()));
+    }
+
+    #[test]
+    fn primes_zomega() {
+        use crate::Omega;
+        // δ = 1 + ω lies over 2, and 3 + ω² = 3 + i has norm 10²
+        assert!(Omega(1, 1, 0, 0).is_prime());
+        assert!(!Omega(3, 0, 1, 0).is_prime());
+        // 3 and 7 split into two primes of norm p², while 17 splits fully
+        assert!(!Omega(3, 0, 0, 0).is_prime());
+        assert!(!Omega(1, 1, 0, -1).is_prime());
+        let p: ZOmega = Omega(3, 1, 0, -1);
+        assert_eq!(p.norm(), 49);
+        assert!(p.is_prime());
+        assert!(!Omega(17, 0, 0, 0).is_prime());
+        assert!(!Omega(2, 0, 0, 0).is_prime());
+        assert!(!Omega(0, 0, 0, 0).is_prime());
+        // associates of δ with coefficients past 2¹⁶, and norms beyond a u64
+        let unit = ZOmega::from(RootTwo::new(22_619_537, 15_994_428));
+        assert!((unit * Omega(1, 1, 0, 0)).is_prime());
+        assert!(!ZOmega::new(1 << 16, 1, 0, 0).is_prime());
+        assert!(!ZOmega::new(i64::MAX, 0, i64::MIN, 0).is_prime());
+    }
+
+    #[test]
+    fn splitting_behavior() {
+        // 7 splits as (3 + √2)(3 - √2), but 3 stays prime
+        let f = RootTwo::new(7, 0).factor().unwrap();
+        assert_eq!(
+            f.factors(),
+            [(RootTwo::new(3, 1), 1), (RootTwo::new(3, -1), 1)]
+        );
+        let f = RootTwo::new(9, 0).factor().unwrap();
+        assert_eq!(f.factors(), [(RootTwo::new(3, 0), 2)]);
+        // 4 = √2⁴
+        let f = RootTwo::new(4, 0).factor().unwrap();
+        assert_eq!(f.factors(), [(RootTwo::new(0, 1), 4)]);
+        assert_eq!(*f.unit(), RootTwo::one());
+    }
+}
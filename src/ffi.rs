@@ -0,0 +1,241 @@
+//! A C ABI over opaque handles, for the `ffi` feature. Every function is
+//! prefixed `rings_`, and the header can be generated with cbindgen.
+//!
+//! Handles are heap allocated by the `_new` functions and by arithmetic, and
+//! each must be released exactly once with the matching `_free`. Arithmetic
+//! returns a null handle where the Rust operators would overflow, so callers
+//! should check every result.
+
+use std::{cmp::Ordering, ptr};
+
+use crate::{Dyadic, Quad, RootTwo};
+
+/// An exact dyadic rational `n/2ᵏ`.
+pub struct RingsDyadic(Dyadic);
+
+/// An exact element `a + b√2` of D[√2].
+pub struct RingsRootTwo(RootTwo<Dyadic>);
+
+fn into_handle<T>(x: Option<T>) -> *mut T {
+    x.map_or(ptr::null_mut(), |x| Box::into_raw(Box::new(x)))
+}
+
+fn ordering(o: Ordering) -> i32 {
+    o as i32
+}
+
+/// The dyadic `n/2ᵏ`.
+#[no_mangle]
+pub extern "C" fn rings_dyadic_new(n: i64, k: u32) -> *mut RingsDyadic {
+    into_handle(Some(RingsDyadic(Dyadic::new(n, k))))
+}
+
+/// `x + y`, or null on overflow.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_dyadic_add(
+    x: *const RingsDyadic,
+    y: *const RingsDyadic,
+) -> *mut RingsDyadic {
+    into_handle((*x).0.checked_add((*y).0).map(RingsDyadic))
+}
+
+/// `x - y`, or null on overflow.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_dyadic_sub(
+    x: *const RingsDyadic,
+    y: *const RingsDyadic,
+) -> *mut RingsDyadic {
+    into_handle((*x).0.checked_sub((*y).0).map(RingsDyadic))
+}
+
+/// `x·y`, or null on overflow.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_dyadic_mul(
+    x: *const RingsDyadic,
+    y: *const RingsDyadic,
+) -> *mut RingsDyadic {
+    into_handle((*x).0.checked_mul((*y).0).map(RingsDyadic))
+}
+
+/// `-1`, `0` or `1` as `x` is less than, equal to or greater than `y`.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_dyadic_compare(x: *const RingsDyadic, y: *const RingsDyadic) -> i32 {
+    ordering((*x).0.cmp(&(*y).0))
+}
+
+/// The nearest `double`.
+///
+/// # Safety
+///
+/// `x` must be a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_dyadic_to_double(x: *const RingsDyadic) -> f64 {
+    f64::from((*x).0)
+}
+
+/// Releases `x`, which may be null.
+///
+/// # Safety
+///
+/// `x` must be null or a live handle from this module, and is dangling
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rings_dyadic_free(x: *mut RingsDyadic) {
+    if !x.is_null() {
+        drop(Box::from_raw(x));
+    }
+}
+
+/// `a + b√2`, copying the two coefficients.
+///
+/// # Safety
+///
+/// `a` and `b` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_new(
+    a: *const RingsDyadic,
+    b: *const RingsDyadic,
+) -> *mut RingsRootTwo {
+    into_handle(Some(RingsRootTwo(Quad((*a).0, (*b).0))))
+}
+
+/// `a + b√2` for integers `a` and `b`.
+#[no_mangle]
+pub extern "C" fn rings_roottwo_from_integers(a: i64, b: i64) -> *mut RingsRootTwo {
+    into_handle(Some(RingsRootTwo(Quad(
+        Dyadic::new(a, 0),
+        Dyadic::new(b, 0),
+    ))))
+}
+
+/// `x + y`, or null on overflow.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_add(
+    x: *const RingsRootTwo,
+    y: *const RingsRootTwo,
+) -> *mut RingsRootTwo {
+    into_handle((*x).0.checked_add((*y).0).map(RingsRootTwo))
+}
+
+/// `x - y`, or null on overflow.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_sub(
+    x: *const RingsRootTwo,
+    y: *const RingsRootTwo,
+) -> *mut RingsRootTwo {
+    into_handle((*x).0.checked_sub((*y).0).map(RingsRootTwo))
+}
+
+/// `x·y`, or null on overflow.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_mul(
+    x: *const RingsRootTwo,
+    y: *const RingsRootTwo,
+) -> *mut RingsRootTwo {
+    into_handle((*x).0.checked_mul((*y).0).map(RingsRootTwo))
+}
+
+/// `-1`, `0` or `1` as `x` is less than, equal to or greater than `y`,
+/// decided exactly.
+///
+/// # Safety
+///
+/// `x` and `y` must be live handles from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_compare(
+    x: *const RingsRootTwo,
+    y: *const RingsRootTwo,
+) -> i32 {
+    ordering((*x).0.cmp(&(*y).0))
+}
+
+/// The correctly rounded `double`.
+///
+/// # Safety
+///
+/// `x` must be a live handle from this module.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_to_double(x: *const RingsRootTwo) -> f64 {
+    f64::from((*x).0)
+}
+
+/// Releases `x`, which may be null.
+///
+/// # Safety
+///
+/// `x` must be null or a live handle from this module, and is dangling
+/// afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn rings_roottwo_free(x: *mut RingsRootTwo) {
+    if !x.is_null() {
+        drop(Box::from_raw(x));
+    }
+}
+
+#[cfg(test)]
+mod ffi_tests {
+    use super::*;
+
+    #[test]
+    fn handles_round_trip() {
+        unsafe {
+            let half = rings_dyadic_new(1, 1);
+            let quarter = rings_dyadic_mul(half, half);
+            assert_eq!(rings_dyadic_to_double(quarter), 0.25);
+            assert_eq!(rings_dyadic_compare(quarter, half), -1);
+            let sum = rings_dyadic_add(quarter, half);
+            assert_eq!(rings_dyadic_to_double(sum), 0.75);
+            let big = rings_dyadic_new(i64::MAX, 0);
+            assert!(rings_dyadic_add(big, big).is_null());
+
+            let x = rings_roottwo_from_integers(1, 1);
+            let y = rings_roottwo_new(half, quarter);
+            let product = rings_roottwo_mul(x, y);
+            // (1 + √2)(1/2 + √2/4) = 1 + 3√2/4
+            let (one, three_quarters) = (rings_dyadic_new(1, 0), rings_dyadic_new(3, 2));
+            let expected = rings_roottwo_new(one, three_quarters);
+            assert_eq!(rings_roottwo_compare(product, expected), 0);
+            assert!((rings_roottwo_to_double(x) - 2.414_213_562_373_095).abs() < 1e-15);
+            let difference = rings_roottwo_sub(x, x);
+            assert_eq!(rings_roottwo_to_double(difference), 0.0);
+            let huge = rings_roottwo_from_integers(0, i64::MAX);
+            assert!(rings_roottwo_mul(huge, huge).is_null());
+
+            for handle in [half, quarter, sum, big, one, three_quarters] {
+                rings_dyadic_free(handle);
+            }
+            for handle in [x, y, product, expected, difference, huge] {
+                rings_roottwo_free(handle);
+            }
+            rings_roottwo_free(ptr::null_mut());
+        }
+    }
+}
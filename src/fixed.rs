@@ -0,0 +1,307 @@
+use std::{cmp::Ordering, ops};
+
+use num::{BigInt, Integer, One, Signed, Zero};
+
+use crate::{
+    quad::{floor_big, round_to_f64},
+    Dyadic, RootTwo,
+};
+
+/// The direction in which an inexact result is rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rounding {
+    /// Towards negative infinity.
+    Down,
+    /// Towards positive infinity.
+    Up,
+    /// To the nearest representable value, with ties to even.
+    Nearest,
+}
+
+/// A fixed-point real `m·2⁻ᵏ` with an arbitrary-precision mantissa `m` and
+/// `k` fractional bits. Unlike `f64` the precision is chosen per value, and
+/// every inexact operation rounds in a chosen direction, so lower and upper
+/// bounds can be carried through a computation separately.
+///
+/// Sums and differences are exact at the larger of the two precisions.
+/// Products are rounded to nearest at that precision by `*`, and in any
+/// direction by [`mul_round`](Self::mul_round).
+#[derive(Debug, Clone)]
+pub struct Fixed {
+    pub(crate) m: BigInt,
+    pub(crate) k: u32,
+}
+
+/// `m / 2ˢ` rounded to an integer in the given direction.
+fn shift_round(m: BigInt, s: u32, rounding: Rounding) -> BigInt {
+    let q = &m >> s;
+    let r = m - (&q << s);
+    if r.is_zero() {
+        return q;
+    }
+    let up = match rounding {
+        Rounding::Down => false,
+        Rounding::Up => true,
+        Rounding::Nearest => match (r << 1u32).cmp(&(BigInt::one() << s)) {
+            Ordering::Less => false,
+            Ordering::Equal => q.is_odd(),
+            Ordering::Greater => true,
+        },
+    };
+    if up {
+        q + 1
+    } else {
+        q
+    }
+}
+
+impl Fixed {
+    /// The value `m·2⁻ᵏ`.
+    pub fn new(m: BigInt, k: u32) -> Fixed {
+        Fixed { m, k }
+    }
+
+    pub fn mantissa(&self) -> &BigInt {
+        &self.m
+    }
+
+    /// The number of fractional bits `k`.
+    pub fn precision(&self) -> u32 {
+        self.k
+    }
+
+    /// The nearest value with `k` fractional bits in the given direction,
+    /// which is exact when `k` is at least the current precision.
+    pub fn with_precision(&self, k: u32, rounding: Rounding) -> Fixed {
+        let m = if k >= self.k {
+            &self.m << (k - self.k)
+        } else {
+            shift_round(self.m.clone(), self.k - k, rounding)
+        };
+        Fixed { m, k }
+    }
+
+    /// The mantissas of `self` and `rhs` at the larger precision.
+    fn align(&self, rhs: &Fixed) -> (BigInt, BigInt, u32) {
+        let k = self.k.max(rhs.k);
+        (&self.m << (k - self.k), &rhs.m << (k - rhs.k), k)
+    }
+
+    /// The product at the larger of the two precisions, rounded in the
+    /// given direction.
+    pub fn mul_round(&self, rhs: &Fixed, rounding: Rounding) -> Fixed {
+        let k = self.k.max(rhs.k);
+        let m = shift_round(&self.m * &rhs.m, self.k + rhs.k - k, rounding);
+        Fixed { m, k }
+    }
+
+    /// The square root at the same precision, rounded in the given
+    /// direction.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is negative.
+    pub fn sqrt(&self, rounding: Rounding) -> Fixed {
+        assert!(
+            !self.m.is_negative(),
+            "attempted to take the square root of a negative number"
+        );
+        // √(m·2⁻ᵏ) = √(m·2ᵏ)·2⁻ᵏ
+        let n = &self.m << self.k;
+        let r = n.sqrt();
+        let square = &r * &r;
+        let up = square != n
+            && match rounding {
+                Rounding::Down => false,
+                Rounding::Up => true,
+                // n is an integer, so it is never exactly (r + 1/2)²
+                Rounding::Nearest => n > square + &r,
+            };
+        let m = if up { r + 1 } else { r };
+        Fixed { m, k: self.k }
+    }
+
+    /// `x` with `k` fractional bits, rounded in the given direction.
+    pub fn from_roottwo(x: RootTwo<Dyadic>, k: u32, rounding: Rounding) -> Fixed {
+        let (a, b, e) = x.common_denom();
+        Fixed::from_parts(a, b, e, k, rounding)
+    }
+
+    /// `(a + b√2)/2ᵉ` with `k` fractional bits, rounded in the given
+    /// direction.
+    pub(crate) fn from_parts(a: BigInt, b: BigInt, e: u32, k: u32, rounding: Rounding) -> Fixed {
+        let m = if b.is_zero() {
+            shift_round(a << k, e, rounding)
+        } else {
+            // 2ᵏx is irrational, so ⌊2ᵏ⁺¹x⌋ with an odd bit appended rounds
+            // the same way in every direction
+            let twice = floor_big(a << (k + 1), b << (k + 1), 0);
+            shift_round((twice << 1u32) + 1, e + 2, rounding)
+        };
+        Fixed::new(m, k)
+    }
+
+    /// The nearest `f64`.
+    pub fn to_f64(&self) -> f64 {
+        round_to_f64(self.m.clone(), BigInt::zero(), &(BigInt::one() << self.k))
+    }
+
+    /// The exact value as a dyadic.
+    pub fn to_dyadic(&self) -> Dyadic<BigInt> {
+        Dyadic::new(self.m.clone(), self.k)
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####         Traits for Fixed        ####
+// #######                           #######
+// #########################################
+
+impl From<Dyadic> for Fixed {
+    fn from(val: Dyadic) -> Self {
+        Fixed::new(val.0.into(), val.1)
+    }
+}
+
+impl PartialEq for Fixed {
+    fn eq(&self, other: &Fixed) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Fixed {}
+
+impl PartialOrd for Fixed {
+    fn partial_cmp(&self, other: &Fixed) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fixed {
+    fn cmp(&self, other: &Fixed) -> Ordering {
+        let (a, b, _) = self.align(other);
+        a.cmp(&b)
+    }
+}
+
+impl ops::Add for Fixed {
+    type Output = Fixed;
+    fn add(self, rhs: Fixed) -> Self::Output {
+        let (a, b, k) = self.align(&rhs);
+        Fixed::new(a + b, k)
+    }
+}
+
+impl ops::Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, rhs: Fixed) -> Self::Output {
+        let (a, b, k) = self.align(&rhs);
+        Fixed::new(a - b, k)
+    }
+}
+
+impl ops::Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Self::Output {
+        Fixed::new(-self.m, self.k)
+    }
+}
+
+impl ops::Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, rhs: Fixed) -> Self::Output {
+        self.mul_round(&rhs, Rounding::Nearest)
+    }
+}
+
+#[cfg(test)]
+mod fixed_tests {
+    use super::*;
+
+    fn fixed(m: i64, k: u32) -> Fixed {
+        Fixed::new(m.into(), k)
+    }
+
+    #[test]
+    fn directed_rounding() {
+        for (x, down, up, nearest) in [
+            (fixed(11, 2), 2, 3, 3),
+            (fixed(10, 2), 2, 3, 2),
+            (fixed(-10, 2), -3, -2, -2),
+            (fixed(14, 2), 3, 4, 4),
+            (fixed(-7, 0), -7, -7, -7),
+        ] {
+            assert_eq!(x.with_precision(0, Rounding::Down), fixed(down, 0));
+            assert_eq!(x.with_precision(0, Rounding::Up), fixed(up, 0));
+            assert_eq!(x.with_precision(0, Rounding::Nearest), fixed(nearest, 0));
+        }
+        let x = fixed(3, 10).with_precision(20, Rounding::Down);
+        assert_eq!((x.mantissa(), x.precision()), (&BigInt::from(3 << 10), 20));
+    }
+
+    #[test]
+    fn arithmetic_fixed() {
+        let x = Fixed::from(Dyadic(3, 2));
+        let y = Fixed::from(Dyadic(1, 5));
+        assert_eq!(x.clone() + y.clone(), Fixed::from(Dyadic(25, 5)));
+        assert_eq!((x.clone() - y.clone()).precision(), 5);
+        assert_eq!(-(y.clone() - x.clone()), Fixed::from(Dyadic(23, 5)));
+        // 3/4 · 1/32 is three quarters of the last place
+        assert_eq!(x.mul_round(&y, Rounding::Down), fixed(0, 5));
+        assert_eq!(x.mul_round(&y, Rounding::Up), fixed(1, 5));
+        assert_eq!(x.clone() * y.clone(), fixed(1, 5));
+        assert!(y < x && -x.clone() < y);
+        assert_eq!(x.to_f64(), 0.75);
+        assert_eq!(y.to_dyadic(), Dyadic(BigInt::one(), 5));
+    }
+
+    #[test]
+    fn square_roots_fixed() {
+        let two = fixed(2, 0).with_precision(100, Rounding::Down);
+        let (down, up) = (two.sqrt(Rounding::Down), two.sqrt(Rounding::Up));
+        assert!(down.mul_round(&down, Rounding::Up) <= two);
+        assert!(up.mul_round(&up, Rounding::Down) >= two);
+        assert_eq!(up.clone() - down.clone(), fixed(1, 100));
+        let root_two = RootTwo::new(Dyadic(0, 0), Dyadic(1, 0));
+        for rounding in [Rounding::Down, Rounding::Up, Rounding::Nearest] {
+            assert_eq!(
+                two.sqrt(rounding),
+                Fixed::from_roottwo(root_two, 100, rounding)
+            );
+        }
+        assert_eq!(
+            fixed(9, 4).sqrt(Rounding::Up),
+            fixed(3, 2).with_precision(4, Rounding::Down)
+        );
+    }
+
+    #[test]
+    fn roottwo_past_f64() {
+        // λ⁻⁴⁰·λ⁴⁰ = 1, bracketed far more tightly than f64 could
+        let huge = RootTwo::new(
+            Dyadic(1_023_286_908_188_737, 0),
+            Dyadic(723_573_111_879_672, 0),
+        );
+        let tiny = huge.conj();
+        let bounds = |rounding| {
+            let x = Fixed::from_roottwo(tiny, 120, rounding);
+            x.mul_round(&Fixed::from_roottwo(huge, 120, rounding), rounding)
+        };
+        let one = fixed(1, 0);
+        assert!(bounds(Rounding::Down) <= one && one <= bounds(Rounding::Up));
+        assert!(bounds(Rounding::Up) - bounds(Rounding::Down) < fixed(1, 60));
+        let exact = Fixed::from_roottwo(RootTwo::new(Dyadic(-5, 3), Dyadic(0, 0)), 1, Rounding::Up);
+        assert_eq!(exact, fixed(-1, 1));
+        assert!(
+            (Fixed::from_roottwo(tiny, 120, Rounding::Nearest).to_f64() - f64::from(tiny)).abs()
+                < 1e-30
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn sqrt_negative() {
+        fixed(-1, 0).sqrt(Rounding::Down);
+    }
+}
@@ -0,0 +1,279 @@
+use std::ops;
+
+use num::{pow, One, Zero};
+
+use crate::{Adj, Quad};
+
+/// An element `a + bτ` of the ring generated by the golden ratio
+/// `τ = (1 + √5)/2`, which satisfies `τ² = τ + 1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Golden<T>(pub(crate) T, pub(crate) T);
+
+/// The ring ℤ[τ], the ring of integers of ℚ(√5).
+pub type ZTau = Golden<i64>;
+
+/// The ring ℤ[√5], an index-2 subring of ℤ[τ].
+pub type RootFive<T> = Quad<T, 5>;
+
+impl<T> Golden<T> {
+    pub fn new(a: T, b: T) -> Golden<T> {
+        Golden(a, b)
+    }
+
+    /// The Galois conjugate, sending `τ` to `1 - τ`.
+    pub fn conj(self) -> Golden<T>
+    where
+        T: ops::Add<Output = T> + ops::Neg<Output = T> + Copy,
+    {
+        Golden(self.0 + self.1, -self.1)
+    }
+
+    /// The field norm `(a + bτ)(a + b - bτ) == a² + ab - b²`.
+    pub fn norm(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+    {
+        self.0 * self.0 + self.0 * self.1 - self.1 * self.1
+    }
+}
+
+impl ZTau {
+    pub fn is_unit(self) -> bool {
+        self.norm().abs() == 1
+    }
+
+    /// The inverse of a unit, which is `±conj(self)` with the sign of the
+    /// norm. For example `τ⁻¹ == τ - 1`.
+    pub fn inv(self) -> Option<ZTau> {
+        match self.norm() {
+            1 => Some(self.conj()),
+            -1 => Some(-self.conj()),
+            _ => None,
+        }
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####        Traits for Golden        ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Golden<T> {
+    type Output = Golden<T>;
+    fn add(self, rhs: Golden<T>) -> Self::Output {
+        Golden(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Golden<T> {
+    type Output = Golden<T>;
+    fn sub(self, rhs: Golden<T>) -> Self::Output {
+        Golden(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Golden<T> {
+    type Output = Golden<T>;
+    fn neg(self) -> Self::Output {
+        Golden(-self.0, -self.1)
+    }
+}
+
+impl<T: ops::AddAssign> ops::AddAssign for Golden<T> {
+    fn add_assign(&mut self, rhs: Golden<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<T: ops::SubAssign> ops::SubAssign for Golden<T> {
+    fn sub_assign(&mut self, rhs: Golden<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl<T> ops::Mul for Golden<T>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + Copy,
+{
+    type Output = Golden<T>;
+    fn mul(self, rhs: Golden<T>) -> Self::Output {
+        let bd = self.1 * rhs.1;
+        Golden(self.0 * rhs.0 + bd, self.0 * rhs.1 + self.1 * rhs.0 + bd)
+    }
+}
+
+impl<T: ops::Mul<i64, Output = T>> ops::Mul<i64> for Golden<T> {
+    type Output = Golden<T>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Golden(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<T> ops::MulAssign for Golden<T>
+where
+    Golden<T>: ops::Mul<Output = Golden<T>> + Clone,
+{
+    fn mul_assign(&mut self, rhs: Golden<T>) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<'a, T> ops::Add for &'a Golden<T>
+where
+    &'a T: ops::Add<Output = T>,
+{
+    type Output = Golden<T>;
+    fn add(self, rhs: &'a Golden<T>) -> Self::Output {
+        Golden(&self.0 + &rhs.0, &self.1 + &rhs.1)
+    }
+}
+
+impl<'a, T> ops::Sub for &'a Golden<T>
+where
+    &'a T: ops::Sub<Output = T>,
+{
+    type Output = Golden<T>;
+    fn sub(self, rhs: &'a Golden<T>) -> Self::Output {
+        Golden(&self.0 - &rhs.0, &self.1 - &rhs.1)
+    }
+}
+
+impl<'a, T> ops::Neg for &'a Golden<T>
+where
+    &'a T: ops::Neg<Output = T>,
+{
+    type Output = Golden<T>;
+    fn neg(self) -> Self::Output {
+        Golden(-&self.0, -&self.1)
+    }
+}
+
+impl<'a, T> ops::Mul for &'a Golden<T>
+where
+    &'a T: ops::Mul<Output = T>,
+    T: ops::Add<Output = T> + Clone,
+{
+    type Output = Golden<T>;
+    fn mul(self, rhs: &'a Golden<T>) -> Self::Output {
+        let bd = &self.1 * &rhs.1;
+        Golden(
+            &self.0 * &rhs.0 + bd.clone(),
+            &self.0 * &rhs.1 + &self.1 * &rhs.0 + bd,
+        )
+    }
+}
+
+impl<T: ops::MulAssign<i64>> ops::MulAssign<i64> for Golden<T> {
+    fn mul_assign(&mut self, rhs: i64) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+    }
+}
+
+impl pow::Pow<u32> for ZTau {
+    type Output = ZTau;
+    fn pow(self, power: u32) -> Self::Output {
+        pow::pow(self, power as usize)
+    }
+}
+
+impl<T: Zero> Zero for Golden<T> {
+    fn zero() -> Self {
+        Golden(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<T> One for Golden<T>
+where
+    T: Zero + One + PartialEq,
+    Golden<T>: ops::Mul<Output = Self>,
+{
+    fn one() -> Self {
+        Golden(T::one(), T::zero())
+    }
+
+    fn is_one(&self) -> bool {
+        self.0.is_one() && self.1.is_zero()
+    }
+}
+
+impl<T> Adj for Golden<T> {
+    fn adj(self) -> Self {
+        self
+    }
+}
+
+impl<T: Into<f64>> From<Golden<T>> for f64 {
+    fn from(val: Golden<T>) -> Self {
+        let tau = (1.0 + 5f64.sqrt()) / 2.0;
+        val.0.into() + val.1.into() * tau
+    }
+}
+
+impl From<RootFive<i64>> for ZTau {
+    /// Embeds `a + b√5` using `√5 = 2τ - 1`.
+    fn from(val: RootFive<i64>) -> Self {
+        Golden(val.0 - val.1, 2 * val.1)
+    }
+}
+
+#[cfg(test)]
+mod golden_tests {
+    use super::*;
+    use num::pow::Pow;
+    #[test]
+    fn units_ztau() {
+        let tau = Golden(0, 1);
+        assert!(tau.is_unit());
+        assert_eq!(tau.inv(), Some(Golden(-1, 1)));
+        assert_eq!(tau.pow(7).inv().unwrap() * tau.pow(7), Golden(1, 0));
+        assert!(!Golden(2, 0).is_unit());
+        assert_eq!(Golden(3, 1).inv(), None);
+    }
+
+    #[test]
+    fn tau_squared() {
+        let tau = Golden(0, 1);
+        assert_eq!(tau * tau, tau + Golden(1, 0));
+    }
+
+    #[test]
+    fn powers_of_tau_are_fibonacci() {
+        // τⁿ == F(n-1) + F(n)τ
+        let tau = Golden(0, 1);
+        assert_eq!(tau.pow(0), Golden(1, 0));
+        assert_eq!(tau.pow(10), Golden(34, 55));
+        assert_eq!(tau.pow(10).norm(), 1);
+    }
+
+    #[test]
+    fn norm_and_conj() {
+        let x = Golden(3, 4);
+        assert_eq!(x.norm(), 5);
+        assert_eq!(x * x.conj(), Golden(x.norm(), 0));
+    }
+
+    #[test]
+    fn from_rootfive() {
+        let first = RootFive::new(3, 4);
+        let second = RootFive::new(-1, 2);
+        assert_eq!(
+            ZTau::from(first) * ZTau::from(second),
+            ZTau::from(first * second)
+        );
+        let approx: f64 = Golden(1.0, 1.0).into();
+        assert!((approx - 2.618033988749895).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,202 @@
+use std::{cmp::Ordering, ops};
+
+use crate::{Dyadic, RootTwo};
+
+/// A closed interval `[lo, hi]` of exact endpoints, by default dyadic.
+/// Arithmetic on intervals encloses every result of the same arithmetic on
+/// their points, so bounds on irrationals such as `√2`-expressions compose
+/// into certified bounds on the whole computation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T = Dyadic>(pub(crate) T, pub(crate) T);
+
+impl<T: Ord> Interval<T> {
+    /// # Panics
+    ///
+    /// Panics if `lo > hi`.
+    pub fn new(lo: T, hi: T) -> Interval<T> {
+        assert!(lo <= hi, "interval endpoints out of order");
+        Interval(lo, hi)
+    }
+
+    pub fn lo(&self) -> &T {
+        &self.0
+    }
+
+    pub fn hi(&self) -> &T {
+        &self.1
+    }
+
+    /// Whether `lo ≤ x ≤ hi`.
+    pub fn contains(&self, x: &T) -> bool {
+        self.0 <= *x && *x <= self.1
+    }
+
+    /// Whether every point of `other` lies in `self`.
+    pub fn encloses(&self, other: &Interval<T>) -> bool {
+        self.0 <= other.0 && other.1 <= self.1
+    }
+
+    /// The ordering of every point of `self` against every point of `other`,
+    /// or `None` if the intervals overlap and the order is not certain.
+    pub fn compare(&self, other: &Interval<T>) -> Option<Ordering> {
+        if self.1 < other.0 {
+            Some(Ordering::Less)
+        } else if self.0 > other.1 {
+            Some(Ordering::Greater)
+        } else if self.0 == self.1 && other.0 == other.1 && self.0 == other.0 {
+            Some(Ordering::Equal)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Clone> Interval<T> {
+    /// The interval holding only `x`.
+    pub fn point(x: T) -> Interval<T> {
+        Interval(x.clone(), x)
+    }
+}
+
+impl<T: ops::Sub<Output = T> + Clone> Interval<T> {
+    /// `hi - lo`
+    pub fn width(&self) -> T {
+        self.1.clone() - self.0.clone()
+    }
+}
+
+impl RootTwo<Dyadic> {
+    /// The interval between the multiples of `2⁻ᵖ` on either side of `self`,
+    /// where `p` is `precision`. It is a point exactly when `self` is such a
+    /// multiple.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `2ᵖ·self` does not fit in an `i64`.
+    pub fn to_interval(self, precision: u32) -> Interval {
+        let scaled = self
+            .mul_pow2(precision as i32)
+            .expect("2ᵖ·self overflows i64");
+        Interval(
+            Dyadic::new(scaled.floor(), precision),
+            Dyadic::new(scaled.ceil(), precision),
+        )
+    }
+}
+
+impl RootTwo<i64> {
+    /// The interval between the multiples of `2⁻ᵖ` on either side of `self`,
+    /// like [`RootTwo::<Dyadic>::to_interval`].
+    pub fn to_interval(self, precision: u32) -> Interval {
+        RootTwo::from_normalized(self, 0).to_interval(precision)
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####       Traits for Interval       ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Interval<T> {
+    type Output = Interval<T>;
+    fn add(self, rhs: Interval<T>) -> Self::Output {
+        Interval(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Interval<T> {
+    type Output = Interval<T>;
+    fn sub(self, rhs: Interval<T>) -> Self::Output {
+        Interval(self.0 - rhs.1, self.1 - rhs.0)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Interval<T> {
+    type Output = Interval<T>;
+    fn neg(self) -> Self::Output {
+        Interval(-self.1, -self.0)
+    }
+}
+
+impl<T: ops::Mul<Output = T> + Ord + Copy> ops::Mul for Interval<T> {
+    type Output = Interval<T>;
+    /// The least and greatest of the products of endpoints.
+    fn mul(self, rhs: Interval<T>) -> Self::Output {
+        let products = [
+            self.0 * rhs.0,
+            self.0 * rhs.1,
+            self.1 * rhs.0,
+            self.1 * rhs.1,
+        ];
+        let lo = products.into_iter().min().unwrap();
+        let hi = products.into_iter().max().unwrap();
+        Interval(lo, hi)
+    }
+}
+
+#[cfg(test)]
+mod interval_tests {
+    use super::*;
+    use num::Zero;
+
+    #[test]
+    fn enclosures_of_roottwo() {
+        let root_two = RootTwo::new(0, 1).to_interval(10);
+        assert_eq!(root_two, Interval(Dyadic(181, 7), Dyadic(1449, 10)));
+        assert!(root_two.width() == Dyadic(1, 10));
+        assert_eq!(
+            RootTwo::new(3, 0).to_interval(5),
+            Interval::point(Dyadic(3, 0))
+        );
+        let x = RootTwo::new(Dyadic(-5, 3), Dyadic(7, 4));
+        let enclosure = x.to_interval(20);
+        assert!(RootTwo::new(*enclosure.lo(), Dyadic::zero()) < x);
+        assert!(RootTwo::new(*enclosure.hi(), Dyadic::zero()) > x);
+        // the square of an enclosure of √2 encloses 2
+        assert!((root_two * root_two).contains(&Dyadic(2, 0)));
+        assert!(!(root_two + root_two).contains(&Dyadic(3, 0)));
+    }
+
+    #[test]
+    fn arithmetic_encloses_points() {
+        let x = Interval::new(Dyadic(-3, 1), Dyadic(5, 2));
+        let y = Interval::new(Dyadic(1, 0), Dyadic(3, 0));
+        assert_eq!(x + y, Interval(Dyadic(-1, 1), Dyadic(17, 2)));
+        assert_eq!(x - y, Interval(Dyadic(-9, 1), Dyadic(1, 2)));
+        assert_eq!(-x, Interval(Dyadic(-5, 2), Dyadic(3, 1)));
+        assert_eq!(x * y, Interval(Dyadic(-9, 1), Dyadic(15, 2)));
+        assert_eq!(x * x, Interval(Dyadic(-15, 3), Dyadic(9, 2)));
+        for (a, b) in [(Dyadic(-3, 1), Dyadic(1, 0)), (Dyadic(1, 3), Dyadic(5, 2))] {
+            assert!((x * y).contains(&(a * b)));
+            assert!((x - y).contains(&(a - b)));
+        }
+        assert!((x + y).encloses(&(Interval::point(Dyadic(0, 0)) + y)));
+        assert!(!y.encloses(&x));
+    }
+
+    #[test]
+    fn certified_comparisons() {
+        // λ⁻¹⁰ < 2⁻¹² is decided once the enclosure is narrow enough
+        let tiny = RootTwo::new(3363, -2378);
+        let bound = Interval::point(Dyadic(1, 12));
+        assert_eq!(tiny.to_interval(8).compare(&bound), None);
+        assert_eq!(tiny.to_interval(20).compare(&bound), Some(Ordering::Less));
+        assert_eq!(
+            bound.compare(&tiny.to_interval(20)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(bound.compare(&bound), Some(Ordering::Equal));
+        // intervals over ℤ[√2] itself
+        let z = Interval::new(RootTwo::new(1, -1), RootTwo::new(0, 1));
+        assert!(z.contains(&RootTwo::new(2, -1)));
+        assert!(!z.contains(&RootTwo::new(2, 0)));
+        assert_eq!(z * z, Interval(RootTwo::new(-2, 1), RootTwo::new(2, 0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn endpoints_out_of_order() {
+        Interval::new(Dyadic(1, 0), Dyadic(0, 0));
+    }
+}
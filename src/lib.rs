@@ -1,216 +1,138 @@
-use std::{f64::consts::SQRT_2, ops};
-
-use num::pow;
+mod approx;
+#[cfg(feature = "proptest")]
+pub mod arbitrary;
+#[cfg(feature = "rkyv")]
+mod archive;
+#[cfg(feature = "bigint")]
+mod bigint;
+mod contfrac;
+mod cplx;
+mod cyclotomic;
+pub mod diophantine;
+mod dyadic;
+mod eisenstein;
+pub mod encoding;
+mod error;
+mod factor;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fixed;
+mod golden;
+mod interval;
+mod matrix;
+mod matrix2;
+pub mod newsynth;
+pub mod numtheory;
+mod omega;
+#[cfg(feature = "python")]
+mod python;
+mod quad;
+mod quaternion;
+#[cfg(feature = "rand")]
+pub mod random;
+#[cfg(test)]
+mod reference;
+mod ring;
+#[cfg(feature = "serde")]
+mod serialize;
+mod so3;
+pub mod synthesis;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod zi;
+
+#[cfg(feature = "bigint")]
+pub use bigint::ToReal;
+pub use contfrac::ContinuedFraction;
+pub use cplx::Cplx;
+pub use cyclotomic::Cyclotomic;
+pub use dyadic::Dyadic;
+pub use eisenstein::Eisenstein;
+pub use encoding::Encode;
+pub use error::{DecodeError, DimensionError, FromFloatError, FromRatioError, ParseRingError};
+pub use factor::Factorization;
+pub use fixed::{Fixed, Rounding};
+pub use golden::{Golden, RootFive, ZTau};
+pub use interval::Interval;
+pub use matrix::Matrix;
+pub use matrix2::{Matrix2, Vector2};
+pub use omega::{DOmega, Omega, ZOmega};
+pub use quad::{QRootTwo, Quad, RootMinusTwo, RootTwo};
+pub use quaternion::Quaternion;
+pub use ring::{
+    CommutativeRing, ComplexRing, EuclideanDomain, Field, HalfRing, NormedRing, OmegaRing, Ring,
+    RootHalfRing, RootTwoRing,
+};
+pub use so3::SO3Matrix;
+pub use zi::ZI;
 
 /// The root-two conjugate. `adj2(a + b√2) == a - b√2`
 pub trait Adj2 {
     fn adj2(self) -> Self;
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct RootTwo<T>(T, T);
-
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct Dyadic(i64, u32);
-
-// #########################################
-// #######                           #######
-// ####        Traits for RootTwo       ####
-// #######                           #######
-// #########################################
-
-impl<T: ops::Add<Output = T>> ops::Add for RootTwo<T> {
-    type Output = RootTwo<T>;
-    fn add(self, rhs: RootTwo<T>) -> Self::Output {
-        RootTwo(self.0 + rhs.0, self.1 + rhs.1)
-    }
+/// The complex conjugate. `adj(a + bi) == a - bi`, which is the identity on
+/// real rings.
+pub trait Adj {
+    fn adj(self) -> Self;
 }
 
-impl<T: ops::Sub<Output = T>> ops::Sub for RootTwo<T> {
-    type Output = RootTwo<T>;
-    fn sub(self, rhs: RootTwo<T>) -> Self::Output {
-        RootTwo(self.0 - rhs.0, self.1 - rhs.1)
-    }
-}
+/// Splits an element into its component in the integer subring and an exact
+/// fractional remainder, coefficient by coefficient. Each coefficient of the
+/// fractional part lies in `[0, 1)`.
+pub trait WholePart: Sized {
+    /// The integer subring, e.g. `i64` for `Dyadic`.
+    type Whole;
 
-impl<T: ops::Neg<Output = T>> ops::Neg for RootTwo<T> {
-    type Output = RootTwo<T>;
-    fn neg(self) -> Self::Output {
-        RootTwo(-self.0, -self.1)
-    }
-}
+    /// The image of an element of the integer subring.
+    fn from_whole(whole: Self::Whole) -> Self;
 
-impl<T> ops::Mul for RootTwo<T>
-where
-    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Mul<i64, Output = T> + Copy,
-{
-    type Output = RootTwo<T>;
-    fn mul(self, rhs: RootTwo<T>) -> Self::Output {
-        RootTwo(
-            self.0 * rhs.0 + self.1 * rhs.1 * 2,
-            self.0 * rhs.1 + self.1 * rhs.0,
-        )
-    }
-}
+    /// The coefficientwise floor.
+    fn whole_part(self) -> Self::Whole;
 
-impl pow::Pow<u32> for RootTwo<i64> {
-    type Output = RootTwo<i64>;
-    fn pow(self, power: u32) -> Self::Output {
-        if power == 0 {
-            return RootTwo(0, 0);
-        }
-        // if power < 0 {
-        //     return 1 / pow(self, -power);
-        // }
-        let mut result = self;
-        let mut power = power - 1;
-        while power > 0 {
-            result = result * self;
-            power -= 1;
-        }
-        result
-    }
-}
-
-impl<T: Into<f64>> Into<f64> for RootTwo<T> {
-    fn into(self) -> f64 {
-        self.0.into() + self.1.into() * SQRT_2
-    }
+    /// `self - from_whole(whole_part(self))`, or `None` if a coefficient
+    /// does not fit, as for `1 - 2⁻⁷⁰` from the dyadic `-2⁻⁷⁰`.
+    fn frac_part(self) -> Option<Self>;
 }
 
-impl<T: ops::Neg<Output = T>> Adj2 for RootTwo<T> {
-    fn adj2(self) -> Self {
-        RootTwo(self.0, -self.1)
-    }
+/// The least denominator exponent of an element: the smallest `k` such that
+/// `2ᵏ·x` lands in the integer subring, or `√2ᵏ·x` for rings containing `√2`.
+pub trait DenomExp {
+    fn denomexp(&self) -> u32;
 }
 
-// #########################################
-// #######                           #######
-// ####        Traits for Dyadic        ####
-// #######                           #######
-// #########################################
-
-impl Dyadic {
-    fn simplify(mut x: i64, mut k: u32) -> Dyadic {
-        // keep dividing by 2 while numerator is power of 2
-        //
-        // TODO: consider adding an enum, then returning i64 if k is 0
-        while x > 0 && k > 0 && (x & (x - 1)) == 0 {
-            x /= 2;
-            k -= 1;
-        }
-        Dyadic(x, k)
-    }
+/// The residue of an integral element modulo 2, with each coefficient reduced
+/// to `0` or `1`.
+pub trait Parity {
+    type Residue;
+    fn residue(&self) -> Self::Residue;
 }
 
-impl ops::Add for Dyadic {
-    type Output = Dyadic;
-    fn add(self, rhs: Dyadic) -> Self::Output {
-        if self.1 == rhs.1 {
-            return Dyadic::simplify(self.0 + rhs.0, self.1);
-        }
-        let (a, b) = if self.1 < rhs.1 {
-            (self, rhs)
-        } else {
-            (rhs, self)
-        };
-        let k_delta = 1i64 << (b.1 - a.1);
-        Dyadic::simplify(a.0 * k_delta + b.0, b.1)
+impl Parity for i64 {
+    type Residue = u8;
+    fn residue(&self) -> u8 {
+        self.rem_euclid(2) as u8
     }
 }
 
-impl ops::Sub for Dyadic {
-    type Output = Dyadic;
-    fn sub(self, rhs: Dyadic) -> Self::Output {
-        self + -rhs
-    }
+/// Rendering as LaTeX math, such as `\frac{3}{2^{2}}` or `1 - 2\sqrt{2}`,
+/// without surrounding `$` delimiters.
+pub trait ToLatex {
+    fn to_latex(&self) -> String;
 }
 
-impl ops::Neg for Dyadic {
-    type Output = Dyadic;
-    fn neg(self) -> Self::Output {
-        Dyadic(-self.0, self.1)
+impl ToLatex for i64 {
+    fn to_latex(&self) -> String {
+        self.to_string()
     }
 }
 
-impl ops::Mul for Dyadic {
-    type Output = Dyadic;
-    fn mul(self, rhs: Self) -> Self::Output {
-        Dyadic::simplify(self.0 * rhs.0, self.1 + rhs.1)
-    }
-}
-
-impl ops::Mul<i64> for Dyadic {
-    type Output = Dyadic;
-    fn mul(self, rhs: i64) -> Self::Output {
-        Dyadic::simplify(self.0 * rhs, self.1)
-    }
-}
-
-impl ops::Mul<Dyadic> for i64 {
-    type Output = Dyadic;
-    fn mul(self, rhs: Dyadic) -> Self::Output {
-        Dyadic::simplify(self * rhs.0, rhs.1)
-    }
-}
-
-impl Into<f64> for Dyadic {
-    fn into(self) -> f64 {
-        let num = self.0 as f64;
-        let denom = (1i64 << self.1) as f64;
-        num / denom
-    }
-}
-
-#[cfg(test)]
-mod roottwo_tests {
-    use super::*;
-    #[test]
-    fn basic_add_zroottwo() {
-        let first = RootTwo(1, 2);
-        let second = RootTwo(3, 4);
-        assert_eq!(first + second, RootTwo(4, 6));
-        assert_eq!(first - second, RootTwo(-2, -2));
-    }
-
-    #[test]
-    fn basic_add_droottwo() {
-        let first = RootTwo(Dyadic(3, 2), Dyadic(3, 7));
-        let second = RootTwo(Dyadic(4, 2), Dyadic(3, 8));
-        assert_eq!(first + second, RootTwo(Dyadic(7, 2), Dyadic(9, 8)));
-        assert_eq!(first - second, RootTwo(Dyadic(-1, 2), Dyadic(3, 8)));
-    }
-
-    #[test]
-    fn mul_two_root_twos() {
-        let first = RootTwo(3, 4);
-        let second = RootTwo(5, 6);
-        let expected = RootTwo(63, 38);
-        assert_eq!(first * second, expected);
-        assert_eq!(second * first, expected);
-    }
-}
-
-#[cfg(test)]
-mod dyadic_tests {
-    use super::*;
-    #[test]
-    fn basic_add_dyadic() {
-        let first = Dyadic(3, 2);
-        let second = Dyadic(1, 2);
-        assert_eq!(first + second, Dyadic(1, 0));
-        assert_eq!(-Dyadic(3, 2), Dyadic(-3, 2));
-    }
-
-    #[test]
-    fn into_float_works() {
-        assert_eq!(Into::<f64>::into(Dyadic(3, 2)), 0.75);
-    }
-
-    #[test]
-    fn powers_of_two() {
-        for i in 0..20 {
-            assert_eq!(1 << i, i64::pow(2, i))
+impl ToLatex for num::Rational64 {
+    fn to_latex(&self) -> String {
+        match (self.numer().signum(), *self.denom()) {
+            (_, 1) => self.numer().to_string(),
+            (-1, d) => format!("-\\frac{{{}}}{{{d}}}", self.numer().unsigned_abs()),
+            (_, d) => format!("\\frac{{{}}}{{{d}}}", self.numer()),
         }
     }
 }
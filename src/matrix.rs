@@ -0,0 +1,318 @@
+use std::ops;
+
+use num::{One, Zero};
+
+use crate::{Adj, Adj2, DimensionError, Matrix2, Ring};
+
+/// A square matrix of any dimension, stored row-major on the heap. The
+/// Kronecker product builds multi-qubit operators from [`Matrix2`] gates,
+/// which convert to and from the 2×2 case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Matrix<T> {
+    n: usize,
+    entries: Vec<T>,
+}
+
+impl<T> Matrix<T> {
+    /// The `n×n` matrix with the given entries in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are not exactly `n²` entries.
+    pub fn new(n: usize, entries: Vec<T>) -> Matrix<T> {
+        assert_eq!(
+            entries.len(),
+            n * n,
+            "a {n}×{n} matrix needs {} entries",
+            n * n
+        );
+        Matrix { n, entries }
+    }
+
+    /// The matrix whose `(i, j)` entry is `f(i, j)`.
+    pub fn from_fn(n: usize, mut f: impl FnMut(usize, usize) -> T) -> Matrix<T> {
+        let entries = (0..n * n).map(|k| f(k / n, k % n)).collect();
+        Matrix { n, entries }
+    }
+
+    /// The number of rows, which is also the number of columns.
+    pub fn dim(&self) -> usize {
+        self.n
+    }
+
+    /// The entries in row-major order.
+    pub fn entries(&self) -> &[T] {
+        &self.entries
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.entries[i * self.n..(i + 1) * self.n]
+    }
+
+    pub fn transpose(&self) -> Matrix<T>
+    where
+        T: Clone,
+    {
+        Matrix::from_fn(self.n, |i, j| self[(j, i)].clone())
+    }
+
+    pub fn zero(n: usize) -> Matrix<T>
+    where
+        T: Zero + Clone,
+    {
+        Matrix::new(n, vec![T::zero(); n * n])
+    }
+
+    pub fn identity(n: usize) -> Matrix<T>
+    where
+        T: Zero + One,
+    {
+        Matrix::from_fn(n, |i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    /// The Kronecker product `self ⊗ rhs`, in which `self` acts on the more
+    /// significant index. For gates, `(A ⊗ B)(x ⊗ y) == Ax ⊗ By`.
+    pub fn kron(&self, rhs: &Matrix<T>) -> Matrix<T>
+    where
+        T: ops::Mul<Output = T> + Clone,
+    {
+        let m = rhs.n;
+        Matrix::from_fn(self.n * m, |i, j| {
+            self[(i / m, j / m)].clone() * rhs[(i % m, j % m)].clone()
+        })
+    }
+
+    /// The matrix applied to a column vector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have `dim()` entries.
+    pub fn apply(&self, v: &[T]) -> Vec<T>
+    where
+        T: Zero + ops::Mul<Output = T> + Clone,
+    {
+        assert_eq!(v.len(), self.n, "vector length does not match dimension");
+        (0..self.n)
+            .map(|i| {
+                self.row(i)
+                    .iter()
+                    .zip(v)
+                    .fold(T::zero(), |acc, (a, x)| acc + a.clone() * x.clone())
+            })
+            .collect()
+    }
+
+    /// Multiplies every entry by `s` on the left.
+    pub fn scale(&self, s: T) -> Matrix<T>
+    where
+        T: ops::Mul<Output = T> + Clone,
+    {
+        Matrix {
+            n: self.n,
+            entries: self.entries.iter().map(|x| s.clone() * x.clone()).collect(),
+        }
+    }
+
+    fn zip_with(self, rhs: Matrix<T>, f: impl Fn(T, T) -> T) -> Matrix<T> {
+        assert_eq!(self.n, rhs.n, "matrix dimensions do not match");
+        let entries = self
+            .entries
+            .into_iter()
+            .zip(rhs.entries)
+            .map(|(a, b)| f(a, b))
+            .collect();
+        Matrix { n: self.n, entries }
+    }
+}
+
+impl<T: Ring + Adj> Matrix<T> {
+    /// Whether `U†U == I`, decided exactly.
+    pub fn is_unitary(&self) -> bool {
+        &self.clone().adj() * self == Matrix::identity(self.n)
+    }
+
+    /// Whether `self == self†`.
+    pub fn is_hermitian(&self) -> bool {
+        self.clone().adj() == *self
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####        Traits for Matrix        ####
+// #######                           #######
+// #########################################
+
+impl<T> ops::Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        assert!(i < self.n && j < self.n, "index ({i}, {j}) out of range");
+        &self.entries[i * self.n + j]
+    }
+}
+
+impl<T> ops::IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        assert!(i < self.n && j < self.n, "index ({i}, {j}) out of range");
+        &mut self.entries[i * self.n + j]
+    }
+}
+
+/// # Panics
+///
+/// Panics if the dimensions differ, and likewise for subtraction and
+/// multiplication.
+impl<T: ops::Add<Output = T>> ops::Add for Matrix<T> {
+    type Output = Matrix<T>;
+    fn add(self, rhs: Matrix<T>) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Matrix<T> {
+    type Output = Matrix<T>;
+    fn sub(self, rhs: Matrix<T>) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Matrix<T> {
+    type Output = Matrix<T>;
+    fn neg(self) -> Self::Output {
+        Matrix {
+            n: self.n,
+            entries: self.entries.into_iter().map(|x| -x).collect(),
+        }
+    }
+}
+
+impl<T: Zero + ops::Mul<Output = T> + Clone> ops::Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+    fn mul(self, rhs: &Matrix<T>) -> Self::Output {
+        assert_eq!(self.n, rhs.n, "matrix dimensions do not match");
+        let n = self.n;
+        Matrix::from_fn(n, |i, j| {
+            (0..n).fold(T::zero(), |acc, k| {
+                acc + self[(i, k)].clone() * rhs[(k, j)].clone()
+            })
+        })
+    }
+}
+
+impl<T: Zero + ops::Mul<Output = T> + Clone> ops::Mul for Matrix<T> {
+    type Output = Matrix<T>;
+    fn mul(self, rhs: Matrix<T>) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+impl<T: Adj + Clone> Adj for Matrix<T> {
+    /// The Hermitian adjoint, the conjugate transpose.
+    fn adj(self) -> Self {
+        Matrix::from_fn(self.n, |i, j| self[(j, i)].clone().adj())
+    }
+}
+
+impl<T: Adj2> Adj2 for Matrix<T> {
+    fn adj2(self) -> Self {
+        Matrix {
+            n: self.n,
+            entries: self.entries.into_iter().map(Adj2::adj2).collect(),
+        }
+    }
+}
+
+impl<T> From<Matrix2<T>> for Matrix<T> {
+    fn from(val: Matrix2<T>) -> Self {
+        Matrix::new(2, vec![val.0, val.1, val.2, val.3])
+    }
+}
+
+impl<T> TryFrom<Matrix<T>> for Matrix2<T> {
+    type Error = DimensionError;
+
+    fn try_from(val: Matrix<T>) -> Result<Self, Self::Error> {
+        if val.n != 2 {
+            return Err(DimensionError {
+                expected: 2,
+                found: val.n,
+            });
+        }
+        let mut it = val.entries.into_iter();
+        let mut next = || it.next().unwrap();
+        Ok(Matrix2(next(), next(), next(), next()))
+    }
+}
+
+#[cfg(test)]
+mod matrix_tests {
+    use super::*;
+    use crate::{DOmega, Omega, OmegaRing, RootHalfRing, ZOmega};
+
+    fn hadamard() -> Matrix2<DOmega> {
+        let s = DOmega::roothalf();
+        Matrix2(s, s, s, -s)
+    }
+
+    #[test]
+    fn kron_of_gates() {
+        let (h, t) = (
+            hadamard(),
+            Matrix2(
+                DOmega::one(),
+                DOmega::zero(),
+                DOmega::zero(),
+                DOmega::omega(),
+            ),
+        );
+        let (hm, tm) = (Matrix::from(h), Matrix::from(t));
+        let ht = hm.kron(&tm);
+        assert_eq!(ht.dim(), 4);
+        // (H ⊗ T)(H ⊗ T†) == I ⊗ I
+        let id = Matrix::<DOmega>::identity(4);
+        assert_eq!(&ht * &hm.kron(&tm.clone().adj()), id);
+        assert_eq!(ht.clone().adj(), hm.clone().adj().kron(&tm.clone().adj()));
+        // the mixed-product property
+        let (a, b) = (Matrix::from(h * t), Matrix::from(t * h));
+        assert_eq!(&hm.kron(&tm) * &tm.kron(&hm), a.kron(&b));
+        assert_eq!(ht[(1, 1)], DOmega::roothalf() * DOmega::omega());
+        assert_eq!(ht[(2, 0)], DOmega::roothalf());
+        assert!(ht.is_unitary() && !ht.is_hermitian());
+        assert!(hm.kron(&hm).is_hermitian());
+        assert!(!(hm.clone() + hm).is_unitary());
+    }
+
+    #[test]
+    fn arithmetic_matrix() {
+        let x: Matrix<ZOmega> = Matrix::from_fn(3, |i, j| Omega(i as i64, j as i64, 0, 1));
+        let y: Matrix<ZOmega> = Matrix::identity(3).scale(Omega(0, 0, 1, 0));
+        assert_eq!((x.clone() + y.clone()) - y.clone(), x);
+        assert_eq!(-x.clone() + x.clone(), Matrix::zero(3));
+        assert_eq!(x.transpose().transpose(), x);
+        assert_eq!(&x * &Matrix::identity(3), x);
+        assert_eq!((&x * &y).adj(), &y.clone().adj() * &x.clone().adj());
+        assert_eq!(
+            x.clone().adj2().row(2),
+            &[Omega(2, 0, 0, -1), Omega(2, -1, 0, -1), Omega(2, -2, 0, -1)]
+        );
+        let v = vec![Omega(1, 0, 0, 0), Omega(0, 0, 0, 0), Omega(0, 0, 0, 0)];
+        assert_eq!(x.apply(&v), vec![x[(0, 0)], x[(1, 0)], x[(2, 0)]]);
+        let mut z = x.clone();
+        z[(0, 0)] = Omega(5, 0, 0, 0);
+        assert_eq!(z.entries()[0], Omega(5, 0, 0, 0));
+    }
+
+    #[test]
+    fn matrix2_round_trip() {
+        let h = hadamard();
+        assert_eq!(Matrix2::try_from(Matrix::from(h)), Ok(h));
+        assert_eq!(
+            Matrix2::try_from(Matrix::<DOmega>::identity(4)),
+            Err(DimensionError {
+                expected: 2,
+                found: 4
+            })
+        );
+        assert_eq!(Matrix::from(h * h), &Matrix::from(h) * &Matrix::from(h));
+    }
+}
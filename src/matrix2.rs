@@ -0,0 +1,356 @@
+use std::ops;
+
+use num::{One, Zero};
+
+use crate::{Adj, Adj2, Ring};
+
+/// A 2×2 matrix `[[a, b], [c, d]]` over any of the crate's rings, stored in
+/// row-major order. Over D[ω] these are exactly the single-qubit Clifford+T
+/// operators, up to the unitarity condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Matrix2<T>(pub(crate) T, pub(crate) T, pub(crate) T, pub(crate) T);
+
+impl<T> Matrix2<T> {
+    /// The matrix with rows `[a, b]` and `[c, d]`.
+    pub fn new(a: T, b: T, c: T, d: T) -> Matrix2<T> {
+        Matrix2(a, b, c, d)
+    }
+
+    /// The entries as rows.
+    pub fn rows(self) -> [[T; 2]; 2] {
+        [[self.0, self.1], [self.2, self.3]]
+    }
+
+    pub fn transpose(self) -> Matrix2<T> {
+        Matrix2(self.0, self.2, self.1, self.3)
+    }
+
+    /// The determinant `ad - bc`, which is multiplicative over commutative
+    /// rings.
+    pub fn det(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Sub<Output = T>,
+    {
+        self.0 * self.3 - self.1 * self.2
+    }
+
+    /// The trace `a + d`.
+    pub fn trace(self) -> T
+    where
+        T: ops::Add<Output = T>,
+    {
+        self.0 + self.3
+    }
+
+    /// Scalar multiplication, with `s` on the left of every entry.
+    pub fn scale(self, s: T) -> Matrix2<T>
+    where
+        T: ops::Mul<Output = T> + Clone,
+    {
+        Matrix2(
+            s.clone() * self.0,
+            s.clone() * self.1,
+            s.clone() * self.2,
+            s * self.3,
+        )
+    }
+}
+
+impl<T: Ring + Adj> Matrix2<T> {
+    /// Whether `U†U == I`, decided exactly, so over D[ω] there is no
+    /// tolerance to choose.
+    pub fn is_unitary(&self) -> bool {
+        self.clone().adj() * self.clone() == Matrix2::one()
+    }
+
+    /// Whether `self` is unitary with determinant one, i.e. lies in SU(2).
+    pub fn is_special(&self) -> bool {
+        self.is_unitary() && self.clone().det() == T::one()
+    }
+
+    /// Whether `self == self†`.
+    pub fn is_hermitian(&self) -> bool {
+        self.clone().adj() == *self
+    }
+}
+
+/// A column vector `(x, y)`, such as a single-qubit state `x|0⟩ + y|1⟩`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vector2<T>(pub(crate) T, pub(crate) T);
+
+impl<T> Vector2<T> {
+    pub fn new(x: T, y: T) -> Vector2<T> {
+        Vector2(x, y)
+    }
+
+    pub fn entries(self) -> [T; 2] {
+        [self.0, self.1]
+    }
+
+    /// The bilinear product `x₀y₀ + x₁y₁`, with no conjugation.
+    pub fn dot(self, rhs: Vector2<T>) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T>,
+    {
+        self.0 * rhs.0 + self.1 * rhs.1
+    }
+
+    /// The Hermitian inner product `⟨self|rhs⟩ = x₀†y₀ + x₁†y₁`, conjugating
+    /// the left side as is usual in physics.
+    pub fn inner(self, rhs: Vector2<T>) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T> + Adj,
+    {
+        self.0.adj() * rhs.0 + self.1.adj() * rhs.1
+    }
+
+    /// The squared length `⟨self|self⟩`, which is real and exact.
+    pub fn norm_sqr(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T> + Adj + Clone,
+    {
+        self.clone().inner(self)
+    }
+
+    /// Scalar multiplication, with `s` on the left of both entries.
+    pub fn scale(self, s: T) -> Vector2<T>
+    where
+        T: ops::Mul<Output = T> + Clone,
+    {
+        Vector2(s.clone() * self.0, s * self.1)
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####       Traits for Matrix2        ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Matrix2<T> {
+    type Output = Matrix2<T>;
+    fn add(self, rhs: Matrix2<T>) -> Self::Output {
+        Matrix2(
+            self.0 + rhs.0,
+            self.1 + rhs.1,
+            self.2 + rhs.2,
+            self.3 + rhs.3,
+        )
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Matrix2<T> {
+    type Output = Matrix2<T>;
+    fn sub(self, rhs: Matrix2<T>) -> Self::Output {
+        Matrix2(
+            self.0 - rhs.0,
+            self.1 - rhs.1,
+            self.2 - rhs.2,
+            self.3 - rhs.3,
+        )
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Matrix2<T> {
+    type Output = Matrix2<T>;
+    fn neg(self) -> Self::Output {
+        Matrix2(-self.0, -self.1, -self.2, -self.3)
+    }
+}
+
+impl<T: ops::Mul<Output = T> + ops::Add<Output = T> + Clone> ops::Mul for Matrix2<T> {
+    type Output = Matrix2<T>;
+    fn mul(self, rhs: Matrix2<T>) -> Self::Output {
+        Matrix2(
+            self.0.clone() * rhs.0.clone() + self.1.clone() * rhs.2.clone(),
+            self.0 * rhs.1.clone() + self.1 * rhs.3.clone(),
+            self.2.clone() * rhs.0 + self.3.clone() * rhs.2,
+            self.2 * rhs.1 + self.3 * rhs.3,
+        )
+    }
+}
+
+/// Matrix–vector application.
+impl<T: ops::Mul<Output = T> + ops::Add<Output = T> + Clone> ops::Mul<Vector2<T>> for Matrix2<T> {
+    type Output = Vector2<T>;
+    fn mul(self, rhs: Vector2<T>) -> Self::Output {
+        Vector2(
+            self.0 * rhs.0.clone() + self.1 * rhs.1.clone(),
+            self.2 * rhs.0 + self.3 * rhs.1,
+        )
+    }
+}
+
+impl<T: Zero + PartialEq> Zero for Matrix2<T> {
+    fn zero() -> Self {
+        Matrix2(T::zero(), T::zero(), T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero() && self.2.is_zero() && self.3.is_zero()
+    }
+}
+
+impl<T: Zero + One + Clone> One for Matrix2<T> {
+    fn one() -> Self {
+        Matrix2(T::one(), T::zero(), T::zero(), T::one())
+    }
+}
+
+impl<T: Adj> Adj for Matrix2<T> {
+    /// The Hermitian adjoint, the conjugate transpose.
+    fn adj(self) -> Self {
+        Matrix2(self.0.adj(), self.2.adj(), self.1.adj(), self.3.adj())
+    }
+}
+
+impl<T: Adj2> Adj2 for Matrix2<T> {
+    /// The root-two conjugate of every entry, which is a ring homomorphism
+    /// of matrices.
+    fn adj2(self) -> Self {
+        Matrix2(self.0.adj2(), self.1.adj2(), self.2.adj2(), self.3.adj2())
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####       Traits for Vector2        ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Vector2<T> {
+    type Output = Vector2<T>;
+    fn add(self, rhs: Vector2<T>) -> Self::Output {
+        Vector2(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Vector2<T> {
+    type Output = Vector2<T>;
+    fn sub(self, rhs: Vector2<T>) -> Self::Output {
+        Vector2(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Vector2<T> {
+    type Output = Vector2<T>;
+    fn neg(self) -> Self::Output {
+        Vector2(-self.0, -self.1)
+    }
+}
+
+impl<T: Zero + PartialEq> Zero for Vector2<T> {
+    fn zero() -> Self {
+        Vector2(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<T: Adj2> Adj2 for Vector2<T> {
+    fn adj2(self) -> Self {
+        Vector2(self.0.adj2(), self.1.adj2())
+    }
+}
+
+#[cfg(test)]
+mod matrix2_tests {
+    use super::*;
+    use crate::{ComplexRing, DOmega, Dyadic, Omega, OmegaRing, RootHalfRing, RootTwoRing, ZOmega};
+
+    fn hadamard() -> Matrix2<DOmega> {
+        let s = DOmega::roothalf();
+        Matrix2(s, s, s, -s)
+    }
+
+    #[test]
+    fn hadamard_is_an_involution() {
+        let h = hadamard();
+        assert_eq!(h * h, Matrix2::one());
+        assert_eq!(h.adj(), h);
+        assert_eq!(h.det(), -DOmega::one());
+        assert_eq!(h.trace(), DOmega::zero());
+    }
+
+    #[test]
+    fn arithmetic_matrix2() {
+        let x: Matrix2<ZOmega> = Matrix2(
+            Omega(1, 0, 0, 0),
+            Omega(0, 1, 0, 0),
+            Omega(0, 0, 0, 0),
+            Omega(2, 0, 0, 0),
+        );
+        let y = Matrix2(
+            Omega(0, 0, 1, 0),
+            Omega(0, 0, 0, 0),
+            Omega(1, 1, 0, 0),
+            Omega(0, 0, 0, -1),
+        );
+        assert_eq!((x + y) - y, x);
+        assert_eq!(x - x, Matrix2::zero());
+        assert_eq!(-(-x), x);
+        assert_eq!((x * y).det(), x.det() * y.det());
+        assert_eq!((x * y).adj(), y.adj() * x.adj());
+        assert_eq!((x * y).adj2(), x.adj2() * y.adj2());
+        assert_eq!(x.scale(Omega(2, 0, 0, 0)), x + x);
+        assert_eq!(x.transpose().rows(), [[x.0, x.2], [x.1, x.3]]);
+    }
+
+    #[test]
+    fn t_gate_phases() {
+        let t: Matrix2<DOmega> = Matrix2(
+            DOmega::one(),
+            DOmega::zero(),
+            DOmega::zero(),
+            DOmega::omega(),
+        );
+        let t8 = (0..8).fold(Matrix2::one(), |acc, _| acc * t);
+        assert_eq!(t8, Matrix2::one());
+        assert_eq!(t.det(), DOmega::omega());
+        let half = Omega(Dyadic(1, 1), Dyadic(0, 0), Dyadic(0, 0), Dyadic(0, 0));
+        assert_eq!(t.adj() * t, Matrix2::one());
+        assert_eq!(
+            Matrix2::<DOmega>::one().scale(half),
+            Matrix2(half, DOmega::zero(), DOmega::zero(), half)
+        );
+    }
+
+    #[test]
+    fn states_through_gates() {
+        let (zero, one) = (DOmega::zero(), DOmega::one());
+        let ket0 = Vector2(one, zero);
+        let plus = hadamard() * ket0;
+        let s = DOmega::roothalf();
+        assert_eq!(plus, Vector2(s, s));
+        assert_eq!(hadamard() * plus, ket0);
+        assert_eq!(plus.norm_sqr(), one);
+        assert_eq!(plus.inner(hadamard() * Vector2(zero, one)), zero);
+        // the inner product conjugates its left side, unlike dot
+        let i = Vector2(DOmega::i(), zero);
+        assert_eq!(i.inner(i), one);
+        assert_eq!(i.dot(i), -one);
+        assert_eq!((plus + ket0) - ket0, plus);
+        assert_eq!(plus.scale(DOmega::roottwo()), Vector2(one, one));
+        assert_eq!(-plus + plus, Vector2::zero());
+        assert_eq!(i.entries(), [DOmega::i(), zero]);
+    }
+
+    #[test]
+    fn exact_predicates() {
+        let h = hadamard();
+        assert!(h.is_unitary() && h.is_hermitian() && !h.is_special());
+        let (zero, one, omega) = (DOmega::zero(), DOmega::one(), DOmega::omega());
+        let t = Matrix2(one, zero, zero, omega);
+        assert!(t.is_unitary() && !t.is_hermitian() && !t.is_special());
+        // ω⁷S has determinant ω¹⁴·i == 1
+        let omega7 = (0..7).fold(one, |acc, _| acc * omega);
+        assert!((t * t).scale(omega7).is_special());
+        // √2·H is orthogonal up to scale, but not unitary
+        assert!(!h.scale(DOmega::roottwo()).is_unitary());
+        let y = Matrix2(zero, -DOmega::i(), DOmega::i(), zero);
+        assert!(y.is_hermitian() && y.is_unitary());
+        assert!(!Matrix2(zero, DOmega::i(), DOmega::i(), zero).is_hermitian());
+    }
+}
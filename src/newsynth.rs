@@ -0,0 +1,602 @@
+//! The textual forms that the Haskell `newsynth` package's `show` gives its
+//! `ZRootTwo`, `DRootTwo` and `DOmega` elements, so that results can be
+//! compared between the two implementations.
+//!
+//! `newsynth` writes `√2` as `roottwo` and dyadics as reduced fractions, with
+//! Haskell's precedence rules deciding the parentheses, e.g.
+//! `3 - 2*roottwo` and `1/2 + 3/4*roottwo`. Its `Omega a b c d` is
+//! `aω³ + bω² + cω + d`, the reverse of the coefficient order of [`Omega`].
+//!
+//! Parsing accepts any expression built from integer literals, `roottwo`,
+//! `omega`, the constructors `Omega a b c d`, `RootTwo a b` and `Dyadic a n`,
+//! and the operators `+`, `-`, `*`, `^` and division by powers of two. It
+//! evaluates the expression exactly and then checks that the value lies in
+//! the requested ring.
+
+use num::{BigInt, One, Zero};
+
+use crate::{DOmega, Dyadic, Omega, ParseRingError, Quad, RootTwo};
+
+/// Printing and parsing in `newsynth`'s format.
+pub trait Newsynth: Sized {
+    /// The string `newsynth` shows for this value.
+    fn to_newsynth(&self) -> String;
+
+    /// Parses an expression in `newsynth`'s syntax.
+    ///
+    /// Fails with [`ParseRingError::NotDyadic`] on division by anything but
+    /// a power of two, and with [`ParseRingError::Malformed`] if the value is
+    /// not in this ring or overflows its coefficients, for `0^0`, or for
+    /// parentheses nested more than 128 deep.
+    fn from_newsynth(s: &str) -> Result<Self, ParseRingError>;
+}
+
+/// Haskell's `showsPrec`, which parenthesizes `self` when it appears as an
+/// operand of an operator with precedence above `d`.
+trait ShowsPrec {
+    fn shows_prec(&self, d: u8, out: &mut String);
+}
+
+fn show_paren(out: &mut String, paren: bool, body: impl FnOnce(&mut String)) {
+    if paren {
+        out.push('(');
+    }
+    body(out);
+    if paren {
+        out.push(')');
+    }
+}
+
+impl ShowsPrec for i64 {
+    fn shows_prec(&self, d: u8, out: &mut String) {
+        show_paren(out, d > 6 && *self < 0, |out| {
+            out.push_str(&self.to_string())
+        });
+    }
+}
+
+impl ShowsPrec for Dyadic {
+    /// `newsynth`'s `showsPrec_rational`, which treats `-n/d` as a negation
+    /// of a division.
+    fn shows_prec(&self, d: u8, out: &mut String) {
+        if self.1 == 0 {
+            self.0.shows_prec(d, out);
+        } else if self.0 < 0 {
+            show_paren(out, d > 6, |out| {
+                out.push('-');
+                (-*self).shows_prec(7, out);
+            });
+        } else {
+            show_paren(out, d > 7, |out| {
+                out.push_str(&format!("{}/{}", self.0, BigInt::one() << self.1));
+            });
+        }
+    }
+}
+
+impl<T: ShowsPrec + Zero + One + PartialOrd + Copy + std::ops::Neg<Output = T>> ShowsPrec
+    for RootTwo<T>
+{
+    fn shows_prec(&self, d: u8, out: &mut String) {
+        let Quad(a, b) = *self;
+        if b.is_zero() {
+            a.shows_prec(d, out);
+        } else if a.is_zero() {
+            if b.is_one() {
+                out.push_str("roottwo");
+            } else if (-b).is_one() {
+                show_paren(out, d > 6, |out| out.push_str("-roottwo"));
+            } else {
+                show_paren(out, d > 7, |out| {
+                    b.shows_prec(7, out);
+                    out.push_str("*roottwo");
+                });
+            }
+        } else {
+            show_paren(out, d > 6, |out| {
+                a.shows_prec(6, out);
+                if b > T::zero() {
+                    out.push_str(" + ");
+                    Quad::<T, 2>(T::zero(), b).shows_prec(7, out);
+                } else {
+                    out.push_str(" - ");
+                    Quad::<T, 2>(T::zero(), -b).shows_prec(7, out);
+                }
+            });
+        }
+    }
+}
+
+impl<T: ShowsPrec> ShowsPrec for Omega<T> {
+    fn shows_prec(&self, d: u8, out: &mut String) {
+        show_paren(out, d > 10, |out| {
+            out.push_str("Omega");
+            for c in [&self.3, &self.2, &self.1, &self.0] {
+                out.push(' ');
+                c.shows_prec(11, out);
+            }
+        });
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####             Parsing             ####
+// #######                           #######
+// #########################################
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(String),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>, ParseRingError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() {
+            let mut digits = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_ascii_digit()) {
+                digits.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Int(digits));
+        } else if c.is_ascii_alphabetic() {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_ascii_alphanumeric()) {
+                name.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Ident(name));
+        } else if "+-*/^()".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else {
+            return Err(ParseRingError::Malformed);
+        }
+    }
+    Ok(tokens)
+}
+
+fn overflow<T>(x: Option<T>) -> Result<T, ParseRingError> {
+    x.ok_or(ParseRingError::Malformed)
+}
+
+fn add(x: DOmega, y: DOmega) -> Result<DOmega, ParseRingError> {
+    Ok(Omega(
+        overflow(x.0.checked_add(y.0))?,
+        overflow(x.1.checked_add(y.1))?,
+        overflow(x.2.checked_add(y.2))?,
+        overflow(x.3.checked_add(y.3))?,
+    ))
+}
+
+/// The sum of the products `xᵢ·yᵢ`, each with the given sign.
+fn dot(terms: [(Dyadic, Dyadic, bool); 4]) -> Result<Dyadic, ParseRingError> {
+    terms
+        .into_iter()
+        .try_fold(Dyadic::zero(), |acc, (x, y, minus)| {
+            let product = overflow(x.checked_mul(y))?;
+            overflow(if minus {
+                acc.checked_sub(product)
+            } else {
+                acc.checked_add(product)
+            })
+        })
+}
+
+fn mul(x: DOmega, y: DOmega) -> Result<DOmega, ParseRingError> {
+    let Omega(a, b, c, d) = x;
+    let Omega(e, f, g, h) = y;
+    // ω⁴ = -1
+    Ok(Omega(
+        dot([(a, e, false), (b, h, true), (c, g, true), (d, f, true)])?,
+        dot([(a, f, false), (b, e, false), (c, h, true), (d, g, true)])?,
+        dot([(a, g, false), (b, f, false), (c, e, false), (d, h, true)])?,
+        dot([(a, h, false), (b, g, false), (c, f, false), (d, e, false)])?,
+    ))
+}
+
+fn neg(x: DOmega) -> DOmega {
+    Omega(-x.0, -x.1, -x.2, -x.3)
+}
+
+fn constant(x: Dyadic) -> DOmega {
+    Omega(x, Dyadic::zero(), Dyadic::zero(), Dyadic::zero())
+}
+
+/// How deeply parentheses may nest, which bounds the recursion of
+/// [`Parser`] on untrusted input.
+const MAX_DEPTH: usize = 128;
+
+/// A recursive descent parser over Haskell's precedences: `+` and `-` at
+/// 6, `*` and `/` at 7, `^` at 8 and constructor application above them
+/// all.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expr(&mut self) -> Result<DOmega, ParseRingError> {
+        let mut x = if self.eat('-') {
+            neg(self.term()?)
+        } else {
+            self.term()?
+        };
+        loop {
+            if self.eat('+') {
+                x = add(x, self.term()?)?;
+            } else if self.eat('-') {
+                x = add(x, neg(self.term()?))?;
+            } else {
+                return Ok(x);
+            }
+        }
+    }
+
+    fn term(&mut self) -> Result<DOmega, ParseRingError> {
+        let mut x = self.power()?;
+        loop {
+            if self.eat('*') {
+                x = mul(x, self.power()?)?;
+            } else if self.eat('/') {
+                let y = self.power()?;
+                x = mul(x, constant(inverse_pow2(y)?))?;
+            } else {
+                return Ok(x);
+            }
+        }
+    }
+
+    fn power(&mut self) -> Result<DOmega, ParseRingError> {
+        let x = self.application()?;
+        if !self.eat('^') {
+            return Ok(x);
+        }
+        let e = match self.tokens.get(self.pos) {
+            Some(Token::Int(digits)) => digits.parse::<u32>()?,
+            _ => return Err(ParseRingError::Malformed),
+        };
+        self.pos += 1;
+        // Haskell reads 0^0 as 1, but no ring element should come of it
+        if e == 0 && x.is_zero() {
+            return Err(ParseRingError::Malformed);
+        }
+        // by squaring, so a huge exponent costs only one multiplication per bit
+        let (mut base, mut e, mut acc) = (x, e, constant(Dyadic::one()));
+        while e > 0 {
+            if e & 1 == 1 {
+                acc = mul(acc, base)?;
+            }
+            e >>= 1;
+            if e > 0 {
+                base = mul(base, base)?;
+            }
+        }
+        Ok(acc)
+    }
+
+    fn application(&mut self) -> Result<DOmega, ParseRingError> {
+        let arity = match self.peek() {
+            Some(Token::Ident(name)) if name == "Omega" => 4,
+            Some(Token::Ident(name)) if name == "RootTwo" || name == "Dyadic" => 2,
+            _ => return self.atom(),
+        };
+        let Some(Token::Ident(name)) = self.peek().cloned() else {
+            unreachable!()
+        };
+        self.pos += 1;
+        let args = (0..arity)
+            .map(|_| self.atom())
+            .collect::<Result<Vec<_>, _>>()?;
+        match name.as_str() {
+            "Omega" => add(
+                add(mul(args[0], omega_pow(3))?, mul(args[1], omega_pow(2))?)?,
+                add(mul(args[2], omega_pow(1))?, args[3])?,
+            ),
+            "RootTwo" => add(args[0], mul(args[1], root_two())?),
+            _ => {
+                let n = match args[1] {
+                    Omega(n, z1, z2, z3)
+                        if n.1 == 0 && n.0 >= 0 && [z1, z2, z3].iter().all(Zero::is_zero) =>
+                    {
+                        u32::try_from(n.0).map_err(|_| ParseRingError::Malformed)?
+                    }
+                    _ => return Err(ParseRingError::Malformed),
+                };
+                mul(args[0], constant(Dyadic::new(1, n)))
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Result<DOmega, ParseRingError> {
+        let token = self.peek().cloned().ok_or(ParseRingError::Malformed)?;
+        self.pos += 1;
+        match token {
+            Token::Int(digits) => Ok(constant(Dyadic::new(digits.parse()?, 0))),
+            Token::Ident(name) if name == "roottwo" => Ok(root_two()),
+            Token::Ident(name) if name == "omega" => Ok(omega_pow(1)),
+            Token::Op('(') => {
+                if self.depth == MAX_DEPTH {
+                    return Err(ParseRingError::Malformed);
+                }
+                self.depth += 1;
+                let x = self.expr();
+                self.depth -= 1;
+                let x = x?;
+                if self.eat(')') {
+                    Ok(x)
+                } else {
+                    Err(ParseRingError::Malformed)
+                }
+            }
+            _ => Err(ParseRingError::Malformed),
+        }
+    }
+}
+
+fn omega_pow(k: usize) -> DOmega {
+    let mut coeffs = [Dyadic::zero(); 4];
+    coeffs[k] = Dyadic::one();
+    Omega(coeffs[0], coeffs[1], coeffs[2], coeffs[3])
+}
+
+/// `√2 = ω - ω³`
+fn root_two() -> DOmega {
+    DOmega::from(RootTwo::new(Dyadic::zero(), Dyadic::one()))
+}
+
+/// `1/x` for a positive power of two `x`.
+fn inverse_pow2(x: DOmega) -> Result<Dyadic, ParseRingError> {
+    let Omega(n, b, c, d) = x;
+    if !(b.is_zero() && c.is_zero() && d.is_zero()) || n.0 <= 0 || n.1 != 0 {
+        return Err(ParseRingError::NotDyadic);
+    }
+    if n.0.count_ones() != 1 {
+        return Err(ParseRingError::NotDyadic);
+    }
+    Ok(Dyadic::new(1, n.0.trailing_zeros()))
+}
+
+fn parse(s: &str) -> Result<DOmega, ParseRingError> {
+    let mut parser = Parser {
+        tokens: tokenize(s)?,
+        pos: 0,
+        depth: 0,
+    };
+    let x = parser.expr()?;
+    if parser.pos == parser.tokens.len() {
+        Ok(x)
+    } else {
+        Err(ParseRingError::Malformed)
+    }
+}
+
+/// The element of D[√2] that `x` is, if it is real.
+fn real(x: DOmega) -> Result<RootTwo<Dyadic>, ParseRingError> {
+    if x.2.is_zero() && x.3 == -x.1 {
+        Ok(Quad(x.0, x.1))
+    } else {
+        Err(ParseRingError::Malformed)
+    }
+}
+
+fn integer(x: Dyadic) -> Result<i64, ParseRingError> {
+    if x.1 == 0 {
+        Ok(x.0)
+    } else {
+        Err(ParseRingError::Malformed)
+    }
+}
+
+impl Newsynth for i64 {
+    fn to_newsynth(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_newsynth(s: &str) -> Result<Self, ParseRingError> {
+        match real(parse(s)?)? {
+            Quad(a, b) if b.is_zero() => integer(a),
+            _ => Err(ParseRingError::Malformed),
+        }
+    }
+}
+
+impl Newsynth for Dyadic {
+    fn to_newsynth(&self) -> String {
+        let mut out = String::new();
+        self.shows_prec(0, &mut out);
+        out
+    }
+
+    fn from_newsynth(s: &str) -> Result<Self, ParseRingError> {
+        match real(parse(s)?)? {
+            Quad(a, b) if b.is_zero() => Ok(a),
+            _ => Err(ParseRingError::Malformed),
+        }
+    }
+}
+
+impl Newsynth for RootTwo<i64> {
+    /// `newsynth`'s `ZRootTwo`.
+    fn to_newsynth(&self) -> String {
+        let mut out = String::new();
+        self.shows_prec(0, &mut out);
+        out
+    }
+
+    fn from_newsynth(s: &str) -> Result<Self, ParseRingError> {
+        let Quad(a, b) = real(parse(s)?)?;
+        Ok(Quad(integer(a)?, integer(b)?))
+    }
+}
+
+impl Newsynth for RootTwo<Dyadic> {
+    /// `newsynth`'s `DRootTwo`.
+    fn to_newsynth(&self) -> String {
+        let mut out = String::new();
+        self.shows_prec(0, &mut out);
+        out
+    }
+
+    fn from_newsynth(s: &str) -> Result<Self, ParseRingError> {
+        real(parse(s)?)
+    }
+}
+
+impl Newsynth for DOmega {
+    /// `newsynth`'s `DOmega`, as `Omega a b c d` with the coefficients of
+    /// `ω³, ω², ω, 1`.
+    fn to_newsynth(&self) -> String {
+        let mut out = String::new();
+        self.shows_prec(0, &mut out);
+        out
+    }
+
+    fn from_newsynth(s: &str) -> Result<Self, ParseRingError> {
+        parse(s)
+    }
+}
+
+#[cfg(test)]
+mod newsynth_tests {
+    use super::*;
+
+    /// Pairs of values and the strings `newsynth` shows for them.
+    const ZROOTTWO: [((i64, i64), &str); 7] = [
+        ((0, 0), "0"),
+        ((-3, 0), "-3"),
+        ((0, 1), "roottwo"),
+        ((0, -1), "-roottwo"),
+        ((0, -3), "(-3)*roottwo"),
+        ((3, -2), "3 - 2*roottwo"),
+        ((-1, 1), "-1 + roottwo"),
+    ];
+
+    #[test]
+    fn zroottwo_fixtures() {
+        for ((a, b), s) in ZROOTTWO {
+            let x = RootTwo::new(a, b);
+            assert_eq!(x.to_newsynth(), s);
+            assert_eq!(RootTwo::<i64>::from_newsynth(s), Ok(x), "{s}");
+        }
+        assert_eq!(
+            RootTwo::<i64>::from_newsynth("RootTwo 5 (-7)"),
+            Ok(Quad(5, -7))
+        );
+        assert_eq!(RootTwo::<i64>::from_newsynth("roottwo^3"), Ok(Quad(0, 2)));
+        assert_eq!(
+            RootTwo::<i64>::from_newsynth("1/2"),
+            Err(ParseRingError::Malformed)
+        );
+    }
+
+    #[test]
+    fn droottwo_fixtures() {
+        for (x, s) in [
+            (
+                RootTwo::new(Dyadic(1, 1), Dyadic(3, 2)),
+                "1/2 + 3/4*roottwo",
+            ),
+            (RootTwo::new(Dyadic(-1, 1), Dyadic(0, 0)), "-1/2"),
+            (
+                RootTwo::new(Dyadic(-1, 1), Dyadic(-1, 3)),
+                "-1/2 - 1/8*roottwo",
+            ),
+            (RootTwo::new(Dyadic(0, 0), Dyadic(-5, 4)), "(-5/16)*roottwo"),
+            (RootTwo::new(Dyadic(7, 0), Dyadic(1, 0)), "7 + roottwo"),
+        ] {
+            assert_eq!(x.to_newsynth(), s);
+            assert_eq!(RootTwo::<Dyadic>::from_newsynth(s), Ok(x), "{s}");
+        }
+        assert_eq!(
+            RootTwo::<Dyadic>::from_newsynth("1/3"),
+            Err(ParseRingError::NotDyadic)
+        );
+    }
+
+    #[test]
+    fn domega_fixtures() {
+        for (x, s) in [
+            (
+                Omega(Dyadic(0, 0), Dyadic(1, 1), Dyadic(0, 0), Dyadic(-1, 1)),
+                "Omega (-1/2) 0 (1/2) 0",
+            ),
+            (
+                Omega(Dyadic(1, 0), Dyadic(-3, 0), Dyadic(0, 0), Dyadic(5, 2)),
+                "Omega (5/4) 0 (-3) 1",
+            ),
+        ] {
+            assert_eq!(x.to_newsynth(), s);
+            assert_eq!(DOmega::from_newsynth(s), Ok(x), "{s}");
+        }
+        // ω² = i and ω⁴ = -1
+        assert_eq!(
+            DOmega::from_newsynth("omega^4 + 1/2*omega^2"),
+            Ok(Omega(
+                Dyadic(-1, 0),
+                Dyadic(0, 0),
+                Dyadic(1, 1),
+                Dyadic(0, 0)
+            ))
+        );
+        assert_eq!(
+            DOmega::from_newsynth("Omega 0 0 1 (Dyadic 3 2)"),
+            DOmega::from_newsynth("omega + 3/4")
+        );
+        // exponents cost one multiplication per bit, and overflow is an error
+        assert_eq!(DOmega::from_newsynth("omega^40000000"), Ok(omega_pow(0)));
+        assert_eq!(
+            DOmega::from_newsynth("omega^4000000005"),
+            Ok(neg(omega_pow(1)))
+        );
+        assert_eq!(
+            DOmega::from_newsynth("(1/2)^62"),
+            Ok(constant(Dyadic::new(1, 62)))
+        );
+        assert_eq!(DOmega::from_newsynth("0^3"), Ok(DOmega::zero()));
+        assert_eq!(
+            DOmega::from_newsynth("roottwo^0"),
+            Ok(constant(Dyadic::one()))
+        );
+        let nested = format!("{}1{}", "(".repeat(MAX_DEPTH), ")".repeat(MAX_DEPTH));
+        assert_eq!(DOmega::from_newsynth(&nested), Ok(constant(Dyadic::one())));
+        let deep = format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000));
+        for s in [
+            "",
+            "Omega 1 2 3",
+            "roottwo +",
+            "(1",
+            "1 2",
+            "2^x",
+            "0^0",
+            "2^64",
+            &deep,
+        ] {
+            assert_eq!(
+                DOmega::from_newsynth(s),
+                Err(ParseRingError::Malformed),
+                "{s}"
+            );
+        }
+    }
+}
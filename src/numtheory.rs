@@ -0,0 +1,418 @@
+//! Primality testing and factorization of rational integers, which the
+//! ring-level factorization and norm equation solvers are built on.
+
+#[cfg(feature = "bigint")]
+use num::{BigInt, One, Zero};
+
+/// `a·b mod m` without overflow.
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+/// `aᵉ mod m` by square-and-multiply.
+pub(crate) fn pow_mod(mut a: u64, mut e: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    a %= m;
+    while e > 0 {
+        if e & 1 == 1 {
+            result = mul_mod(result, a, m);
+        }
+        a = mul_mod(a, a, m);
+        e >>= 1;
+    }
+    result
+}
+
+/// The first twelve primes. As Miller–Rabin bases they correctly classify
+/// every integer below `3.3·10²⁴`, and so all of `u64`.
+const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Whether `n` is prime, by Miller–Rabin with a deterministic set of bases.
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// Whether `n` is a prime of ℤ, i.e. `±p` for a rational prime `p`.
+pub fn is_prime_i64(n: i64) -> bool {
+    is_prime(n.unsigned_abs())
+}
+
+/// Whether `n` is probably prime, by Miller–Rabin with the first `rounds`
+/// of the (at most twelve) bases used by [`is_prime`]. Inputs that fit in a
+/// `u64` are decided exactly. Beyond that the bases are fixed rather than
+/// random, so there is no bound on the error: some composites, the least
+/// being `318665857834031151167461`, pass all twelve rounds.
+#[cfg(feature = "bigint")]
+pub fn is_probable_prime(n: &BigInt, rounds: usize) -> bool {
+    let two = BigInt::from(2);
+    if n < &two {
+        return false;
+    }
+    if let Ok(small) = u64::try_from(n) {
+        return is_prime(small);
+    }
+    if BASES.iter().any(|&p| (n % p).is_zero()) {
+        return false;
+    }
+    let n1: BigInt = n - 1;
+    let s = n1.trailing_zeros().unwrap_or(0);
+    let d: BigInt = &n1 >> s;
+    BASES.iter().take(rounds).all(|&a| {
+        let mut x = BigInt::from(a).modpow(&d, n);
+        if x.is_one() || x == n1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = x.modpow(&two, n);
+            if x == n1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+/// The Jacobi symbol `(a/n)`, which is the Legendre symbol when `n` is
+/// prime: `1` if `a` is a nonzero square modulo `n`, `-1` if it is not, and
+/// `0` if they share a factor. For composite `n`, a value of `1` does not
+/// imply that `a` is a square.
+///
+/// # Panics
+///
+/// Panics if `n` is even.
+pub fn jacobi(a: i64, n: u64) -> i32 {
+    if n.is_multiple_of(2) {
+        panic!("the Jacobi symbol needs an odd modulus");
+    }
+    let mut a = (a as i128).rem_euclid(n as i128) as u64;
+    let mut n = n;
+    let mut result = 1;
+    while a != 0 {
+        // (2/n) = -1 exactly when n ≡ ±3 (mod 8)
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+        // quadratic reciprocity
+        (a, n) = (n, a);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// A square root of `a` modulo a prime `p`, or `None` if `a` is not a
+/// quadratic residue. Of the two roots `±r`, this returns the one in
+/// `[0, p/2]`.
+///
+/// Uses a single exponentiation when `p ≡ 3 (mod 4)`, and Tonelli–Shanks
+/// otherwise. The result is meaningless if `p` is not prime.
+pub fn sqrt_mod_p(a: u64, p: u64) -> Option<u64> {
+    let a = a % p;
+    if p == 2 || a == 0 {
+        return Some(a);
+    }
+    // Euler's criterion
+    if pow_mod(a, (p - 1) / 2, p) != 1 {
+        return None;
+    }
+    let r = if p % 4 == 3 {
+        pow_mod(a, (p + 1) / 4, p)
+    } else {
+        // write p - 1 = q·2ˢ with q odd, and find a non-residue z
+        let s = (p - 1).trailing_zeros();
+        let q = (p - 1) >> s;
+        let z = (2..p).find(|&z| pow_mod(z, (p - 1) / 2, p) == p - 1)?;
+        let (mut m, mut c) = (s, pow_mod(z, q, p));
+        let (mut t, mut r) = (pow_mod(a, q, p), pow_mod(a, q.div_ceil(2), p));
+        // invariant: r² = a·t, where t has order 2^i for some i < m
+        while t != 1 {
+            let mut i = 0;
+            let mut t2 = t;
+            while t2 != 1 && i < m {
+                t2 = mul_mod(t2, t2, p);
+                i += 1;
+            }
+            if i == m {
+                return None;
+            }
+            let b = pow_mod(c, 1 << (m - i - 1), p);
+            r = mul_mod(r, b, p);
+            c = mul_mod(b, b, p);
+            t = mul_mod(t, c, p);
+            m = i;
+        }
+        r
+    };
+    Some(r.min(p - r))
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// A nontrivial factor of `n`, by Pollard's rho with Brent's cycle
+/// detection, retrying with a new polynomial `x² + c` on failure. Returns
+/// `None` if `n` is prime or at most one, when there is no such factor.
+pub fn pollard_rho(n: u64) -> Option<u64> {
+    if n <= 1 || is_prime(n) {
+        return None;
+    }
+    let mut effort = u64::MAX;
+    rho_within(n, &mut effort)
+}
+
+/// [`pollard_rho`] for a composite `n`, which gives up with `None` once it
+/// has evaluated the polynomial `effort` times. The evaluations are
+/// subtracted from `effort`.
+fn rho_within(n: u64, effort: &mut u64) -> Option<u64> {
+    if n.is_multiple_of(2) {
+        return Some(2);
+    }
+    for c in 1..n {
+        let f = |x| ((mul_mod(x, x, n) as u128 + c as u128) % n as u128) as u64;
+        let (mut x, mut y, mut g) = (2, 2, 1);
+        let mut power = 1;
+        let mut lam = 1;
+        while g == 1 {
+            *effort = effort.checked_sub(1)?;
+            if power == lam {
+                x = y;
+                power *= 2;
+                lam = 0;
+            }
+            y = f(y);
+            lam += 1;
+            g = gcd(x.abs_diff(y), n);
+        }
+        if g != n {
+            return Some(g);
+        }
+    }
+    unreachable!("some polynomial x² + c splits a composite")
+}
+
+/// The prime factorization of `n` as `(p, e)` pairs in increasing order of
+/// `p`, which is empty for `n = 1`. Small factors are removed by trial
+/// division and the rest split with [`pollard_rho`].
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn factor(n: u64) -> Vec<(u64, u32)> {
+    factor_within(n, u64::MAX).expect("factoring a u64 takes fewer than 2⁶⁴ steps")
+}
+
+/// [`factor`] with an effort budget, returning `None` if splitting the
+/// composite parts of `n` takes more than `effort` steps of Pollard's rho
+/// in total. Every `n` is factored within about `n^(1/4)` steps, but a
+/// caller solving many norm equations may rather skip the hard ones.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn factor_within(mut n: u64, mut effort: u64) -> Option<Vec<(u64, u32)>> {
+    if n == 0 {
+        panic!("attempted to factor zero");
+    }
+    let mut primes = Vec::new();
+    for p in BASES {
+        while n.is_multiple_of(p) {
+            primes.push(p);
+            n /= p;
+        }
+    }
+    let mut stack = vec![n];
+    while let Some(m) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            primes.push(m);
+            continue;
+        }
+        let d = rho_within(m, &mut effort)?;
+        stack.push(d);
+        stack.push(m / d);
+    }
+    primes.sort_unstable();
+    let mut factors: Vec<(u64, u32)> = Vec::new();
+    for p in primes {
+        match factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    Some(factors)
+}
+
+#[cfg(test)]
+mod numtheory_tests {
+    use super::*;
+
+    #[test]
+    fn miller_rabin() {
+        let small: Vec<u64> = (0..60).filter(|&n| is_prime(n)).collect();
+        assert_eq!(
+            small,
+            [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59]
+        );
+        // strong pseudoprimes to several small bases
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(3_825_123_056_546_413_051));
+        assert!(is_prime(18_446_744_073_709_551_557));
+        assert!(!is_prime(u64::MAX));
+        assert!(is_prime_i64(-7));
+        assert!(!is_prime_i64(i64::MIN));
+    }
+
+    #[test]
+    fn factor_integers() {
+        assert_eq!(factor(1), []);
+        assert_eq!(factor(360), [(2, 3), (3, 2), (5, 1)]);
+        // a product of two primes near 2³²
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        assert_eq!(factor(p * q), [(q, 1), (p, 1)]);
+        assert_eq!(
+            factor(u64::MAX),
+            [
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65537, 1),
+                (6_700_417, 1)
+            ]
+        );
+        let d = pollard_rho(1_000_003 * 999_983).unwrap();
+        assert!(d == 1_000_003 || d == 999_983);
+        assert_eq!(pollard_rho(18), Some(2));
+        assert_eq!(pollard_rho(0), None);
+        assert_eq!(pollard_rho(1), None);
+        assert_eq!(pollard_rho(999_983), None);
+        // near u64::MAX, where x² mod n + c no longer fits in a u64
+        let (p, q) = (4_294_967_291, 4_294_967_279);
+        let d = pollard_rho(p * q).unwrap();
+        assert!(d == p || d == q);
+        assert_eq!(factor_within(40_009 * 40_129, 1), None);
+        assert_eq!(
+            factor_within(40_009 * 40_129, 1_000_000),
+            Some(vec![(40_009, 1), (40_129, 1)])
+        );
+        assert_eq!(factor_within(64 * 97, 0), Some(vec![(2, 6), (97, 1)]));
+    }
+
+    #[test]
+    fn modular_square_roots() {
+        for p in [
+            2u64,
+            3,
+            7,
+            17,
+            41,
+            97,
+            257,
+            65537,
+            1_000_000_007,
+            4_294_967_291,
+        ] {
+            for a in [1u64, 2, 3, 5, p - 1, 123_456_789] {
+                match sqrt_mod_p(a, p) {
+                    Some(r) => {
+                        assert_eq!(mul_mod(r, r, p), a % p);
+                        assert!(r <= p / 2);
+                    }
+                    None => assert_eq!(pow_mod(a, (p - 1) / 2, p), p - 1),
+                }
+            }
+        }
+        // 2 is a square modulo p ≡ ±1 (mod 8), and -1 modulo p ≡ 1 (mod 4)
+        assert_eq!(sqrt_mod_p(2, 7), Some(3));
+        assert_eq!(sqrt_mod_p(16, 17), Some(4));
+        assert_eq!(sqrt_mod_p(3, 7), None);
+        assert_eq!(sqrt_mod_p(0, 13), Some(0));
+    }
+
+    #[test]
+    fn jacobi_symbols() {
+        for p in [3u64, 5, 7, 17, 97, 65537, 1_000_000_007] {
+            for a in [-1i64, 2, 3, 10, 12_345] {
+                let euler = pow_mod(a.rem_euclid(p as i64) as u64, (p - 1) / 2, p);
+                let expected = match euler {
+                    0 => 0,
+                    1 => 1,
+                    _ => -1,
+                };
+                assert_eq!(jacobi(a, p), expected);
+            }
+        }
+        assert_eq!(jacobi(1001, 9907), -1);
+        assert_eq!(jacobi(19, 45), 1);
+        assert_eq!(jacobi(8, 21), -1);
+        // a symbol of 1 modulo a composite need not mean a square
+        assert_eq!(jacobi(2, 15), 1);
+        assert_eq!(jacobi(3, 9), 0);
+        assert_eq!(jacobi(0, 1), 1);
+        // u64::MAX ≡ 7 (mod 8)
+        assert_eq!(jacobi(2, u64::MAX), 1);
+        assert_eq!(jacobi(-1, u64::MAX), -1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn jacobi_even_modulus() {
+        jacobi(3, 10);
+    }
+
+    #[test]
+    #[should_panic]
+    fn factor_zero() {
+        factor(0);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn probable_primes() {
+        // 2¹²⁷ - 1 is a Mersenne prime, 2¹²⁸ + 1 is not prime
+        let m127 = (BigInt::one() << 127) - 1;
+        assert!(is_probable_prime(&m127, 12));
+        assert!(!is_probable_prime(&((BigInt::one() << 128) + 1), 12));
+        assert!(!is_probable_prime(&(&m127 * &m127), 12));
+        assert!(is_probable_prime(&BigInt::from(97), 1));
+        assert!(!is_probable_prime(&BigInt::from(-97), 12));
+    }
+}
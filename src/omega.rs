@@ -0,0 +1,807 @@
+use std::ops;
+
+use num::{BigInt, Complex, Integer, One, Signed, Zero};
+
+use crate::{
+    quad::round_to_f64_directed, ring::rounddiv, Adj, Adj2, DenomExp, Dyadic, Fixed, Parity,
+    RootTwo, Rounding, WholePart,
+};
+
+/// An element `a + bω + cω² + dω³` of the ring generated by `ω = e^(iπ/4)`,
+/// the primitive eighth root of unity. Note that `ω⁴ = -1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Omega<T>(pub(crate) T, pub(crate) T, pub(crate) T, pub(crate) T);
+
+/// The ring ℤ[ω].
+pub type ZOmega = Omega<i64>;
+
+/// The ring D[ω] = ℤ[1/2, ω], whose elements are the entries of exact
+/// Clifford+T unitaries.
+pub type DOmega = Omega<Dyadic>;
+
+impl<T> Omega<T> {
+    /// `a + bω + cω² + dω³`
+    pub fn new(a: T, b: T, c: T, d: T) -> Omega<T> {
+        Omega(a, b, c, d)
+    }
+
+    /// The coefficients `[a, b, c, d]` of `a + bω + cω² + dω³`.
+    pub fn coeffs(self) -> [T; 4] {
+        [self.0, self.1, self.2, self.3]
+    }
+}
+
+impl<T> Omega<T>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+{
+    /// The field norm down to the base ring, i.e. the product of all four
+    /// Galois conjugates. This is `norm(x·x†)` computed in the √2 subring.
+    pub fn norm(self) -> T
+    where
+        T: Adj + ops::Neg<Output = T> + ops::Mul<i64, Output = T>,
+    {
+        // x·x† is real, so it has the form a + b(ω - ω³)
+        let real = self * self.adj();
+        let (a, b) = (real.0, real.1);
+        a * a - b * b * 2
+    }
+}
+
+fn wide(x: ZOmega) -> Omega<BigInt> {
+    Omega(x.0.into(), x.1.into(), x.2.into(), x.3.into())
+}
+
+fn wide_adj(x: &Omega<BigInt>) -> Omega<BigInt> {
+    Omega(x.0.clone(), -&x.3, -&x.2, -&x.1)
+}
+
+/// The square root of `a + b√2` in ℤ[√2] that is nonnegative as a real
+/// number, like [`RootTwo::sqrt`] for coefficients of any size.
+fn sqrt_roottwo(a: BigInt, b: BigInt) -> Option<(BigInt, BigInt)> {
+    let exact_sqrt = |n: BigInt| {
+        let r = (!n.is_negative()).then(|| n.sqrt())?;
+        (&r * &r == n).then_some(r)
+    };
+    let m = exact_sqrt(&a * &a - &b * &b * 2)?;
+    let root = [m.clone(), -m].into_iter().find_map(|m| {
+        let (x2, y4) = (&a + &m, &a - &m);
+        if x2.is_odd() || !(&y4 % 4u8).is_zero() {
+            return None;
+        }
+        let x = exact_sqrt(x2 / 2)?;
+        let y = exact_sqrt(y4 / 4)?;
+        let y = if b.is_negative() { -y } else { y };
+        (&x * &y * 2 == b).then_some((x, y))
+    })?;
+    let (x, y) = root;
+    // x ≥ 0, so x + y√2 is negative exactly when y < 0 and x² < 2y²
+    Some(if y.is_negative() && &x * &x < &y * &y * 2 {
+        (-x, -y)
+    } else {
+        (x, y)
+    })
+}
+
+fn narrow(x: Omega<BigInt>) -> Option<ZOmega> {
+    let Omega(a, b, c, d) = x;
+    Some(Omega(
+        a.try_into().ok()?,
+        b.try_into().ok()?,
+        c.try_into().ok()?,
+        d.try_into().ok()?,
+    ))
+}
+
+impl ZOmega {
+    /// The norm in a `BigInt`, where [`Omega::norm`] overflows an `i64` once
+    /// a coefficient reaches `2¹⁶`.
+    pub(crate) fn wide_norm(self) -> BigInt {
+        let x = wide(self);
+        let real = &x * &wide_adj(&x);
+        &real.0 * &real.0 - &real.1 * &real.1 * 2
+    }
+
+    /// Units have norm `1`, since the norm of ℤ[ω] is never negative.
+    pub fn is_unit(self) -> bool {
+        self.wide_norm().is_one()
+    }
+
+    /// The inverse of a unit, which is the product of its other three Galois
+    /// conjugates, `x†·(x·x†)•`, or `None` for a non-unit or an inverse that
+    /// does not fit in an `i64`.
+    pub fn inv(self) -> Option<ZOmega> {
+        if !self.is_unit() {
+            return None;
+        }
+        let x = wide(self);
+        let x_adj = wide_adj(&x);
+        narrow(&x_adj * &(&x * &x_adj).adj2())
+    }
+
+    /// The element of ℤ[ω] nearest to `self / rhs`, rounding each
+    /// coefficient of the exact quotient. Multiplying `rhs` by its other
+    /// three Galois conjugates gives its norm, so the quotient is
+    /// `self·rhs†·(rhs·rhs†)• / norm(rhs)`, where `•` is the `√2` conjugate.
+    /// The quotient is computed in a `BigInt`, since the norm alone exceeds
+    /// an `i128` once the coefficients pass `2³¹`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero, or if the quotient does not fit in an `i64`.
+    pub fn rounddiv(self, rhs: ZOmega) -> ZOmega {
+        let y = wide(rhs);
+        let y_adj = wide_adj(&y);
+        let real = &y * &y_adj;
+        let norm: BigInt = &real.0 * &real.0 - &real.1 * &real.1 * 2;
+        if norm.is_zero() {
+            panic!("attempted to divide by zero");
+        }
+        let Omega(a, b, c, d) = &(&wide(self) * &y_adj) * &real.adj2();
+        let round = |x| i64::try_from(rounddiv(x, &norm)).expect("quotient overflows i64");
+        Omega(round(a), round(b), round(c), round(d))
+    }
+
+    /// `self - q·rhs`, the remainder of [`ZOmega::rounddiv`], which only
+    /// needs wide intermediates: the remainder itself is smaller than `rhs`.
+    pub(crate) fn sub_mul(self, q: ZOmega, rhs: ZOmega) -> ZOmega {
+        let Omega(a, b, c, d) = &wide(self) - &(&wide(q) * &wide(rhs));
+        let narrow = |x: BigInt| i64::try_from(x).expect("remainder overflows i64");
+        Omega(narrow(a), narrow(b), narrow(c), narrow(d))
+    }
+}
+
+impl ZOmega {
+    /// A square root with positive real part, or with nonnegative imaginary
+    /// part if it is purely imaginary, whenever `self` is a square in ℤ[ω].
+    ///
+    /// A root `w` is a zero of `X² - tX + n` with `t = w + w†` and `n = w·w†`
+    /// in ℤ[√2]. Both are recovered from `self`, since `n² = self·self†` and
+    /// `t² = self + self† + 2n`, and then `w = (self + n) / t`. These are
+    /// computed in `BigInt`s, so large coefficients give `None` rather than
+    /// overflow.
+    pub fn sqrt(self) -> Option<ZOmega> {
+        let x = wide(self);
+        let x_adj = wide_adj(&x);
+        // x·x† and x + x† are real, of the form a + b(ω - ω³)
+        let norm = &x * &x_adj;
+        let (n0, n1) = sqrt_roottwo(norm.0, norm.1)?;
+        let trace = &x + &x_adj;
+        let (t0, t1): (BigInt, BigInt) = (trace.0 + &n0 * 2, trace.1 + &n1 * 2);
+        let w = if t0.is_zero() && t1.is_zero() {
+            // w is purely imaginary, so -self = (w/i)² is real
+            let (r0, r1) = sqrt_roottwo(-&x.0, -&x.1)?;
+            narrow(Omega(BigInt::zero(), r1.clone(), r0, r1))?
+        } else {
+            let (t0, t1) = sqrt_roottwo(t0, t1)?;
+            let t = narrow(Omega(t0, t1.clone(), BigInt::zero(), -t1))?;
+            let n = Omega(n0, n1.clone(), BigInt::zero(), -n1);
+            narrow(&x + &n)?.rounddiv(t)
+        };
+        (&wide(w) * &wide(w) == x).then_some(w)
+    }
+
+    pub fn is_square(self) -> bool {
+        self.sqrt().is_some()
+    }
+}
+
+impl ZOmega {
+    /// The real and imaginary parts as `f64`s rounded in the given direction,
+    /// like [`DOmega::to_complex_rounded`].
+    pub fn to_complex_rounded(self, rounding: Rounding) -> Complex<f64> {
+        DOmega::from_integral(self).to_complex_rounded(rounding)
+    }
+
+    /// The real and imaginary parts with `k` fractional bits, like
+    /// [`DOmega::to_complex_fixed`].
+    pub fn to_complex_fixed(self, k: u32, rounding: Rounding) -> Complex<Fixed> {
+        DOmega::from_integral(self).to_complex_fixed(k, rounding)
+    }
+}
+
+impl DOmega {
+    fn from_integral(x: ZOmega) -> DOmega {
+        Omega(x.0.into(), x.1.into(), x.2.into(), x.3.into())
+    }
+
+    /// The real and imaginary parts as `(a + b√2)/2ᵉ` over a common `e`.
+    /// Since `ω = (1 + i)/√2`, they are `a + (b - d)√2/2` and
+    /// `c + (b + d)√2/2`.
+    fn complex_parts(self) -> ((BigInt, BigInt), (BigInt, BigInt), u32) {
+        let k = self.0 .1.max(self.1 .1).max(self.2 .1).max(self.3 .1);
+        let whole = |x: Dyadic| BigInt::from(x.0) << (k + 1 - x.1);
+        let root = |x: Dyadic| BigInt::from(x.0) << (k - x.1);
+        let (b, d) = (root(self.1), root(self.3));
+        ((whole(self.0), &b - &d), (whole(self.2), b + d), k + 1)
+    }
+
+    /// The real and imaginary parts as `f64`s, each rounded in the given
+    /// direction. Rounding down and up gives a box of floats that certainly
+    /// contains `self`.
+    pub fn to_complex_rounded(self, rounding: Rounding) -> Complex<f64> {
+        let ((a, b), (c, d), e) = self.complex_parts();
+        let denom = BigInt::one() << e;
+        Complex::new(
+            round_to_f64_directed(a, b, &denom, rounding),
+            round_to_f64_directed(c, d, &denom, rounding),
+        )
+    }
+
+    /// The real and imaginary parts with `k` fractional bits, each rounded
+    /// in the given direction, for precision beyond `f64`.
+    pub fn to_complex_fixed(self, k: u32, rounding: Rounding) -> Complex<Fixed> {
+        let ((a, b), (c, d), e) = self.complex_parts();
+        Complex::new(
+            Fixed::from_parts(a, b, e, k, rounding),
+            Fixed::from_parts(c, d, e, k, rounding),
+        )
+    }
+
+    /// A square root in D[ω], with the same choice of sign as
+    /// [`ZOmega::sqrt`]. Since ℤ[ω] is integrally closed, this reduces to
+    /// the integral case after clearing an even power of two. Returns `None`
+    /// as well if the cleared numerators do not fit in an `i64`.
+    pub fn sqrt(self) -> Option<DOmega> {
+        let coeffs = [self.0, self.1, self.2, self.3];
+        let k = coeffs.iter().map(|c| c.denomexp()).max().unwrap_or(0);
+        let scale = |c: Dyadic| {
+            let Dyadic(n, e) = Dyadic::simplify(c.0, c.1);
+            let shift = k.checked_mul(2)? - e;
+            n.checked_shl(shift).filter(|m| m >> shift == n)
+        };
+        let [a, b, c, d] = [
+            scale(coeffs[0])?,
+            scale(coeffs[1])?,
+            scale(coeffs[2])?,
+            scale(coeffs[3])?,
+        ];
+        let w = Omega(a, b, c, d).sqrt()?;
+        let [a, b, c, d] = [w.0, w.1, w.2, w.3].map(|n| Dyadic::simplify(n, k));
+        Some(Omega(a, b, c, d))
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####         Traits for Omega        ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Omega<T> {
+    type Output = Omega<T>;
+    fn add(self, rhs: Omega<T>) -> Self::Output {
+        Omega(
+            self.0 + rhs.0,
+            self.1 + rhs.1,
+            self.2 + rhs.2,
+            self.3 + rhs.3,
+        )
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Omega<T> {
+    type Output = Omega<T>;
+    fn sub(self, rhs: Omega<T>) -> Self::Output {
+        Omega(
+            self.0 - rhs.0,
+            self.1 - rhs.1,
+            self.2 - rhs.2,
+            self.3 - rhs.3,
+        )
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Omega<T> {
+    type Output = Omega<T>;
+    fn neg(self) -> Self::Output {
+        Omega(-self.0, -self.1, -self.2, -self.3)
+    }
+}
+
+impl<T: ops::AddAssign> ops::AddAssign for Omega<T> {
+    fn add_assign(&mut self, rhs: Omega<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+        self.2 += rhs.2;
+        self.3 += rhs.3;
+    }
+}
+
+impl<T: ops::SubAssign> ops::SubAssign for Omega<T> {
+    fn sub_assign(&mut self, rhs: Omega<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+        self.2 -= rhs.2;
+        self.3 -= rhs.3;
+    }
+}
+
+impl<T> ops::Mul for Omega<T>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+{
+    type Output = Omega<T>;
+    fn mul(self, rhs: Omega<T>) -> Self::Output {
+        let Omega(a0, a1, a2, a3) = self;
+        let Omega(b0, b1, b2, b3) = rhs;
+        // any term of degree 4 or more wraps around with a sign flip
+        Omega(
+            a0 * b0 - (a1 * b3 + a2 * b2 + a3 * b1),
+            a0 * b1 + a1 * b0 - (a2 * b3 + a3 * b2),
+            a0 * b2 + a1 * b1 + a2 * b0 - a3 * b3,
+            a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0,
+        )
+    }
+}
+
+impl<T: ops::Mul<i64, Output = T>> ops::Mul<i64> for Omega<T> {
+    type Output = Omega<T>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Omega(self.0 * rhs, self.1 * rhs, self.2 * rhs, self.3 * rhs)
+    }
+}
+
+impl<T> ops::MulAssign for Omega<T>
+where
+    Omega<T>: ops::Mul<Output = Omega<T>> + Clone,
+{
+    fn mul_assign(&mut self, rhs: Omega<T>) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<'a, T> ops::Add for &'a Omega<T>
+where
+    &'a T: ops::Add<Output = T>,
+{
+    type Output = Omega<T>;
+    fn add(self, rhs: &'a Omega<T>) -> Self::Output {
+        Omega(
+            &self.0 + &rhs.0,
+            &self.1 + &rhs.1,
+            &self.2 + &rhs.2,
+            &self.3 + &rhs.3,
+        )
+    }
+}
+
+impl<'a, T> ops::Sub for &'a Omega<T>
+where
+    &'a T: ops::Sub<Output = T>,
+{
+    type Output = Omega<T>;
+    fn sub(self, rhs: &'a Omega<T>) -> Self::Output {
+        Omega(
+            &self.0 - &rhs.0,
+            &self.1 - &rhs.1,
+            &self.2 - &rhs.2,
+            &self.3 - &rhs.3,
+        )
+    }
+}
+
+impl<'a, T> ops::Neg for &'a Omega<T>
+where
+    &'a T: ops::Neg<Output = T>,
+{
+    type Output = Omega<T>;
+    fn neg(self) -> Self::Output {
+        Omega(-&self.0, -&self.1, -&self.2, -&self.3)
+    }
+}
+
+impl<'a, T> ops::Mul for &'a Omega<T>
+where
+    &'a T: ops::Mul<Output = T>,
+    T: ops::Add<Output = T> + ops::Sub<Output = T>,
+{
+    type Output = Omega<T>;
+    fn mul(self, rhs: &'a Omega<T>) -> Self::Output {
+        let Omega(a0, a1, a2, a3) = self;
+        let Omega(b0, b1, b2, b3) = rhs;
+        Omega(
+            a0 * b0 - (a1 * b3 + a2 * b2 + a3 * b1),
+            a0 * b1 + a1 * b0 - (a2 * b3 + a3 * b2),
+            a0 * b2 + a1 * b1 + a2 * b0 - a3 * b3,
+            a0 * b3 + a1 * b2 + a2 * b1 + a3 * b0,
+        )
+    }
+}
+
+impl<T: ops::MulAssign<i64>> ops::MulAssign<i64> for Omega<T> {
+    fn mul_assign(&mut self, rhs: i64) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+        self.2 *= rhs;
+        self.3 *= rhs;
+    }
+}
+
+impl ops::Mul<ZOmega> for i64 {
+    type Output = ZOmega;
+    fn mul(self, rhs: ZOmega) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl ops::Mul<DOmega> for i64 {
+    type Output = DOmega;
+    fn mul(self, rhs: DOmega) -> Self::Output {
+        rhs * self
+    }
+}
+
+/// Each part correctly rounded. See [`DOmega::to_complex_rounded`].
+impl From<ZOmega> for Complex<f64> {
+    fn from(val: ZOmega) -> Self {
+        val.to_complex_rounded(Rounding::Nearest)
+    }
+}
+
+/// Each part correctly rounded. See [`DOmega::to_complex_rounded`].
+impl From<DOmega> for Complex<f64> {
+    fn from(val: DOmega) -> Self {
+        val.to_complex_rounded(Rounding::Nearest)
+    }
+}
+
+impl<T: ops::Neg<Output = T>> Adj2 for Omega<T> {
+    /// Maps `√2` to `-√2` while fixing `i`, which sends `ω` to `-ω`.
+    fn adj2(self) -> Self {
+        Omega(self.0, -self.1, self.2, -self.3)
+    }
+}
+
+impl<T: Zero> Zero for Omega<T> {
+    fn zero() -> Self {
+        Omega(T::zero(), T::zero(), T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero() && self.2.is_zero() && self.3.is_zero()
+    }
+}
+
+impl<T> One for Omega<T>
+where
+    T: Zero + One + PartialEq,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+    fn one() -> Self {
+        Omega(T::one(), T::zero(), T::zero(), T::zero())
+    }
+
+    fn is_one(&self) -> bool {
+        self.0.is_one() && self.1.is_zero() && self.2.is_zero() && self.3.is_zero()
+    }
+}
+
+impl<T: Adj + ops::Neg<Output = T>> Adj for Omega<T> {
+    /// Sends `ω` to `ω⁻¹ = -ω³`.
+    fn adj(self) -> Self {
+        Omega(self.0.adj(), -self.3.adj(), -self.2.adj(), -self.1.adj())
+    }
+}
+
+impl<T: WholePart> WholePart for Omega<T> {
+    type Whole = Omega<T::Whole>;
+
+    fn from_whole(whole: Self::Whole) -> Self {
+        Omega(
+            T::from_whole(whole.0),
+            T::from_whole(whole.1),
+            T::from_whole(whole.2),
+            T::from_whole(whole.3),
+        )
+    }
+
+    fn whole_part(self) -> Self::Whole {
+        Omega(
+            self.0.whole_part(),
+            self.1.whole_part(),
+            self.2.whole_part(),
+            self.3.whole_part(),
+        )
+    }
+
+    fn frac_part(self) -> Option<Self> {
+        Some(Omega(
+            self.0.frac_part()?,
+            self.1.frac_part()?,
+            self.2.frac_part()?,
+            self.3.frac_part()?,
+        ))
+    }
+}
+
+impl DenomExp for DOmega {
+    /// With `k` the largest dyadic exponent, `y = 2ᵏ·self` is in ℤ[ω] and
+    /// not divisible by `2 = √2²`, so `y/√2` is integral exactly when the
+    /// odd coefficients of `y` are those of `1 + ω²`, `ω + ω³` or their sum.
+    fn denomexp(&self) -> u32 {
+        let exps = [self.0, self.1, self.2, self.3].map(|c| c.denomexp());
+        let k = exps.into_iter().max().unwrap_or(0);
+        if k == 0 {
+            return 0;
+        }
+        // the coefficients of y that are odd
+        let odd = exps.map(|e| e == k);
+        if odd[0] == odd[2] && odd[1] == odd[3] {
+            2 * k - 1
+        } else {
+            2 * k
+        }
+    }
+}
+
+impl<T: Parity> Parity for Omega<T> {
+    type Residue = Omega<T::Residue>;
+    fn residue(&self) -> Self::Residue {
+        Omega(
+            self.0.residue(),
+            self.1.residue(),
+            self.2.residue(),
+            self.3.residue(),
+        )
+    }
+}
+
+impl From<RootTwo<i64>> for ZOmega {
+    /// Embeds `a + b√2` using `√2 = ω - ω³`.
+    fn from(val: RootTwo<i64>) -> Self {
+        Omega(val.0, val.1, 0, -val.1)
+    }
+}
+
+impl From<RootTwo<Dyadic>> for DOmega {
+    /// Embeds `a + b√2` using `√2 = ω - ω³`.
+    fn from(val: RootTwo<Dyadic>) -> Self {
+        Omega(val.0, val.1, Dyadic::zero(), -val.1)
+    }
+}
+
+#[cfg(test)]
+mod omega_tests {
+    use super::*;
+    use num::pow::Pow;
+    use std::{cmp::Ordering, f64::consts::FRAC_1_SQRT_2};
+    #[test]
+    fn units_zomega() {
+        let omega: ZOmega = Omega(0, 1, 0, 0);
+        assert_eq!(omega.inv(), Some(Omega(0, 0, 0, -1)));
+        // λ = 1 + √2 = 1 + ω - ω³
+        let lambda: ZOmega = Omega(1, 1, 0, -1);
+        let u = lambda * lambda * omega;
+        assert!(u.is_unit());
+        assert_eq!(u * u.inv().unwrap(), Omega(1, 0, 0, 0));
+        assert!(!Omega(1, 1, 0, 0).is_unit());
+        assert_eq!(Omega(2, 0, 0, 0).inv(), None);
+        // norms far beyond an i64
+        assert!(!ZOmega::new(1 << 16, 1, 0, 0).is_unit());
+        assert!(!ZOmega::new(i64::MAX, i64::MIN, i64::MAX, i64::MIN).is_unit());
+        assert_eq!(ZOmega::new(1 << 40, 0, 0, 0).inv(), None);
+        let big = RootTwo::<i64>::lambda().pow(40i32).unwrap();
+        let small = RootTwo::<i64>::lambda().pow(-40i32).unwrap();
+        assert!(ZOmega::from(big).is_unit());
+        assert_eq!(ZOmega::from(big).inv(), Some(ZOmega::from(small)));
+    }
+
+    #[test]
+    fn constructor_and_coefficients() {
+        let x = ZOmega::new(1, -2, 3, -4);
+        assert_eq!(x, Omega(1, -2, 3, -4));
+        assert_eq!(x.coeffs(), [1, -2, 3, -4]);
+        assert_eq!(
+            DOmega::new(Dyadic(1, 1), Dyadic(0, 0), Dyadic(0, 0), Dyadic(0, 0)).coeffs()[0],
+            Dyadic(1, 1)
+        );
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn reference_operands_zomega() {
+        let x = Omega(1, 2, 3, 4);
+        let y = Omega(5, -6, 7, 8);
+        assert_eq!(&x * &y, x * y);
+        assert_eq!(&x + &y, x + y);
+        assert_eq!(&x - &y, x - y);
+        assert_eq!(-&x, -x);
+    }
+
+    #[test]
+    fn compound_assignment_zomega() {
+        let x = Omega(1, 2, 3, 4);
+        let mut y = Omega(0, 1, 0, -1);
+        y *= x;
+        assert_eq!(y, Omega(0, 1, 0, -1) * x);
+        y += x;
+        y -= Omega(0, 1, 0, -1) * x;
+        assert_eq!(y, x);
+        y *= 3;
+        assert_eq!(y, x * 3);
+    }
+
+    #[test]
+    fn basic_add_zomega() {
+        let first = Omega(1, 2, 3, 4);
+        let second = Omega(5, -6, 7, 8);
+        assert_eq!(first + second, Omega(6, -4, 10, 12));
+        assert_eq!(first - second, Omega(-4, 8, -4, -4));
+        assert_eq!(-first, Omega(-1, -2, -3, -4));
+    }
+
+    #[test]
+    fn omega_is_eighth_root_of_unity() {
+        let omega = Omega(0, 1, 0, 0);
+        assert_eq!(omega * omega, Omega(0, 0, 1, 0));
+        assert_eq!(omega * omega * omega * omega, Omega(-1, 0, 0, 0));
+    }
+
+    #[test]
+    fn square_roots_zomega() {
+        for w in [
+            Omega(0, 0, 0, 0),
+            Omega(1, 0, 0, 0),
+            Omega(3, -1, 4, 1),
+            Omega(0, 0, 5, 0),
+            Omega(-2, 7, 1, -8),
+            Omega(0, 1, 0, 1),
+        ] {
+            let root = (w * w).sqrt().unwrap();
+            assert!(root == w || root == -w);
+        }
+        // -1 = i² and 2 = √2², taking the roots in the upper right
+        assert_eq!(Omega(-1, 0, 0, 0).sqrt(), Some(Omega(0, 0, 1, 0)));
+        assert_eq!(Omega(2, 0, 0, 0).sqrt(), Some(Omega(0, 1, 0, -1)));
+        assert_eq!(Omega(0, 0, 1, 0).sqrt(), Some(Omega(0, 1, 0, 0)));
+        assert!(!Omega(0, 1, 0, 0).is_square());
+        assert!(!Omega(1, 1, 0, 0).is_square());
+        assert!(!Omega(3, 0, 0, 0).is_square());
+        // coefficients near 2³³, where self·self† no longer fits in an i64
+        let w: ZOmega = Omega(1 << 16, 3, -5, 1 << 15);
+        let root = (w * w).sqrt().unwrap();
+        assert!(root == w || root == -w);
+        assert_eq!(ZOmega::new(1 << 33, 1, 0, 0).sqrt(), None);
+        assert_eq!(
+            ZOmega::new(1 << 34, 0, 0, 0).sqrt(),
+            Some(ZOmega::new(1 << 17, 0, 0, 0))
+        );
+        assert_eq!(
+            ZOmega::new(i64::MAX, i64::MIN, i64::MAX, i64::MIN).sqrt(),
+            None
+        );
+        assert_eq!(ZOmega::new(-(1 << 62) - 1, 0, 0, 0).sqrt(), None);
+        assert_eq!(
+            ZOmega::new(-(1 << 60), 0, 0, 0).sqrt(),
+            Some(ZOmega::new(0, 0, 1 << 30, 0))
+        );
+    }
+
+    #[test]
+    fn square_roots_domega() {
+        let d = |n, k| Dyadic(n, k);
+        let half: DOmega = Omega(d(1, 1), d(0, 0), d(0, 0), d(0, 0));
+        // 1/√2 = (ω - ω³)/2
+        let roothalf = Omega(d(0, 0), d(1, 1), d(0, 0), d(-1, 1));
+        assert_eq!(half.sqrt(), Some(roothalf));
+        let w = Omega(d(3, 2), d(-1, 0), d(0, 0), d(5, 3));
+        let root = (w * w).sqrt().unwrap();
+        assert!(root == w || root == -w);
+        let omega: DOmega = Omega(d(0, 0), d(1, 2), d(0, 0), d(0, 0));
+        assert_eq!(omega.sqrt(), None);
+        // clearing 4³⁵ from 1/2⁷⁰ needs a shift past 64 bits
+        let tiny: DOmega = Omega(d(1, 70), d(1, 0), d(0, 0), d(0, 0));
+        assert_eq!(tiny.sqrt(), None);
+        let wide: DOmega = Omega(d(1, 1), d(1 << 62, 0), d(0, 0), d(0, 0));
+        assert_eq!(wide.sqrt(), None);
+    }
+
+    #[test]
+    fn mul_agrees_with_roottwo() {
+        let first = RootTwo::new(3, 4);
+        let second = RootTwo::new(5, 6);
+        let product = ZOmega::from(first) * ZOmega::from(second);
+        assert_eq!(product, ZOmega::from(first * second));
+        assert_eq!(ZOmega::from(first).adj2(), ZOmega::from(first.adj2()));
+    }
+
+    #[test]
+    fn domega_arithmetic() {
+        let half = Dyadic(1, 1);
+        let zero = Dyadic(0, 0);
+        // (1 + i)/2 squared is i/2
+        let x = Omega(half, zero, half, zero);
+        assert_eq!(x * x, Omega(zero, zero, half, zero));
+        assert_eq!(x * 2, Omega(Dyadic(1, 0), zero, Dyadic(1, 0), zero));
+        assert_eq!(2 * x, x * 2);
+    }
+
+    #[test]
+    fn denomexp_domega() {
+        let zero = Dyadic(0, 0);
+        let roothalf = DOmega::from(RootTwo::new(zero, Dyadic(1, 1)));
+        assert_eq!(roothalf.denomexp(), 1);
+        // (1 + ω)/2 needs the full √2²
+        assert_eq!(Omega(Dyadic(1, 1), Dyadic(1, 1), zero, zero).denomexp(), 2);
+        // (1 + i)/2 == ω/√2
+        assert_eq!(Omega(Dyadic(1, 1), zero, Dyadic(1, 1), zero).denomexp(), 1);
+        assert_eq!(Omega(Dyadic(3, 0), zero, zero, Dyadic(6, 1)).denomexp(), 0);
+        // exponents far apart, and numerators that would overflow if scaled
+        let big = Dyadic(i64::MAX, 0);
+        assert_eq!(Omega(big, zero, zero, Dyadic(1, 1)).denomexp(), 2);
+        assert_eq!(
+            Omega(Dyadic(1, 70), big, Dyadic(-1, 70), zero).denomexp(),
+            139
+        );
+    }
+
+    #[test]
+    fn residues_zomega() {
+        assert_eq!(Omega(1, -2, 3, -5).residue(), Omega(1, 0, 1, 1));
+        // multiplication by ω permutes residues up to sign, which is invisible mod 2
+        let x = Omega(1, 0, 1, 1);
+        assert_eq!(
+            (x * ZOmega::from(RootTwo::new(1, 0))).residue(),
+            x.residue()
+        );
+        assert_eq!((x * Omega(0, 1, 0, 0)).residue(), Omega(1, 1, 0, 1));
+    }
+
+    #[test]
+    fn into_complex_works() {
+        let roothalf = RootTwo::new(Dyadic(0, 0), Dyadic(1, 1));
+        let z: Complex<f64> = DOmega::from(roothalf).into();
+        assert!((z - Complex::new(FRAC_1_SQRT_2, 0.0)).norm() < 1e-12);
+        let omega: Complex<f64> = Omega(0, 1, 0, 0).into();
+        assert!((omega - Complex::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn directed_complex_bounds() {
+        let omega: ZOmega = Omega(0, 1, 0, 0);
+        let nearest = Complex::<f64>::from(omega);
+        assert_eq!(nearest, Complex::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2));
+        // 1/√2 is irrational, so the bounds are adjacent floats either side
+        let (down, up) = (
+            omega.to_complex_rounded(Rounding::Down),
+            omega.to_complex_rounded(Rounding::Up),
+        );
+        assert_eq!(down.re.next_up(), up.re);
+        assert_eq!(down.im.next_up(), up.im);
+        assert!(down.re <= nearest.re && nearest.re <= up.re);
+        assert_eq!(
+            RootTwo::<Dyadic>::new(Dyadic(0, 0), Dyadic(1, 1)).cmp_f64(down.re),
+            Ordering::Greater
+        );
+        // exact parts need no widening
+        let x: DOmega = Omega(Dyadic(3, 2), Dyadic(0, 0), Dyadic(-1, 0), Dyadic(0, 0));
+        assert_eq!(
+            x.to_complex_rounded(Rounding::Down),
+            Complex::new(0.75, -1.0)
+        );
+        assert_eq!(x.to_complex_rounded(Rounding::Up), Complex::new(0.75, -1.0));
+        // λ⁻ⁿ = (√2 - 1)ⁿ cancels almost entirely in its coefficients
+        let lambda_inv = DOmega::from(RootTwo::new(Dyadic(-1, 0), Dyadic(1, 0)));
+        let power = (0..20).fold(DOmega::one(), |power, _| power * lambda_inv);
+        let (down, up) = (
+            power.to_complex_rounded(Rounding::Down),
+            power.to_complex_rounded(Rounding::Up),
+        );
+        assert_eq!(down.re.next_up(), up.re);
+        assert!((down.re - (2f64.sqrt() - 1.0).powi(20)).abs() < 1e-20);
+        assert_eq!((down.im, up.im), (0.0, 0.0));
+        let fixed = power.to_complex_fixed(128, Rounding::Down);
+        assert_eq!(fixed.re.to_f64(), Complex::<f64>::from(power).re);
+        assert!(fixed.re < power.to_complex_fixed(128, Rounding::Up).re);
+        assert_eq!(
+            omega.to_complex_fixed(8, Rounding::Nearest).re.mantissa(),
+            &BigInt::from(181)
+        );
+    }
+}
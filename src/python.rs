@@ -0,0 +1,341 @@
+//! Python bindings via PyO3, built into an extension module named `rings`
+//! when the `python` feature is enabled together with
+//! `pyo3/extension-module`, e.g. by maturin.
+//!
+//! The module has the classes `Dyadic` and `RootTwo`, the latter holding
+//! dyadic coefficients, and the functions `approximate` and
+//! `solve_norm_equation`. Arithmetic mixes freely with Python integers, and
+//! raises `OverflowError` where the Rust operators would overflow.
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use num::Zero;
+use pyo3::{
+    basic::CompareOp,
+    exceptions::{PyOverflowError, PyValueError},
+    prelude::*,
+};
+
+use crate::{approx::approximable, diophantine, Dyadic, Quad, RootTwo};
+
+#[pyclass(name = "Dyadic", module = "rings", frozen)]
+#[derive(Clone, Copy)]
+struct PyDyadic(Dyadic);
+
+#[pyclass(name = "RootTwo", module = "rings", frozen)]
+#[derive(Clone, Copy)]
+struct PyRootTwo(RootTwo<Dyadic>);
+
+/// An operand that converts to a dyadic.
+#[derive(FromPyObject)]
+enum DyadicLike {
+    Dyadic(PyDyadic),
+    Int(i64),
+}
+
+impl From<DyadicLike> for Dyadic {
+    fn from(val: DyadicLike) -> Self {
+        match val {
+            DyadicLike::Dyadic(x) => x.0,
+            DyadicLike::Int(n) => Dyadic::new(n, 0),
+        }
+    }
+}
+
+/// An operand that converts to an element of D[√2].
+#[derive(FromPyObject)]
+enum RootTwoLike {
+    RootTwo(PyRootTwo),
+    Dyadic(DyadicLike),
+}
+
+impl From<RootTwoLike> for RootTwo<Dyadic> {
+    fn from(val: RootTwoLike) -> Self {
+        match val {
+            RootTwoLike::RootTwo(x) => x.0,
+            RootTwoLike::Dyadic(x) => Quad(x.into(), Dyadic::zero()),
+        }
+    }
+}
+
+fn overflow<T>(x: Option<T>) -> PyResult<T> {
+    x.ok_or_else(|| PyOverflowError::new_err("coefficient overflow"))
+}
+
+fn hash(x: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn dyadic_repr(x: Dyadic) -> String {
+    if x.1 == 0 {
+        x.0.to_string()
+    } else {
+        format!("Dyadic({}, {})", x.0, x.1)
+    }
+}
+
+#[pymethods]
+impl PyDyadic {
+    /// `n/2ᵏ`
+    #[new]
+    #[pyo3(signature = (n, k = 0))]
+    fn new(n: i64, k: u32) -> Self {
+        PyDyadic(Dyadic::new(n, k))
+    }
+
+    /// Parses the printed form, such as `"3/2^4"` or `"3/16"`.
+    #[staticmethod]
+    fn parse(s: &str) -> PyResult<Self> {
+        s.parse()
+            .map(PyDyadic)
+            .map_err(|err| PyValueError::new_err(format!("{err}")))
+    }
+
+    #[getter]
+    fn numer(&self) -> i64 {
+        self.0 .0
+    }
+
+    #[getter]
+    fn exponent(&self) -> u32 {
+        self.0 .1
+    }
+
+    fn __add__(&self, other: DyadicLike) -> PyResult<Self> {
+        overflow(self.0.checked_add(other.into())).map(PyDyadic)
+    }
+
+    fn __radd__(&self, other: DyadicLike) -> PyResult<Self> {
+        self.__add__(other)
+    }
+
+    fn __sub__(&self, other: DyadicLike) -> PyResult<Self> {
+        overflow(self.0.checked_sub(other.into())).map(PyDyadic)
+    }
+
+    fn __rsub__(&self, other: DyadicLike) -> PyResult<Self> {
+        overflow(Dyadic::from(other).checked_sub(self.0)).map(PyDyadic)
+    }
+
+    fn __mul__(&self, other: DyadicLike) -> PyResult<Self> {
+        overflow(self.0.checked_mul(other.into())).map(PyDyadic)
+    }
+
+    fn __rmul__(&self, other: DyadicLike) -> PyResult<Self> {
+        self.__mul__(other)
+    }
+
+    fn __neg__(&self) -> PyResult<Self> {
+        overflow(self.0.checked_neg()).map(PyDyadic)
+    }
+
+    fn __float__(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    fn __richcmp__(&self, other: DyadicLike, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other.into()))
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash(&self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Dyadic({}, {})", self.0 .0, self.0 .1)
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[pymethods]
+impl PyRootTwo {
+    /// `a + b√2`
+    #[new]
+    #[pyo3(signature = (a = DyadicLike::Int(0), b = DyadicLike::Int(0)))]
+    fn new(a: DyadicLike, b: DyadicLike) -> Self {
+        PyRootTwo(Quad(a.into(), b.into()))
+    }
+
+    /// Parses the printed form, such as `"1/2^1 - 3√2"`.
+    #[staticmethod]
+    fn parse(s: &str) -> PyResult<Self> {
+        s.parse()
+            .map(PyRootTwo)
+            .map_err(|err| PyValueError::new_err(format!("{err}")))
+    }
+
+    #[getter]
+    fn a(&self) -> PyDyadic {
+        PyDyadic(self.0 .0)
+    }
+
+    #[getter]
+    fn b(&self) -> PyDyadic {
+        PyDyadic(self.0 .1)
+    }
+
+    /// `a - b√2`
+    fn conj(&self) -> PyResult<Self> {
+        let b = overflow(self.0 .1.checked_neg())?;
+        Ok(PyRootTwo(Quad(self.0 .0, b)))
+    }
+
+    fn __add__(&self, other: RootTwoLike) -> PyResult<Self> {
+        overflow(self.0.checked_add(other.into())).map(PyRootTwo)
+    }
+
+    fn __radd__(&self, other: RootTwoLike) -> PyResult<Self> {
+        self.__add__(other)
+    }
+
+    fn __sub__(&self, other: RootTwoLike) -> PyResult<Self> {
+        overflow(self.0.checked_sub(other.into())).map(PyRootTwo)
+    }
+
+    fn __rsub__(&self, other: RootTwoLike) -> PyResult<Self> {
+        overflow(RootTwo::from(other).checked_sub(self.0)).map(PyRootTwo)
+    }
+
+    fn __mul__(&self, other: RootTwoLike) -> PyResult<Self> {
+        overflow(self.0.checked_mul(other.into())).map(PyRootTwo)
+    }
+
+    fn __rmul__(&self, other: RootTwoLike) -> PyResult<Self> {
+        self.__mul__(other)
+    }
+
+    fn __neg__(&self) -> PyResult<Self> {
+        overflow(self.0.checked_neg()).map(PyRootTwo)
+    }
+
+    /// The correctly rounded value.
+    fn __float__(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    fn __richcmp__(&self, other: RootTwoLike, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other.into()))
+    }
+
+    fn __hash__(&self) -> u64 {
+        hash(&self.0)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "RootTwo({}, {})",
+            dyadic_repr(self.0 .0),
+            dyadic_repr(self.0 .1)
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// The closest `u = (a + b√2)/2ᵏ` to `x` with `|u•| ≤ 1`, and the error
+/// `x - u`, as in [`RootTwo::<Dyadic>::approximate`].
+#[pyfunction]
+fn approximate(x: f64, k: u32) -> PyResult<(PyRootTwo, PyRootTwo)> {
+    if !approximable(x, k) {
+        return Err(PyValueError::new_err(
+            "approximation needs a finite x, k ≤ 40 and |x|·2ᵏ < 2⁶²",
+        ));
+    }
+    let (u, err) = RootTwo::<Dyadic>::approximate(x, k);
+    Ok((PyRootTwo(u), PyRootTwo(err)))
+}
+
+/// The coefficients `(a, b, c, d)` of some `t = a + bω + cω² + dω³` with
+/// `t†t = ξ`, or `None`, as in [`diophantine::solve_norm_equation`].
+#[pyfunction]
+fn solve_norm_equation(xi: RootTwoLike) -> PyResult<Option<(i64, i64, i64, i64)>> {
+    let Quad(a, b) = RootTwo::<Dyadic>::from(xi);
+    if a.1 != 0 || b.1 != 0 {
+        return Err(PyValueError::new_err("ξ must have integer coefficients"));
+    }
+    Ok(diophantine::solve_norm_equation(Quad(a.0, b.0)).map(|t| (t.0, t.1, t.2, t.3)))
+}
+
+#[pymodule]
+fn rings(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyDyadic>()?;
+    m.add_class::<PyRootTwo>()?;
+    m.add_function(wrap_pyfunction!(approximate, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_norm_equation, m)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod python_tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    /// Runs `code` with the module bound to `rings`.
+    fn run(code: &std::ffi::CStr) {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let m = pyo3::wrap_pymodule!(rings)(py);
+            let locals = PyDict::new(py);
+            locals.set_item("rings", m).unwrap();
+            py.run(code, None, Some(&locals))
+                .map_err(|err| err.display(py))
+                .unwrap();
+        });
+    }
+
+    #[test]
+    fn dyadic_from_python() {
+        run(c"
+Dyadic = rings.Dyadic
+x = Dyadic(3, 2)
+assert x + 1 == Dyadic(7, 2)
+assert 1 - x == Dyadic(1, 2)
+assert x * 4 == 3 and -x < 0
+assert float(x) == 0.75
+assert repr(x) == 'Dyadic(3, 2)' and str(x) == '3/2^2'
+assert Dyadic.parse('3/16') == Dyadic(3, 4)
+assert (x.numer, x.exponent) == (3, 2)
+try:
+    Dyadic(2**62) + Dyadic(2**62)
+    raise AssertionError
+except OverflowError:
+    pass
+");
+    }
+
+    #[test]
+    fn roottwo_from_python() {
+        run(c"
+RootTwo, Dyadic = rings.RootTwo, rings.Dyadic
+x = RootTwo(1, 1)
+assert x * x.conj() == -1
+assert x * x == RootTwo(3, 2) and 2 * x - x == x
+assert abs(float(x) - 2.414213562373095) < 1e-15
+assert repr(RootTwo(Dyadic(3, 1), -1)) == 'RootTwo(Dyadic(3, 1), -1)'
+assert str(RootTwo(3, -2)) == '3 - 2√2'
+assert RootTwo.parse('3 - 2√2') == RootTwo(3, -2)
+u, err = rings.approximate(0.3, 3)
+assert abs(float(u) + float(err) - 0.3) < 1e-15
+assert rings.solve_norm_equation(RootTwo(5, 0)) is not None
+assert rings.solve_norm_equation(7) is None
+assert rings.solve_norm_equation(RootTwo(2**62, 1)) is None
+try:
+    rings.approximate(float('nan'), 3)
+    raise AssertionError
+except ValueError:
+    pass
+try:
+    rings.approximate(1e9, 40)
+    raise AssertionError
+except ValueError:
+    pass
+");
+    }
+}
@@ -0,0 +1,2263 @@
+use std::{cmp::Ordering, f64::consts::SQRT_2, fmt, iter, ops};
+
+use num::{
+    pow, BigInt, BigRational, CheckedAdd, CheckedMul, CheckedSub, Complex, Num, One, Rational64,
+    Signed, ToPrimitive, Zero,
+};
+
+use crate::{
+    dyadic::{cmp_nonfinite, float_parts, write_approx},
+    ring::{exact_sqrt, rounddiv},
+    Adj, Adj2, DenomExp, Dyadic, EuclideanDomain, FromRatioError, Parity, ParseRingError, Rounding,
+    ToLatex, WholePart,
+};
+
+/// An element `a + b√D` of a quadratic extension, where the radicand `D` is
+/// a non-square integer.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Quad<T, const D: i64>(pub(crate) T, pub(crate) T);
+
+/// Ring elements of the form `a + b√2`.
+pub type RootTwo<T> = Quad<T, 2>;
+
+/// Ring elements of the form `a + b√-2`.
+pub type RootMinusTwo<T> = Quad<T, -2>;
+
+/// The field ℚ(√2).
+pub type QRootTwo = RootTwo<Rational64>;
+
+impl<T, const D: i64> Quad<T, D> {
+    pub fn new(a: T, b: T) -> Quad<T, D> {
+        Quad(a, b)
+    }
+
+    /// The Galois conjugate. `conj(a + b√D) == a - b√D`
+    pub fn conj(self) -> Quad<T, D>
+    where
+        T: ops::Neg<Output = T>,
+    {
+        Quad(self.0, -self.1)
+    }
+
+    /// The field norm. `norm(a + b√D) == a² - Db²`
+    pub fn norm(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Sub<Output = T> + ops::Mul<i64, Output = T> + Clone,
+    {
+        self.0.clone() * self.0 - self.1.clone() * self.1 * D
+    }
+
+    /// The field trace, the sum of both conjugates. `trace(a + b√D) == 2a`
+    pub fn trace(self) -> T
+    where
+        T: ops::Add<Output = T> + Clone,
+    {
+        self.0.clone() + self.0
+    }
+}
+
+impl<T: Zero + One> RootTwo<T> {
+    /// The fundamental unit `λ = 1 + √2`. Every unit of ℤ[√2] is `±λᵏ`.
+    pub fn lambda() -> RootTwo<T> {
+        Quad(T::one(), T::one())
+    }
+}
+
+impl RootTwo<i64> {
+    /// Writes a unit as `s·λᵏ` and returns `(s, k)` with `s = ±1`, or `None`
+    /// if `self` is not a unit.
+    pub fn unit_decomposition(self) -> Option<(i64, i32)> {
+        if !self.is_unit() {
+            return None;
+        }
+        let s = self.sign();
+        let (mut a, mut b) = (s as i128 * self.0 as i128, s as i128 * self.1 as i128);
+        let mut k = 0;
+        // a positive unit exceeds 1 exactly when both coefficients are
+        // positive, and each step moves it towards 1
+        while b != 0 {
+            if a > 0 && b > 0 {
+                (a, b) = (2 * b - a, a - b);
+                k += 1;
+            } else {
+                (a, b) = (a + 2 * b, a + b);
+                k -= 1;
+            }
+        }
+        Some((s, k))
+    }
+
+    /// The square root that is nonnegative as a real number, if one exists
+    /// in ℤ[√2]. Writing `(x + y√2)² = a + b√2` gives `x² + 2y² = a` and
+    /// `x² - 2y² = ±√norm`, which determines `x²` and `y²`. A norm that
+    /// overflows an `i128` is negative, so there is no root.
+    pub fn sqrt(self) -> Option<RootTwo<i64>> {
+        let (a, b) = (self.0 as i128, self.1 as i128);
+        let m = exact_sqrt((a * a).checked_sub((b * b).checked_mul(2)?)?)?;
+        let root = [m, -m].into_iter().find_map(|m| {
+            let x = exact_sqrt((a + m).checked_div(2).filter(|_| (a + m) % 2 == 0)?)?;
+            let y = exact_sqrt((a - m).checked_div(4).filter(|_| (a - m) % 4 == 0)?)?;
+            let y = if b < 0 { -y } else { y };
+            (2 * x * y == b).then_some(Quad(x as i64, y as i64))
+        })?;
+        Some(if root.sign() < 0 { -root } else { root })
+    }
+
+    pub fn is_square(self) -> bool {
+        self.sqrt().is_some()
+    }
+
+    /// The residue modulo `√2`. Since `√2 ≡ 0`, only `a mod 2` survives.
+    pub fn residue_roottwo(&self) -> u8 {
+        self.0.residue()
+    }
+
+    /// The sign of `a + b√2` as a real number, computed exactly by comparing
+    /// `a²` with `2b²` when the coefficients have opposite signs.
+    pub fn sign(self) -> i64 {
+        sign_wide(self.0 as i128, self.1 as i128) as i64
+    }
+
+    /// The element of ℤ[√2] nearest to `self / rhs`, rounding each
+    /// coefficient of the exact quotient `self·conj(rhs) / norm(rhs)`. The
+    /// quotient is computed in a `BigInt`, since `2d²` alone can exceed an
+    /// `i128`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero, or if the quotient does not fit in an `i64`.
+    pub fn rounddiv(self, rhs: RootTwo<i64>) -> RootTwo<i64> {
+        let [a, b, c, d] = [self.0, self.1, rhs.0, rhs.1].map(BigInt::from);
+        let norm: BigInt = &c * &c - &d * &d * 2;
+        if norm.is_zero() {
+            panic!("attempted to divide by zero");
+        }
+        let round = |x| i64::try_from(rounddiv(x, &norm)).expect("quotient overflows i64");
+        Quad(round(&a * &c - &b * &d * 2), round(b * c - a * d))
+    }
+}
+
+impl<const D: i64> Quad<i64, D> {
+    /// Multiplies with `i128` intermediates, so only results whose
+    /// coefficients really exceed `i64` are treated as overflow. The `*`
+    /// operator instead computes `a·c + D·b·d` in `i64`, where the partial
+    /// products can overflow even though their sum fits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either coefficient of the product does not fit in an `i64`.
+    pub fn strict_mul(self, rhs: Quad<i64, D>) -> Quad<i64, D> {
+        self.checked_mul(rhs)
+            .expect("attempt to multiply with overflow")
+    }
+
+    /// The norm computed in `i128`, or `None` if even that overflows, as
+    /// `D·b²` can for `b` near `i64::MIN`. Such a norm is never `±1`.
+    pub(crate) fn wide_norm(self) -> Option<i128> {
+        let (a, b) = (self.0 as i128, self.1 as i128);
+        (a * a).checked_sub((D as i128).checked_mul(b * b)?)
+    }
+
+    pub fn is_unit(self) -> bool {
+        self.wide_norm().is_some_and(|n| n.abs() == 1)
+    }
+
+    /// The inverse of a unit, which is `±conj(self)` with the sign of the
+    /// norm.
+    pub fn inv(self) -> Option<Quad<i64, D>> {
+        match self.wide_norm()? {
+            1 => Some(self.conj()),
+            -1 => Some(-self.conj()),
+            _ => None,
+        }
+    }
+
+    /// The exact quotient `self / rhs`, or `None` if it does not lie in the
+    /// ring or `rhs` is zero. The quotient is `self·conj(rhs) / norm(rhs)`,
+    /// so this checks that the norm divides both coefficients. Both are
+    /// computed in a `BigInt`, since `D·b·d` can exceed an `i128`.
+    pub fn try_div(self, rhs: Quad<i64, D>) -> Option<Quad<i64, D>> {
+        let [a, b, c, d] = [self.0, self.1, rhs.0, rhs.1].map(BigInt::from);
+        let norm: BigInt = &c * &c - &d * &d * D;
+        if norm.is_zero() {
+            return None;
+        }
+        let (x, y): (BigInt, BigInt) = (&a * &c - &b * &d * D, b * c - a * d);
+        if !(&x % &norm).is_zero() || !(&y % &norm).is_zero() {
+            return None;
+        }
+        Some(Quad(
+            (x / &norm).try_into().ok()?,
+            (y / norm).try_into().ok()?,
+        ))
+    }
+
+    /// Coefficientwise addition that returns `None` on overflow.
+    pub fn checked_add(self, rhs: Quad<i64, D>) -> Option<Quad<i64, D>> {
+        Some(Quad(self.0.checked_add(rhs.0)?, self.1.checked_add(rhs.1)?))
+    }
+
+    /// Coefficientwise subtraction that returns `None` on overflow.
+    pub fn checked_sub(self, rhs: Quad<i64, D>) -> Option<Quad<i64, D>> {
+        Some(Quad(self.0.checked_sub(rhs.0)?, self.1.checked_sub(rhs.1)?))
+    }
+
+    /// Multiplication that returns `None` if a coefficient of the product
+    /// does not fit in an `i64`. See [`Quad::strict_mul`].
+    pub fn checked_mul(self, rhs: Quad<i64, D>) -> Option<Quad<i64, D>> {
+        let (a, b) = (self.0 as i128, self.1 as i128);
+        let (c, d) = (rhs.0 as i128, rhs.1 as i128);
+        // |b·d·D| can only exceed i128 when the product is far out of range
+        let first = (b * d).checked_mul(D as i128)?.checked_add(a * c)?;
+        let second = (a * d).checked_add(b * c)?;
+        Some(Quad(first.try_into().ok()?, second.try_into().ok()?))
+    }
+
+    /// Exponentiation by squaring that returns `None` on overflow.
+    pub fn checked_pow(self, mut power: u32) -> Option<Quad<i64, D>> {
+        let mut base = self;
+        let mut result = Quad(1, 0);
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.checked_mul(base)?;
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.checked_mul(base)?;
+            }
+        }
+        Some(result)
+    }
+
+    /// Coefficientwise wrapping addition.
+    pub fn wrapping_add(self, rhs: Quad<i64, D>) -> Quad<i64, D> {
+        Quad(self.0.wrapping_add(rhs.0), self.1.wrapping_add(rhs.1))
+    }
+
+    /// Coefficientwise wrapping subtraction.
+    pub fn wrapping_sub(self, rhs: Quad<i64, D>) -> Quad<i64, D> {
+        Quad(self.0.wrapping_sub(rhs.0), self.1.wrapping_sub(rhs.1))
+    }
+
+    /// Multiplication with each coefficient of the product reduced modulo
+    /// `2⁶⁴`.
+    pub fn wrapping_mul(self, rhs: Quad<i64, D>) -> Quad<i64, D> {
+        let (a, b) = (self.0 as i128, self.1 as i128);
+        let (c, d) = (rhs.0 as i128, rhs.1 as i128);
+        let first = (b * d).wrapping_mul(D as i128).wrapping_add(a * c);
+        let second = (a * d).wrapping_add(b * c);
+        Quad(first as i64, second as i64)
+    }
+
+    /// Exponentiation by squaring with wrapping multiplication.
+    pub fn wrapping_pow(self, mut power: u32) -> Quad<i64, D> {
+        let mut base = self;
+        let mut result = Quad(1, 0);
+        while power > 0 {
+            if power & 1 == 1 {
+                result = result.wrapping_mul(base);
+            }
+            power >>= 1;
+            if power > 0 {
+                base = base.wrapping_mul(base);
+            }
+        }
+        result
+    }
+
+    /// The wrapped sum, together with whether it overflowed.
+    pub fn overflowing_add(self, rhs: Quad<i64, D>) -> (Quad<i64, D>, bool) {
+        (self.wrapping_add(rhs), self.checked_add(rhs).is_none())
+    }
+
+    /// The wrapped difference, together with whether it overflowed.
+    pub fn overflowing_sub(self, rhs: Quad<i64, D>) -> (Quad<i64, D>, bool) {
+        (self.wrapping_sub(rhs), self.checked_sub(rhs).is_none())
+    }
+
+    /// The wrapped product, together with whether it overflowed.
+    pub fn overflowing_mul(self, rhs: Quad<i64, D>) -> (Quad<i64, D>, bool) {
+        (self.wrapping_mul(rhs), self.checked_mul(rhs).is_none())
+    }
+
+    /// The wrapped power, together with whether it overflowed.
+    pub fn overflowing_pow(self, power: u32) -> (Quad<i64, D>, bool) {
+        (self.wrapping_pow(power), self.checked_pow(power).is_none())
+    }
+}
+
+impl<const D: i64> Quad<Dyadic, D> {
+    /// The coefficients `a` and `b` of `a + b√D` as rationals.
+    pub fn to_ratios(self) -> (BigRational, BigRational) {
+        (self.0.into(), self.1.into())
+    }
+
+    /// `a + b√D`, if both coefficients are exactly dyadic.
+    pub fn try_from_ratios(
+        a: BigRational,
+        b: BigRational,
+    ) -> Result<Quad<Dyadic, D>, FromRatioError> {
+        Ok(Quad(a.try_into()?, b.try_into()?))
+    }
+
+    /// Coefficientwise addition that returns `None` on overflow.
+    pub fn checked_add(self, rhs: Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        Some(Quad(self.0.checked_add(rhs.0)?, self.1.checked_add(rhs.1)?))
+    }
+
+    /// Coefficientwise subtraction that returns `None` on overflow.
+    pub fn checked_sub(self, rhs: Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        Some(Quad(self.0.checked_sub(rhs.0)?, self.1.checked_sub(rhs.1)?))
+    }
+
+    /// Coefficientwise negation that returns `None` on overflow.
+    pub fn checked_neg(self) -> Option<Quad<Dyadic, D>> {
+        Some(Quad(self.0.checked_neg()?, self.1.checked_neg()?))
+    }
+
+    /// Multiplication that returns `None` if a coefficient of the product
+    /// overflows.
+    pub fn checked_mul(self, rhs: Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        self.checked_mul_terms(rhs).or_else(|| {
+            // a term overflowed, but the terms may still cancel
+            let ((a, b), (c, d)) = (self.to_ratios(), rhs.to_ratios());
+            let first = &a * &c + BigRational::from_integer(D.into()) * &b * &d;
+            Quad::try_from_ratios(first, a * d + b * c).ok()
+        })
+    }
+
+    fn checked_mul_terms(self, rhs: Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        let bd = self.1.checked_mul(rhs.1)?.checked_mul(Dyadic::new(D, 0))?;
+        let first = self.0.checked_mul(rhs.0)?.checked_add(bd)?;
+        let second = self
+            .0
+            .checked_mul(rhs.1)?
+            .checked_add(self.1.checked_mul(rhs.0)?)?;
+        Some(Quad(first, second))
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####         Traits for Quad         ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>, const D: i64> ops::Add for Quad<T, D> {
+    type Output = Quad<T, D>;
+    fn add(self, rhs: Quad<T, D>) -> Self::Output {
+        Quad(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>, const D: i64> ops::Sub for Quad<T, D> {
+    type Output = Quad<T, D>;
+    fn sub(self, rhs: Quad<T, D>) -> Self::Output {
+        Quad(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl<T: ops::Neg<Output = T>, const D: i64> ops::Neg for Quad<T, D> {
+    type Output = Quad<T, D>;
+    fn neg(self) -> Self::Output {
+        Quad(-self.0, -self.1)
+    }
+}
+
+impl<T: ops::AddAssign, const D: i64> ops::AddAssign for Quad<T, D> {
+    fn add_assign(&mut self, rhs: Quad<T, D>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl<T: ops::SubAssign, const D: i64> ops::SubAssign for Quad<T, D> {
+    fn sub_assign(&mut self, rhs: Quad<T, D>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl<T, const D: i64> ops::Mul for Quad<T, D>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Mul<i64, Output = T> + Clone,
+{
+    type Output = Quad<T, D>;
+    fn mul(self, rhs: Quad<T, D>) -> Self::Output {
+        let Quad(a, b) = self;
+        let Quad(c, d) = rhs;
+        Quad(
+            a.clone() * c.clone() + b.clone() * d.clone() * D,
+            a * d + b * c,
+        )
+    }
+}
+
+impl<T, const D: i64> ops::MulAssign for Quad<T, D>
+where
+    Quad<T, D>: ops::Mul<Output = Quad<T, D>> + Clone,
+{
+    fn mul_assign(&mut self, rhs: Quad<T, D>) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl<'a, T, const D: i64> ops::Add for &'a Quad<T, D>
+where
+    &'a T: ops::Add<Output = T>,
+{
+    type Output = Quad<T, D>;
+    fn add(self, rhs: &'a Quad<T, D>) -> Self::Output {
+        Quad(&self.0 + &rhs.0, &self.1 + &rhs.1)
+    }
+}
+
+impl<'a, T, const D: i64> ops::Sub for &'a Quad<T, D>
+where
+    &'a T: ops::Sub<Output = T>,
+{
+    type Output = Quad<T, D>;
+    fn sub(self, rhs: &'a Quad<T, D>) -> Self::Output {
+        Quad(&self.0 - &rhs.0, &self.1 - &rhs.1)
+    }
+}
+
+impl<'a, T, const D: i64> ops::Neg for &'a Quad<T, D>
+where
+    &'a T: ops::Neg<Output = T>,
+{
+    type Output = Quad<T, D>;
+    fn neg(self) -> Self::Output {
+        Quad(-&self.0, -&self.1)
+    }
+}
+
+impl<'a, T, const D: i64> ops::Mul for &'a Quad<T, D>
+where
+    &'a T: ops::Mul<Output = T>,
+    T: ops::Add<Output = T> + ops::Mul<i64, Output = T>,
+{
+    type Output = Quad<T, D>;
+    fn mul(self, rhs: &'a Quad<T, D>) -> Self::Output {
+        Quad(
+            &self.0 * &rhs.0 + &self.1 * &rhs.1 * D,
+            &self.0 * &rhs.1 + &self.1 * &rhs.0,
+        )
+    }
+}
+
+impl<T: ops::Add<Output = T>, const D: i64> ops::Add<T> for Quad<T, D> {
+    type Output = Quad<T, D>;
+    fn add(self, rhs: T) -> Self::Output {
+        Quad(self.0 + rhs, self.1)
+    }
+}
+
+impl<T: ops::Sub<Output = T>, const D: i64> ops::Sub<T> for Quad<T, D> {
+    type Output = Quad<T, D>;
+    fn sub(self, rhs: T) -> Self::Output {
+        Quad(self.0 - rhs, self.1)
+    }
+}
+
+impl<T: ops::Mul<Output = T> + Clone, const D: i64> ops::Mul<T> for Quad<T, D> {
+    type Output = Quad<T, D>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Quad(self.0 * rhs.clone(), self.1 * rhs)
+    }
+}
+
+impl<const D: i64> ops::Mul<i64> for Quad<Dyadic, D> {
+    type Output = Quad<Dyadic, D>;
+    fn mul(self, rhs: i64) -> Self::Output {
+        Quad(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl<const D: i64> ops::Add<Quad<i64, D>> for i64 {
+    type Output = Quad<i64, D>;
+    fn add(self, rhs: Quad<i64, D>) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl<const D: i64> ops::Sub<Quad<i64, D>> for i64 {
+    type Output = Quad<i64, D>;
+    fn sub(self, rhs: Quad<i64, D>) -> Self::Output {
+        -rhs + self
+    }
+}
+
+impl<const D: i64> ops::Mul<Quad<i64, D>> for i64 {
+    type Output = Quad<i64, D>;
+    fn mul(self, rhs: Quad<i64, D>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<const D: i64> ops::Add<Quad<Dyadic, D>> for Dyadic {
+    type Output = Quad<Dyadic, D>;
+    fn add(self, rhs: Quad<Dyadic, D>) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl<const D: i64> ops::Sub<Quad<Dyadic, D>> for Dyadic {
+    type Output = Quad<Dyadic, D>;
+    fn sub(self, rhs: Quad<Dyadic, D>) -> Self::Output {
+        -rhs + self
+    }
+}
+
+impl<const D: i64> ops::Mul<Quad<Dyadic, D>> for Dyadic {
+    type Output = Quad<Dyadic, D>;
+    fn mul(self, rhs: Quad<Dyadic, D>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl<const D: i64> ops::Mul<Quad<Dyadic, D>> for i64 {
+    type Output = Quad<Dyadic, D>;
+    fn mul(self, rhs: Quad<Dyadic, D>) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl ops::Div for RootTwo<i64> {
+    type Output = RootTwo<i64>;
+    /// The Euclidean quotient; see [`RootTwo::rounddiv`].
+    fn div(self, rhs: RootTwo<i64>) -> Self::Output {
+        self.rounddiv(rhs)
+    }
+}
+
+impl ops::Rem for RootTwo<i64> {
+    type Output = RootTwo<i64>;
+    fn rem(self, rhs: RootTwo<i64>) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+impl<T, const D: i64> pow::Pow<u32> for Quad<T, D>
+where
+    Quad<T, D>: One + Clone,
+{
+    type Output = Quad<T, D>;
+    /// Binary exponentiation, using `O(log power)` multiplications.
+    fn pow(self, power: u32) -> Self::Output {
+        pow::pow(self, power as usize)
+    }
+}
+
+impl<const D: i64> pow::Pow<i32> for Quad<i64, D> {
+    type Output = Option<Quad<i64, D>>;
+
+    /// Negative powers exist exactly when `self` is a unit, and are `None`
+    /// otherwise.
+    fn pow(self, power: i32) -> Self::Output {
+        if power >= 0 {
+            return Some(self.pow(power as u32));
+        }
+        Some(self.inv()?.pow(power.unsigned_abs()))
+    }
+}
+
+impl<const D: i64> Quad<Rational64, D> {
+    /// The multiplicative inverse `(a - b√D) / (a² - Db²)`. Since `D` is not
+    /// a square, the norm only vanishes at zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `self` is zero.
+    pub fn recip(self) -> Quad<Rational64, D> {
+        let norm = self.norm();
+        if norm.is_zero() {
+            panic!("attempted to take the reciprocal of zero");
+        }
+        Quad(self.0 / norm, -self.1 / norm)
+    }
+
+    /// The numerators `(a, b)` of `self` over the common denominator `c > 0`.
+    pub(crate) fn common_denom(self) -> (BigInt, BigInt, BigInt) {
+        let c = num::Integer::lcm(self.0.denom(), self.1.denom());
+        let scale = |q: Rational64| BigInt::from(*q.numer()) * (c / q.denom());
+        (scale(self.0), scale(self.1), BigInt::from(c))
+    }
+}
+
+impl<const D: i64> ops::Div for Quad<Rational64, D> {
+    type Output = Quad<Rational64, D>;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Quad<Rational64, D>) -> Self::Output {
+        self * rhs.recip()
+    }
+}
+
+impl<T: Zero, const D: i64> From<T> for Quad<T, D> {
+    /// Lifts a scalar `a` to `a + 0√D`.
+    fn from(val: T) -> Self {
+        Quad(val, T::zero())
+    }
+}
+
+impl<const D: i64> From<i64> for Quad<Dyadic, D> {
+    fn from(val: i64) -> Self {
+        Quad::from(Dyadic::from(val))
+    }
+}
+
+impl From<RootTwo<i64>> for QRootTwo {
+    fn from(val: RootTwo<i64>) -> Self {
+        Quad(Rational64::from(val.0), Rational64::from(val.1))
+    }
+}
+
+impl From<RootTwo<Dyadic>> for QRootTwo {
+    fn from(val: RootTwo<Dyadic>) -> Self {
+        Quad(val.0.into(), val.1.into())
+    }
+}
+
+impl<T: Zero, const D: i64> Zero for Quad<T, D> {
+    fn zero() -> Self {
+        Quad(T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero()
+    }
+}
+
+impl<T, const D: i64> One for Quad<T, D>
+where
+    T: Zero + One + PartialEq,
+    Quad<T, D>: ops::Mul<Output = Self>,
+{
+    fn one() -> Self {
+        Quad(T::one(), T::zero())
+    }
+
+    fn is_one(&self) -> bool {
+        self.0.is_one() && self.1.is_zero()
+    }
+}
+
+impl<T, const D: i64> iter::Sum for Quad<T, D>
+where
+    Quad<T, D>: Zero,
+{
+    fn sum<I: Iterator<Item = Quad<T, D>>>(iter: I) -> Self {
+        iter.fold(Quad::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, T: 'a, const D: i64> iter::Sum<&'a Quad<T, D>> for Quad<T, D>
+where
+    Quad<T, D>: Zero,
+    for<'b> &'b Quad<T, D>: ops::Add<Output = Quad<T, D>>,
+{
+    fn sum<I: Iterator<Item = &'a Quad<T, D>>>(iter: I) -> Self {
+        iter.fold(Quad::zero(), |acc, x| &acc + x)
+    }
+}
+
+impl<T, const D: i64> iter::Product for Quad<T, D>
+where
+    Quad<T, D>: One,
+{
+    fn product<I: Iterator<Item = Quad<T, D>>>(iter: I) -> Self {
+        iter.fold(Quad::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a, T: 'a, const D: i64> iter::Product<&'a Quad<T, D>> for Quad<T, D>
+where
+    Quad<T, D>: One,
+    for<'b> &'b Quad<T, D>: ops::Mul<Output = Quad<T, D>>,
+{
+    fn product<I: Iterator<Item = &'a Quad<T, D>>>(iter: I) -> Self {
+        iter.fold(Quad::one(), |acc, x| &acc * x)
+    }
+}
+
+/// Parses a sum of a rational term and a `√2` term in either order, such as
+/// `3 - 2√2`, `-√2` or `(3/2) - (1/4)*sqrt2`. The radical may be written
+/// `√2`, `sqrt2` or `sqrt(2)`, optionally after a `*`, and coefficients may
+/// be parenthesized.
+fn parse_roottwo<T>(
+    s: &str,
+    radix: u32,
+    coeff: impl Fn(&str, u32) -> Result<T, ParseRingError>,
+) -> Result<RootTwo<T>, ParseRingError>
+where
+    T: Zero + One + ops::Neg<Output = T>,
+{
+    let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    let s = s.replace("sqrt(2)", "√2").replace("sqrt2", "√2");
+    // split before each sign outside parentheses that starts a new term
+    let mut starts = vec![0];
+    let mut depth = 0;
+    let mut prev = None;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            '+' | '-' if i > 0 && depth == 0 && !matches!(prev, Some('*' | '/' | '^')) => {
+                starts.push(i)
+            }
+            _ => (),
+        }
+        prev = Some(c);
+    }
+    if starts.len() > 2 || depth != 0 {
+        return Err(ParseRingError::Malformed);
+    }
+    starts.push(s.len());
+    let (mut a, mut b) = (None, None);
+    for pair in starts.windows(2) {
+        let term = &s[pair[0]..pair[1]];
+        let (negative, term) = match term.strip_prefix('-') {
+            Some(term) => (true, term),
+            None => (false, term.strip_prefix('+').unwrap_or(term)),
+        };
+        let (radical, term) = match term.strip_suffix("√2") {
+            Some(term) => (true, term.strip_suffix('*').unwrap_or(term)),
+            None if term.is_empty() => return Err(ParseRingError::Malformed),
+            None => (false, term),
+        };
+        let term = term
+            .strip_prefix('(')
+            .and_then(|t| t.strip_suffix(')'))
+            .unwrap_or(term);
+        let value = match term {
+            "" if radical => T::one(),
+            _ => coeff(term, radix)?,
+        };
+        let value = if negative { -value } else { value };
+        let slot = if radical { &mut b } else { &mut a };
+        if slot.replace(value).is_some() {
+            return Err(ParseRingError::Malformed);
+        }
+    }
+    Ok(RootTwo::new(
+        a.unwrap_or_else(T::zero),
+        b.unwrap_or_else(T::zero),
+    ))
+}
+
+impl Num for RootTwo<i64> {
+    type FromStrRadixErr = ParseRingError;
+
+    /// Parses the forms accepted by `FromStr`, with integers written in the
+    /// given radix.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        parse_roottwo(s, radix, |c, radix| Ok(i64::from_str_radix(c, radix)?))
+    }
+}
+
+impl std::str::FromStr for RootTwo<i64> {
+    type Err = ParseRingError;
+
+    /// Parses sums like `3 - 2√2` or `(5) + sqrt2`, including everything
+    /// printed by `Display`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RootTwo::from_str_radix(s, 10)
+    }
+}
+
+impl std::str::FromStr for RootTwo<Dyadic> {
+    type Err = ParseRingError;
+
+    /// Parses sums like `3/2^4 + 2√2` or `(3/2) - (1/4)*sqrt2`, including
+    /// everything printed by `Display`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_roottwo(s, 10, Dyadic::from_str_radix)
+    }
+}
+
+impl<const D: i64> CheckedAdd for Quad<i64, D> {
+    fn checked_add(&self, rhs: &Quad<i64, D>) -> Option<Quad<i64, D>> {
+        Quad::<i64, D>::checked_add(*self, *rhs)
+    }
+}
+
+impl<const D: i64> CheckedSub for Quad<i64, D> {
+    fn checked_sub(&self, rhs: &Quad<i64, D>) -> Option<Quad<i64, D>> {
+        Quad::<i64, D>::checked_sub(*self, *rhs)
+    }
+}
+
+impl<const D: i64> CheckedMul for Quad<i64, D> {
+    fn checked_mul(&self, rhs: &Quad<i64, D>) -> Option<Quad<i64, D>> {
+        Quad::<i64, D>::checked_mul(*self, *rhs)
+    }
+}
+
+impl<const D: i64> CheckedAdd for Quad<Dyadic, D> {
+    fn checked_add(&self, rhs: &Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        Quad::<Dyadic, D>::checked_add(*self, *rhs)
+    }
+}
+
+impl<const D: i64> CheckedSub for Quad<Dyadic, D> {
+    fn checked_sub(&self, rhs: &Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        Quad::<Dyadic, D>::checked_sub(*self, *rhs)
+    }
+}
+
+impl<const D: i64> CheckedMul for Quad<Dyadic, D> {
+    fn checked_mul(&self, rhs: &Quad<Dyadic, D>) -> Option<Quad<Dyadic, D>> {
+        Quad::<Dyadic, D>::checked_mul(*self, *rhs)
+    }
+}
+
+impl Signed for RootTwo<i64> {
+    fn abs(&self) -> Self {
+        if self.is_negative() {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        let diff = *self - *other;
+        if diff.is_positive() {
+            diff
+        } else {
+            RootTwo::zero()
+        }
+    }
+
+    fn signum(&self) -> Self {
+        Quad(self.sign(), 0)
+    }
+
+    fn is_positive(&self) -> bool {
+        self.sign() > 0
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign() < 0
+    }
+}
+
+/// The sign of `a + b√2` for `|a|, |b| < 2⁶⁴`. When the signs of `a` and
+/// `b` differ, the larger of `a²` and `2b²` wins; these are compared as
+/// `⌊a²/2⌋ ≥ b²`, which fits in a `u128` and is exact because `a² ≠ 2b²`.
+fn sign_wide(a: i128, b: i128) -> Ordering {
+    if a.signum() * b.signum() >= 0 {
+        return (a.signum() + b.signum()).cmp(&0);
+    }
+    let (a2, b2) = (a.unsigned_abs().pow(2), b.unsigned_abs().pow(2));
+    if a2 / 2 >= b2 {
+        a.cmp(&0)
+    } else {
+        b.cmp(&0)
+    }
+}
+
+/// The sign of `a + b√2` for integers of any size.
+fn sign_big(a: BigInt, b: BigInt) -> Ordering {
+    let zero = BigInt::zero();
+    let (sa, sb) = (a.cmp(&zero), b.cmp(&zero));
+    if sa == sb || sb == Ordering::Equal {
+        return sa;
+    }
+    if sa == Ordering::Equal {
+        return sb;
+    }
+    if &a * &a > &b * &b * 2 {
+        sa
+    } else {
+        sb
+    }
+}
+
+/// `⌊(a + b√2)/2ᵏ⌋`. Since `2b²` is never a nonzero perfect square,
+/// `⌊b√2⌋` is `√(2b²)` rounded down for `b ≥ 0` and up otherwise.
+pub(crate) fn floor_big(a: BigInt, b: BigInt, k: u32) -> BigInt {
+    let root: BigInt = (&b * &b * 2u32).sqrt();
+    let whole = if b.is_negative() {
+        a - root - 1
+    } else {
+        a + root
+    };
+    whole >> k
+}
+
+fn to_i64(n: BigInt) -> i64 {
+    i64::try_from(n).expect("integer part overflows i64")
+}
+
+impl PartialOrd for RootTwo<i64> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RootTwo<i64> {
+    /// Compares the real values exactly, by the sign of the difference.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let a = self.0 as i128 - other.0 as i128;
+        let b = self.1 as i128 - other.1 as i128;
+        sign_wide(a, b)
+    }
+}
+
+impl PartialOrd for RootTwo<Dyadic> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RootTwo<Dyadic> {
+    /// Compares the real values exactly. All four coefficients are brought
+    /// over a common power of two, which can take more than 128 bits.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let coeffs = [self.0, self.1, other.0, other.1];
+        let k = coeffs.iter().map(|x| x.1).max().unwrap_or(0);
+        let [a, b, c, d] = coeffs.map(|x| BigInt::from(x.0) << (k - x.1));
+        sign_big(a - c, b - d)
+    }
+}
+
+impl PartialEq<i64> for RootTwo<i64> {
+    fn eq(&self, other: &i64) -> bool {
+        self.0 == *other && self.1 == 0
+    }
+}
+
+impl PartialEq<RootTwo<i64>> for i64 {
+    fn eq(&self, other: &RootTwo<i64>) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<i64> for RootTwo<i64> {
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        Some(sign_wide(self.0 as i128 - *other as i128, self.1 as i128))
+    }
+}
+
+impl PartialOrd<RootTwo<i64>> for i64 {
+    fn partial_cmp(&self, other: &RootTwo<i64>) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl PartialEq<i64> for RootTwo<Dyadic> {
+    fn eq(&self, other: &i64) -> bool {
+        self.0 == *other && self.1 == 0
+    }
+}
+
+impl PartialEq<RootTwo<Dyadic>> for i64 {
+    fn eq(&self, other: &RootTwo<Dyadic>) -> bool {
+        other == self
+    }
+}
+
+impl PartialOrd<i64> for RootTwo<Dyadic> {
+    fn partial_cmp(&self, other: &i64) -> Option<Ordering> {
+        Some(self.cmp(&RootTwo::from(*other)))
+    }
+}
+
+impl PartialOrd<RootTwo<Dyadic>> for i64 {
+    fn partial_cmp(&self, other: &RootTwo<Dyadic>) -> Option<Ordering> {
+        other.partial_cmp(self).map(Ordering::reverse)
+    }
+}
+
+impl RootTwo<i64> {
+    fn to_dyadic(self) -> RootTwo<Dyadic> {
+        Quad(Dyadic(self.0, 0), Dyadic(self.1, 0))
+    }
+
+    /// The greatest integer at most `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in an `i64`, and likewise for
+    /// [`ceil`](Self::ceil) and [`round`](Self::round).
+    pub fn floor(self) -> i64 {
+        self.to_dyadic().floor()
+    }
+
+    /// The least integer at least `self`.
+    pub fn ceil(self) -> i64 {
+        self.to_dyadic().ceil()
+    }
+
+    /// The nearest integer, which is never a tie unless `self` is an integer.
+    pub fn round(self) -> i64 {
+        self.to_dyadic().round()
+    }
+
+    /// Compares with a float exactly. See [`Dyadic::cmp_f64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is NaN.
+    pub fn cmp_f64(&self, x: f64) -> Ordering {
+        self.to_dyadic().cmp_f64(x)
+    }
+}
+
+impl RootTwo<Dyadic> {
+    /// The least `k` such that both coefficients are integers after
+    /// multiplying by `2ᵏ`.
+    pub fn common_exponent(&self) -> u32 {
+        self.0.denomexp().max(self.1.denomexp())
+    }
+
+    /// The unique representation `(a + b√2)/2ᵏ` with `k` as small as
+    /// possible, so that `a` and `b` are not both even unless `k` is zero.
+    /// Returns `None` if a numerator overflows an `i64`, which needs the two
+    /// coefficients to have very different exponents.
+    pub fn normalize(self) -> Option<(RootTwo<i64>, u32)> {
+        let k = self.common_exponent();
+        let scale = |x: Dyadic| {
+            let Dyadic(n, j) = Dyadic::simplify(x.0, x.1);
+            let s = k - j;
+            match n {
+                0 => Some(0),
+                _ => n.checked_shl(s).filter(|&m| m >> s == n),
+            }
+        };
+        Some((Quad(scale(self.0)?, scale(self.1)?), k))
+    }
+
+    /// Multiplication by `2ᵏ` for either sign of `k`, coefficient by
+    /// coefficient, or `None` on overflow. See [`Dyadic::mul_pow2`].
+    pub fn mul_pow2(self, k: i32) -> Option<RootTwo<Dyadic>> {
+        Some(Quad(self.0.mul_pow2(k)?, self.1.mul_pow2(k)?))
+    }
+
+    /// The inverse of [`normalize`](Self::normalize), `(a + b√2)/2ᵏ`.
+    pub fn from_normalized(x: RootTwo<i64>, k: u32) -> RootTwo<Dyadic> {
+        Quad(Dyadic::new(x.0, k), Dyadic::new(x.1, k))
+    }
+
+    /// The numerators `(a, b)` of `self` over a common denominator `2ᵏ`.
+    pub(crate) fn common_denom(self) -> (BigInt, BigInt, u32) {
+        let k = self.0 .1.max(self.1 .1);
+        let shift = |x: Dyadic| BigInt::from(x.0) << (k - x.1);
+        (shift(self.0), shift(self.1), k)
+    }
+
+    /// The sign of `a + b√2` as a real number. Only coefficients of opposite
+    /// signs need the exact comparison of `a²` with `2b²`.
+    pub fn sign(self) -> i64 {
+        let (sa, sb) = (self.0 .0.signum(), self.1 .0.signum());
+        if sa * sb >= 0 {
+            return (sa + sb).signum();
+        }
+        let (a, b, _) = self.common_denom();
+        sign_big(a, b) as i64
+    }
+
+    /// `-1`, `0` or `1` according to the sign of the real value.
+    pub fn signum(self) -> RootTwo<Dyadic> {
+        RootTwo::from(self.sign())
+    }
+
+    /// The absolute value as a real number, which is `self` or `-self`.
+    pub fn abs(self) -> RootTwo<Dyadic> {
+        if self.sign() < 0 {
+            -self
+        } else {
+            self
+        }
+    }
+
+    /// The greatest integer at most `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in an `i64`, and likewise for
+    /// [`ceil`](Self::ceil) and [`round`](Self::round).
+    pub fn floor(self) -> i64 {
+        let (a, b, k) = self.common_denom();
+        to_i64(floor_big(a, b, k))
+    }
+
+    /// The least integer at least `self`.
+    pub fn ceil(self) -> i64 {
+        let (a, b, k) = self.common_denom();
+        to_i64(-floor_big(-a, -b, k))
+    }
+
+    /// The nearest integer, rounding ties away from zero like [`f64::round`].
+    /// Ties only occur when the `√2` coefficient is zero.
+    pub fn round(self) -> i64 {
+        let (a, b, k) = self.common_denom();
+        let half = BigInt::one() << k;
+        // ⌊x + 1/2⌋ for x ≥ 0, and -⌊-x + 1/2⌋ otherwise
+        if sign_big(a.clone(), b.clone()) == Ordering::Less {
+            to_i64(-floor_big(half - 2 * a, -2 * b, k + 1))
+        } else {
+            to_i64(floor_big(2 * a + half, 2 * b, k + 1))
+        }
+    }
+
+    /// Splits `self` into its floor and the remainder, whose real value lies
+    /// in `[0, 1)`. Unlike [`WholePart`], which works coefficient by
+    /// coefficient, this keeps the fraction small as a real number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the floor does not fit in an `i64`.
+    pub fn split(self) -> (i64, RootTwo<Dyadic>) {
+        let n = self.floor();
+        (n, self - RootTwo::from(n))
+    }
+
+    /// The nearest `f64` in the given direction, so that rounding down and
+    /// up brackets `self` between adjacent floats.
+    pub fn to_f64_rounded(self, rounding: Rounding) -> f64 {
+        let (a, b, k) = self.common_denom();
+        round_to_f64_directed(a, b, &(BigInt::one() << k), rounding)
+    }
+
+    /// Compares with a float exactly. See [`Dyadic::cmp_f64`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is NaN.
+    pub fn cmp_f64(&self, x: f64) -> Ordering {
+        if let Some(ord) = cmp_nonfinite(x) {
+            return ord;
+        }
+        // a + b√2 - m·2ᵉ over the common denominator 2ᵏ
+        let (m, e) = float_parts(x);
+        let (a, b) = (self.0, self.1);
+        let k = (a.1 as i64).max(b.1 as i64).max(-e);
+        let shift = |n: i64, s: i64| BigInt::from(n) << s;
+        let lhs = shift(a.0, k - a.1 as i64) - shift(m, k + e);
+        sign_big(lhs, shift(b.0, k - b.1 as i64))
+    }
+}
+
+/// `x·2ᵉ`, in steps that keep every intermediate product exact until the
+/// last.
+pub(crate) fn scale_f64(mut x: f64, mut e: i64) -> f64 {
+    while e > 1000 && x.is_finite() {
+        x *= 2f64.powi(1000);
+        e -= 1000;
+    }
+    while e < -1000 && x != 0.0 {
+        x *= 2f64.powi(-1000);
+        e += 1000;
+    }
+    x * 2f64.powi(e as i32)
+}
+
+/// `(a + b√2)/c` for `c > 0` as `m·2ᵉ` with exactly `bits` bits in `m`, or
+/// `None` for zero. `m` is rounded to odd, i.e. truncated and then forced
+/// odd whenever anything was discarded, so rounding it again to at most
+/// `bits - 2` bits is correct.
+///
+/// Since `x = (a² - 2b²)/(c·(a - b√2))` is bounded below by the sizes of
+/// `a`, `b` and `c`, one shift `s` suffices for `⌊2ˢ|x|⌋` to have more than
+/// `bits` bits, and it is computed exactly with an integer square root.
+pub(crate) fn round_to_odd(a: BigInt, b: BigInt, c: &BigInt, bits: u64) -> Option<(BigInt, i64)> {
+    let sign = sign_big(a.clone(), b.clone());
+    let (a, b) = match sign {
+        Ordering::Equal => return None,
+        Ordering::Less => (-a, -b),
+        Ordering::Greater => (a, b),
+    };
+    let s = a.bits().max(b.bits()) + c.bits() + bits + 6;
+    let inexact = !b.is_zero();
+    let (m, r) = num::Integer::div_rem(&floor_big(a << s, b << s, 0), c);
+    let drop = m.bits() - bits;
+    let top = &m >> drop;
+    let sticky = inexact || !r.is_zero() || top.clone() << drop != m;
+    let top = top | BigInt::from(sticky as u8);
+    let top = if sign == Ordering::Less { -top } else { top };
+    Some((top, drop as i64 - s as i64))
+}
+
+/// `m·2ᵉ` for `m` that fits in an `i128`, rounded to the nearest `f64` with
+/// ties to even. This is correct when `m` is exact, or was rounded to odd
+/// with at least two bits more than the result keeps. Results in the
+/// subnormal range are rounded once, at the quantum `2⁻¹⁰⁷⁴`.
+pub(crate) fn round_scaled_f64(m: BigInt, e: i64) -> f64 {
+    let shift = -1074 - e;
+    if shift <= 0 {
+        return scale_f64(m.to_i128().unwrap() as f64, e);
+    }
+    let sign = if m.is_negative() { -1.0 } else { 1.0 };
+    if shift as u64 > m.bits() {
+        // below half the least subnormal
+        return sign * 0.0;
+    }
+    let m = m.abs();
+    let q = &m >> shift;
+    let r = &m - (&q << shift);
+    let half = BigInt::one() << (shift - 1);
+    let q = if r > half || (r == half && q.bit(0)) {
+        q + 1
+    } else {
+        q
+    };
+    sign * scale_f64(q.to_i128().unwrap() as f64, -1074)
+}
+
+/// `(a + b√2)/c` for `c > 0`, rounded to the nearest `f64` with ties to
+/// even, by way of 64 bits rounded to odd.
+pub(crate) fn round_to_f64(a: BigInt, b: BigInt, c: &BigInt) -> f64 {
+    match round_to_odd(a, b, c, 64) {
+        Some((m, e)) => round_scaled_f64(m, e),
+        None => 0.0,
+    }
+}
+
+/// `(a + b√2)/c` for `c > 0` as an `f64` rounded in the given direction.
+/// The directed roundings are the nearest bounds in their direction even
+/// in the subnormal range, since each step is checked exactly.
+pub(crate) fn round_to_f64_directed(a: BigInt, b: BigInt, c: &BigInt, rounding: Rounding) -> f64 {
+    let mut r = round_to_f64(a.clone(), b.clone(), c);
+    if r.is_infinite() {
+        // the value is beyond the greatest finite float, which bounds it
+        // towards zero
+        return match (rounding, r > 0.0) {
+            (Rounding::Down, true) => f64::MAX,
+            (Rounding::Up, false) => f64::MIN,
+            _ => r,
+        };
+    }
+    // the sign of (a + b√2)/c - r
+    let cmp = |r: f64| {
+        let (m, e) = float_parts(r);
+        let (a, b, mc) = if e < 0 {
+            (a.clone() << -e, b.clone() << -e, c * m)
+        } else {
+            (a.clone(), b.clone(), (c * m) << e)
+        };
+        sign_big(a - mc, b)
+    };
+    match rounding {
+        Rounding::Nearest => {}
+        Rounding::Down => {
+            while cmp(r) == Ordering::Less {
+                r = r.next_down();
+            }
+        }
+        Rounding::Up => {
+            while cmp(r) == Ordering::Greater {
+                r = r.next_up();
+            }
+        }
+    }
+    r
+}
+
+/// Correctly rounded, even when `a` and `b` nearly cancel, as they do for
+/// large powers of `λ⁻¹`.
+impl From<RootTwo<i64>> for f64 {
+    fn from(val: RootTwo<i64>) -> Self {
+        round_to_f64(val.0.into(), val.1.into(), &BigInt::one())
+    }
+}
+
+/// Correctly rounded, even when the coefficients nearly cancel.
+impl From<RootTwo<Dyadic>> for f64 {
+    fn from(val: RootTwo<Dyadic>) -> Self {
+        let (a, b, k) = val.common_denom();
+        round_to_f64(a, b, &(BigInt::one() << k))
+    }
+}
+
+/// Correctly rounded, even when the coefficients nearly cancel.
+impl From<QRootTwo> for f64 {
+    fn from(val: QRootTwo) -> Self {
+        let (a, b, c) = val.common_denom();
+        round_to_f64(a, b, &c)
+    }
+}
+
+impl<T: ToPrimitive> From<RootMinusTwo<T>> for Complex<f64> {
+    fn from(val: RootMinusTwo<T>) -> Self {
+        let (a, b) = (val.0.to_f64(), val.1.to_f64());
+        Complex::new(a.unwrap_or(f64::NAN), b.unwrap_or(f64::NAN) * SQRT_2)
+    }
+}
+
+/// Prints `Quad(a, b)` like a derived implementation. The alternate form
+/// `{:#?}` keeps to one line and adds the value of `a + b√D`, as in
+/// `Quad(3, 4) ≈ 8.657` for `{:#.3?}`, which is complex for negative `D`.
+impl<T: fmt::Debug + ToPrimitive, const D: i64> fmt::Debug for Quad<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Quad({:?}, {:?})", self.0, self.1)?;
+        if !f.alternate() {
+            return Ok(());
+        }
+        let a = self.0.to_f64().unwrap_or(f64::NAN);
+        let b = self.1.to_f64().unwrap_or(f64::NAN) * (D.unsigned_abs() as f64).sqrt();
+        write!(f, " ≈ ")?;
+        if D > 0 {
+            return write_approx(f, a + b);
+        }
+        write_approx(f, a)?;
+        write!(f, " {} ", if b < 0.0 { '-' } else { '+' })?;
+        write_approx(f, b.abs())?;
+        write!(f, "i")
+    }
+}
+
+/// Prints `a + b√D` with the sign of `b` pulled out, as in `3 - 2√2`,
+/// leaving out zero terms and a coefficient of `1`. Coefficients that print
+/// as fractions are parenthesized, and the alternate form `{:#}` is passed
+/// on to them.
+impl<T: fmt::Display + Zero, const D: i64> fmt::Display for Quad<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let alternate = f.alternate();
+        let show = |x: &T| {
+            if alternate {
+                format!("{x:#}")
+            } else {
+                x.to_string()
+            }
+        };
+        if self.1.is_zero() {
+            return write!(f, "{}", show(&self.0));
+        }
+        let b = show(&self.1);
+        let (sign, b) = match b.strip_prefix('-') {
+            Some(b) => ("-", b),
+            None => ("+", &b[..]),
+        };
+        let b = match b {
+            "1" => String::new(),
+            b if b.contains(['/', ' ']) => format!("({b})"),
+            b => b.to_string(),
+        };
+        match (self.0.is_zero(), sign) {
+            (true, "+") => write!(f, "{b}√{D}"),
+            (true, _) => write!(f, "-{b}√{D}"),
+            (false, _) => write!(f, "{} {sign} {b}√{D}", show(&self.0)),
+        }
+    }
+}
+
+impl<T: ToLatex + Zero, const D: i64> ToLatex for Quad<T, D> {
+    /// `a + b\sqrt{D}` with the same sign handling and omitted terms as
+    /// `Display`.
+    fn to_latex(&self) -> String {
+        if self.1.is_zero() {
+            return self.0.to_latex();
+        }
+        let b = self.1.to_latex();
+        let (sign, b) = match b.strip_prefix('-') {
+            Some(b) => ("-", b),
+            None => ("+", &b[..]),
+        };
+        let b = if b == "1" { "" } else { b };
+        match (self.0.is_zero(), sign) {
+            (true, "+") => format!("{b}\\sqrt{{{D}}}"),
+            (true, _) => format!("-{b}\\sqrt{{{D}}}"),
+            (false, _) => format!("{} {sign} {b}\\sqrt{{{D}}}", self.0.to_latex()),
+        }
+    }
+}
+
+impl<T: Adj + ops::Neg<Output = T>, const D: i64> Adj for Quad<T, D> {
+    /// For negative `D` the radical `√D` is imaginary and gets negated.
+    fn adj(self) -> Self {
+        if D < 0 {
+            Quad(self.0.adj(), -self.1.adj())
+        } else {
+            Quad(self.0.adj(), self.1.adj())
+        }
+    }
+}
+
+impl<T: ops::Neg<Output = T>> Adj2 for RootTwo<T> {
+    fn adj2(self) -> Self {
+        self.conj()
+    }
+}
+
+impl<T: WholePart, const D: i64> WholePart for Quad<T, D> {
+    type Whole = Quad<T::Whole, D>;
+
+    fn from_whole(whole: Self::Whole) -> Self {
+        Quad(T::from_whole(whole.0), T::from_whole(whole.1))
+    }
+
+    fn whole_part(self) -> Self::Whole {
+        Quad(self.0.whole_part(), self.1.whole_part())
+    }
+
+    fn frac_part(self) -> Option<Self> {
+        Some(Quad(self.0.frac_part()?, self.1.frac_part()?))
+    }
+}
+
+impl DenomExp for RootTwo<Dyadic> {
+    /// Multiplying by `√2²ᵐ` scales both coefficients by `2ᵐ`, while
+    /// `√2²ᵐ⁺¹·(a + b√2) == 2ᵐ⁺¹b + 2ᵐa√2`.
+    fn denomexp(&self) -> u32 {
+        let (ka, kb) = (self.0.denomexp(), self.1.denomexp());
+        if ka >= kb {
+            2 * ka
+        } else {
+            2 * kb - 1
+        }
+    }
+}
+
+impl<T: Parity, const D: i64> Parity for Quad<T, D> {
+    type Residue = Quad<T::Residue, D>;
+    fn residue(&self) -> Self::Residue {
+        Quad(self.0.residue(), self.1.residue())
+    }
+}
+
+#[cfg(test)]
+mod roottwo_tests {
+    use super::*;
+    use crate::Dyadic;
+    use num::pow::Pow;
+    #[test]
+    fn basic_add_zroottwo() {
+        let first = RootTwo::new(1, 2);
+        let second = RootTwo::new(3, 4);
+        assert_eq!(first + second, RootTwo::new(4, 6));
+        assert_eq!(first - second, RootTwo::new(-2, -2));
+    }
+
+    #[test]
+    fn basic_add_droottwo() {
+        let first = RootTwo::new(Dyadic(3, 2), Dyadic(3, 7));
+        let second = RootTwo::new(Dyadic(4, 2), Dyadic(3, 8));
+        assert_eq!(first + second, RootTwo::new(Dyadic(7, 2), Dyadic(9, 8)));
+        assert_eq!(first - second, RootTwo::new(Dyadic(-1, 2), Dyadic(3, 8)));
+    }
+
+    #[test]
+    fn mul_two_root_twos() {
+        let first = RootTwo::new(3, 4);
+        let second = RootTwo::new(5, 6);
+        let expected = RootTwo::new(63, 38);
+        assert_eq!(first * second, expected);
+        assert_eq!(second * first, expected);
+    }
+
+    #[test]
+    fn whole_part_droottwo() {
+        let x = RootTwo::new(Dyadic(7, 2), Dyadic(-1, 1));
+        assert_eq!(x.whole_part(), RootTwo::new(1, -1));
+        assert_eq!(
+            x.frac_part(),
+            Some(RootTwo::new(Dyadic(3, 2), Dyadic(1, 1)))
+        );
+        assert_eq!(
+            RootTwo::<Dyadic>::from_whole(x.whole_part()) + x.frac_part().unwrap(),
+            x
+        );
+        assert_eq!(RootTwo::new(Dyadic(1, 1), Dyadic(-1, 70)).frac_part(), None);
+    }
+
+    #[test]
+    fn denomexp_droottwo() {
+        let zero = Dyadic(0, 0);
+        assert_eq!(RootTwo::new(Dyadic(3, 0), Dyadic(1, 0)).denomexp(), 0);
+        // 1/2 requires √2²
+        assert_eq!(RootTwo::new(Dyadic(1, 1), zero).denomexp(), 2);
+        // 1/√2 == √2/2 requires only √2
+        assert_eq!(RootTwo::new(zero, Dyadic(1, 1)).denomexp(), 1);
+        assert_eq!(RootTwo::new(Dyadic(1, 2), Dyadic(3, 3)).denomexp(), 5);
+    }
+
+    #[test]
+    fn residues_zroottwo() {
+        let x = RootTwo::new(3, -4);
+        assert_eq!(x.residue(), RootTwo::new(1, 0));
+        assert_eq!(RootTwo::new(-1, 5).residue(), RootTwo::new(1, 1));
+        assert_eq!(x.residue_roottwo(), 1);
+        // √2 divides an element exactly when its residue vanishes
+        assert_eq!((x * RootTwo::new(0, 1)).residue_roottwo(), 0);
+    }
+
+    #[test]
+    fn euclidean_division_zroottwo() {
+        let pairs = [
+            (RootTwo::new(63, 38), RootTwo::new(3, 4)),
+            (RootTwo::new(17, -5), RootTwo::new(2, 3)),
+            (RootTwo::new(-100, 71), RootTwo::new(-7, 1)),
+            (RootTwo::new(1, 0), RootTwo::new(0, 1)),
+        ];
+        for (x, y) in pairs {
+            let (q, r) = x.div_rem(y);
+            assert_eq!(q * y + r, x);
+            assert!(r.rank() < y.rank());
+            assert_eq!(x % y, r);
+        }
+        assert_eq!(
+            RootTwo::new(63, 38).rounddiv(RootTwo::new(3, 4)),
+            RootTwo::new(5, 6)
+        );
+    }
+
+    #[test]
+    fn rounddiv_extreme_coefficients() {
+        let min = RootTwo::new(0, i64::MIN);
+        assert_eq!(min.rounddiv(min), RootTwo::new(1, 0));
+        assert_eq!(
+            RootTwo::new(i64::MAX, i64::MIN).rounddiv(min),
+            RootTwo::new(1, 0)
+        );
+        assert_eq!(RootTwo::new(3, 0).rounddiv(min), RootTwo::zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rounddiv_by_zero() {
+        RootTwo::new(1, 1).rounddiv(RootTwo::new(0, 0));
+    }
+
+    #[test]
+    fn zero_and_one() {
+        let x = RootTwo::new(Dyadic(3, 2), Dyadic(-1, 1));
+        assert_eq!(x * RootTwo::one() + RootTwo::zero(), x);
+        assert!(RootTwo::<i64>::one().is_one());
+        assert!(!RootTwo::new(1, 1).is_one());
+        assert!((x - x).is_zero());
+    }
+
+    #[test]
+    fn from_str_radix_zroottwo() {
+        let parse = |s| RootTwo::from_str_radix(s, 10);
+        assert_eq!(parse("3-2√2"), Ok(RootTwo::new(3, -2)));
+        assert_eq!(parse("  -5 "), Ok(RootTwo::new(-5, 0)));
+        assert_eq!(parse("√2"), Ok(RootTwo::new(0, 1)));
+        assert_eq!(parse("-√2 + 1"), Ok(RootTwo::new(1, -1)));
+        assert_eq!(
+            RootTwo::from_str_radix("a+b√2", 16),
+            Ok(RootTwo::new(10, 11))
+        );
+        assert_eq!(parse("1+"), Err(ParseRingError::Malformed));
+        assert_eq!(parse("1+2"), Err(ParseRingError::Malformed));
+        assert!(parse("1+x√2").is_err());
+        assert_eq!(parse("2*sqrt2 - 7"), Ok(RootTwo::new(-7, 2)));
+        assert_eq!(parse("-(3)sqrt(2)"), Ok(RootTwo::new(0, -3)));
+        assert_eq!(parse("1+2-√2"), Err(ParseRingError::Malformed));
+        assert_eq!(parse("(1+√2"), Err(ParseRingError::Malformed));
+    }
+
+    #[test]
+    fn debug_with_values() {
+        assert_eq!(format!("{:?}", RootTwo::new(3, 4)), "Quad(3, 4)");
+        assert_eq!(format!("{:#.3?}", RootTwo::new(3, 4)), "Quad(3, 4) ≈ 8.657");
+        let x = RootTwo::new(Dyadic(1, 1), Dyadic(-1, 0));
+        assert_eq!(
+            format!("{:#.4?}", x),
+            "Quad(Dyadic(1, 1), Dyadic(-1, 0)) ≈ -0.9142"
+        );
+        assert_eq!(
+            format!("{:#.2?}", RootMinusTwo::new(1, -1)),
+            "Quad(1, -1) ≈ 1.00 - 1.41i"
+        );
+    }
+
+    #[test]
+    fn latex_roottwo() {
+        assert_eq!(RootTwo::new(1, -2).to_latex(), "1 - 2\\sqrt{2}");
+        assert_eq!(RootTwo::new(0, 1).to_latex(), "\\sqrt{2}");
+        assert_eq!(RootTwo::new(0, -1).to_latex(), "-\\sqrt{2}");
+        assert_eq!(RootTwo::new(4, 0).to_latex(), "4");
+        let x = RootTwo::new(Dyadic(3, 1), Dyadic(-1, 2));
+        assert_eq!(x.to_latex(), "\\frac{3}{2^{1}} - \\frac{1}{2^{2}}\\sqrt{2}");
+        let q = QRootTwo::new(Rational64::new(-1, 3), Rational64::new(2, 5));
+        assert_eq!(q.to_latex(), "-\\frac{1}{3} + \\frac{2}{5}\\sqrt{2}");
+        let min = Rational64::new(i64::MIN, 3);
+        assert_eq!(min.to_latex(), "-\\frac{9223372036854775808}{3}");
+        assert_eq!(RootMinusTwo::new(1, 1).to_latex(), "1 + \\sqrt{-2}");
+    }
+
+    #[test]
+    fn parse_droottwo() {
+        let parse = |s: &str| s.parse::<RootTwo<Dyadic>>();
+        assert_eq!(
+            parse("(3/2) - (1/4)*sqrt2"),
+            Ok(RootTwo::new(Dyadic(3, 1), Dyadic(-1, 2)))
+        );
+        assert_eq!(
+            parse("1 + 2√2"),
+            Ok(RootTwo::new(Dyadic(1, 0), Dyadic(2, 0)))
+        );
+        assert_eq!(parse("-5/8"), Ok(RootTwo::new(Dyadic(-5, 3), Dyadic(0, 0))));
+        assert_eq!(
+            parse("3/2^4√2"),
+            Ok(RootTwo::new(Dyadic(0, 0), Dyadic(3, 4)))
+        );
+        assert_eq!(parse("1/3 + √2"), Err(ParseRingError::NotDyadic));
+        assert_eq!(parse(""), Err(ParseRingError::Malformed));
+        for x in [
+            RootTwo::new(Dyadic(3, 1), Dyadic(-1, 2)),
+            RootTwo::new(Dyadic(0, 0), Dyadic(-7, 5)),
+            RootTwo::new(Dyadic(9, 70), Dyadic(1, 0)),
+        ] {
+            assert_eq!(parse(&x.to_string()), Ok(x));
+            assert_eq!(parse(&format!("{x:#}")), Ok(x));
+        }
+        assert_eq!("3 - 2√2".parse(), Ok(RootTwo::new(3, -2)));
+    }
+
+    #[test]
+    fn display_roottwo() {
+        assert_eq!(RootTwo::new(3, -2).to_string(), "3 - 2√2");
+        assert_eq!(RootTwo::new(-3, 2).to_string(), "-3 + 2√2");
+        assert_eq!(RootTwo::new(0, -1).to_string(), "-√2");
+        assert_eq!(RootTwo::new(1, 1).to_string(), "1 + √2");
+        assert_eq!(RootTwo::new(-4, 0).to_string(), "-4");
+        assert_eq!(RootTwo::<i64>::zero().to_string(), "0");
+        assert_eq!(RootMinusTwo::new(0, 5).to_string(), "5√-2");
+        let x = RootTwo::new(Dyadic(3, 1), Dyadic(-1, 2));
+        assert_eq!(x.to_string(), "3/2^1 - (1/2^2)√2");
+        assert_eq!(format!("{x:#}"), "3/2 - (1/4)√2");
+        for x in [RootTwo::new(3, -2), RootTwo::new(0, 1), RootTwo::new(-7, 0)] {
+            assert_eq!(RootTwo::from_str_radix(&x.to_string(), 10), Ok(x));
+        }
+    }
+
+    #[test]
+    fn exact_sign_zroottwo() {
+        // 99² - 2·70² == 1, so this is a tiny positive number
+        let tiny = RootTwo::new(99, -70);
+        assert!(tiny.is_positive());
+        assert_eq!((-tiny).signum(), RootTwo::new(-1, 0));
+        assert_eq!((-tiny).abs(), tiny);
+        assert_eq!(RootTwo::new(-99, 70).sign(), -1);
+        assert_eq!(RootTwo::new(0, -3).sign(), -1);
+        assert_eq!(RootTwo::new(5, 0).sign(), 1);
+        assert_eq!(RootTwo::<i64>::zero().sign(), 0);
+        assert_eq!(
+            RootTwo::new(1, 0).abs_sub(&RootTwo::new(0, 1)),
+            RootTwo::zero()
+        );
+        assert_eq!(
+            RootTwo::new(0, 1).abs_sub(&RootTwo::new(1, 0)),
+            RootTwo::new(-1, 1)
+        );
+    }
+
+    #[test]
+    fn compound_assignment() {
+        let x = RootTwo::new(Dyadic(1, 1), Dyadic(3, 2));
+        let mut acc = RootTwo::zero();
+        acc += x;
+        acc += x;
+        assert_eq!(acc, RootTwo::new(Dyadic(1, 0), Dyadic(3, 1)));
+        acc -= x;
+        assert_eq!(acc, x);
+        acc *= x;
+        assert_eq!(acc, x * x);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn reference_operands() {
+        let x = RootTwo::new(Dyadic(1, 1), Dyadic(3, 2));
+        let y = RootTwo::new(Dyadic(-5, 0), Dyadic(1, 3));
+        assert_eq!(&x + &y, x + y);
+        assert_eq!(&x - &y, x - y);
+        assert_eq!(&x * &y, x * y);
+        assert_eq!(-&x, -x);
+        let q = QRootTwo::new(Rational64::new(1, 3), Rational64::new(-2, 5));
+        assert_eq!(&q * &q, q * q);
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let xs = [RootTwo::new(1, 1), RootTwo::new(2, -1), RootTwo::new(0, 3)];
+        assert_eq!(xs.iter().sum::<RootTwo<i64>>(), RootTwo::new(3, 3));
+        assert_eq!(xs.into_iter().sum::<RootTwo<i64>>(), RootTwo::new(3, 3));
+        let product = xs[0] * xs[1] * xs[2];
+        assert_eq!(xs.iter().product::<RootTwo<i64>>(), product);
+        assert_eq!(xs.into_iter().product::<RootTwo<i64>>(), product);
+        assert_eq!(
+            iter::empty::<RootTwo<i64>>().product::<RootTwo<i64>>(),
+            RootTwo::one()
+        );
+    }
+
+    #[test]
+    fn mixed_scalar_arithmetic() {
+        let x = RootTwo::new(Dyadic(1, 1), Dyadic(3, 2));
+        assert_eq!(x + Dyadic(1, 1), RootTwo::new(Dyadic(1, 0), Dyadic(3, 2)));
+        assert_eq!(Dyadic(1, 1) - x, RootTwo::new(Dyadic(0, 0), Dyadic(-3, 2)));
+        assert_eq!(x * 4, RootTwo::new(Dyadic(2, 0), Dyadic(3, 0)));
+        assert_eq!(2 * x, x * Dyadic(2, 0));
+        let z = RootTwo::new(1, -2);
+        assert_eq!(z * 3, RootTwo::new(3, -6));
+        assert_eq!(3 * z, z * 3);
+        assert_eq!(5 - z, RootTwo::new(4, 2));
+        assert_eq!(z + 1, RootTwo::new(2, -2));
+    }
+
+    #[test]
+    fn lift_scalars() {
+        assert_eq!(RootTwo::from(3), RootTwo::new(3, 0));
+        assert_eq!(
+            RootTwo::from(Dyadic(1, 1)),
+            RootTwo::new(Dyadic(1, 1), Dyadic::zero())
+        );
+        let lifted: RootTwo<Dyadic> = (-2).into();
+        assert_eq!(lifted, RootTwo::new(Dyadic(-2, 0), Dyadic::zero()));
+    }
+
+    #[test]
+    fn strict_mul_cancels_partial_products() {
+        // a·c and 2·b·d both overflow i64, but they cancel exactly
+        let x = RootTwo::new(4_000_000_000, 2_828_427_124);
+        let y = RootTwo::new(4_000_000_000, -2_828_427_124);
+        assert_eq!(x.strict_mul(y), RootTwo::new(8_442_177_248, 0));
+        assert_eq!(
+            RootTwo::new(7, -3).strict_mul(RootTwo::new(-2, 5)),
+            RootTwo::new(7, -3) * RootTwo::new(-2, 5)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow")]
+    fn strict_mul_panics_on_true_overflow() {
+        let x = RootTwo::new(1 << 40, 1 << 40);
+        x.strict_mul(x);
+    }
+
+    #[test]
+    fn checked_arithmetic_zroottwo() {
+        let x = RootTwo::new(3, -2);
+        assert_eq!(x.checked_add(RootTwo::new(1, 1)), Some(RootTwo::new(4, -1)));
+        assert_eq!(x.checked_sub(RootTwo::new(i64::MIN, 0)), None);
+        assert_eq!(x.checked_mul(x), Some(x * x));
+        // λ⁵⁰ ≈ 6.9·10¹⁸ is the largest power of λ = 1 + √2 that fits
+        let lambda = RootTwo::new(1, 1);
+        assert!(lambda.checked_pow(50).is_some());
+        assert_eq!(lambda.checked_pow(51), None);
+        assert_eq!(lambda.checked_pow(0), Some(RootTwo::one()));
+        assert_eq!(
+            lambda.checked_pow(5),
+            Some(lambda * lambda * lambda * lambda * lambda)
+        );
+        assert_eq!(
+            CheckedAdd::checked_add(&RootTwo::new(i64::MAX, 0), &x),
+            None
+        );
+    }
+
+    #[test]
+    fn big_rational_coefficients() {
+        let x = RootTwo::new(Dyadic(-3, 2), Dyadic(5, 0));
+        let (a, b) = x.to_ratios();
+        assert_eq!(a, BigRational::new((-3).into(), 4.into()));
+        assert_eq!(b, BigRational::from_integer(5.into()));
+        assert_eq!(RootTwo::try_from_ratios(a, b), Ok(x));
+        assert_eq!(
+            RootTwo::try_from_ratios(BigRational::new(1.into(), 3.into()), BigRational::zero()),
+            Err(FromRatioError::NotDyadic)
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic_droottwo() {
+        let x = RootTwo::new(Dyadic(3, 1), Dyadic(-1, 2));
+        let y = RootTwo::new(Dyadic(1, 0), Dyadic(5, 3));
+        assert_eq!(x.checked_add(y), Some(x + y));
+        assert_eq!(x.checked_sub(y), Some(x - y));
+        assert_eq!(x.checked_mul(y), Some(x * y));
+        assert_eq!(x.checked_neg(), Some(-x));
+        let big = RootTwo::new(Dyadic(0, 0), Dyadic(1 << 31, 0));
+        assert_eq!(big.checked_mul(big), None);
+        // 2⁶³ - 2⁶³ cancels even though neither term fits
+        let x = RootTwo::new(Dyadic(1 << 32, 0), Dyadic(1 << 31, 0));
+        let y = RootTwo::new(Dyadic(1 << 31, 0), Dyadic(-1 << 31, 0));
+        assert_eq!(
+            x.checked_mul(y),
+            Some(RootTwo::new(Dyadic(0, 0), Dyadic(-1 << 62, 0)))
+        );
+        assert_eq!(
+            RootTwo::new(Dyadic(i64::MIN, 0), Dyadic(0, 0)).checked_neg(),
+            None
+        );
+    }
+
+    #[test]
+    fn wrapping_and_overflowing_zroottwo() {
+        let x = RootTwo::new(i64::MAX, 1);
+        assert_eq!(
+            x.wrapping_add(RootTwo::new(1, 1)),
+            RootTwo::new(i64::MIN, 2)
+        );
+        assert_eq!(
+            x.overflowing_sub(RootTwo::new(1, 0)),
+            (RootTwo::new(i64::MAX - 1, 1), false)
+        );
+        let big = RootTwo::new(1 << 40, 3);
+        let (wrapped, overflowed) = big.overflowing_mul(big);
+        assert!(overflowed);
+        assert_eq!(wrapped, RootTwo::new(18, 6 << 40));
+        let lambda = RootTwo::new(1, 1);
+        assert_eq!(
+            lambda.overflowing_pow(50),
+            (lambda.checked_pow(50).unwrap(), false)
+        );
+        assert!(lambda.overflowing_pow(51).1);
+    }
+
+    #[test]
+    fn pow_by_squaring() {
+        let lambda = RootTwo::new(1, 1);
+        let mut expected = lambda;
+        for power in 1..=40u32 {
+            assert_eq!(lambda.pow(power), expected);
+            expected *= lambda;
+        }
+        let half_lambda = RootTwo::new(Dyadic(1, 1), Dyadic(1, 1));
+        let x = half_lambda.pow(20);
+        let lambda20 = lambda.pow(20u32);
+        assert_eq!(
+            x,
+            RootTwo::new(
+                Dyadic::simplify(lambda20.0, 20),
+                Dyadic::simplify(lambda20.1, 20)
+            )
+        );
+        // a large exponent in ℤ[√2] whose value stays small
+        let unit = RootTwo::new(-1, 0);
+        assert_eq!(unit.pow(1001u32), unit);
+    }
+
+    #[test]
+    fn unit_powers() {
+        let lambda = RootTwo::new(1, 1);
+        assert_eq!(lambda.pow(0u32), RootTwo::one());
+        assert_eq!(RootTwo::<i64>::zero().pow(0u32), RootTwo::one());
+        assert_eq!(lambda.pow(-1i32), Some(RootTwo::new(-1, 1)));
+        assert_eq!(
+            lambda.pow(-5i32).unwrap() * lambda.pow(5u32),
+            RootTwo::one()
+        );
+        assert_eq!(lambda.pow(-4i32), Some(RootTwo::new(17, -12)));
+        // 3 + 2√2 = λ² has norm 1
+        let lambda2 = RootTwo::new(3, 2);
+        assert_eq!(lambda2.pow(-1i32), Some(lambda2.conj()));
+        let x = RootTwo::new(2, 1);
+        assert_eq!(x.pow(-1i32), None);
+        assert_eq!(x.pow(2i32), Some(RootTwo::new(6, 4)));
+    }
+
+    #[test]
+    fn units_zroottwo() {
+        let lambda = RootTwo::new(1, 1);
+        assert!(lambda.is_unit());
+        assert_eq!(lambda.inv(), Some(RootTwo::new(-1, 1)));
+        assert_eq!(RootTwo::new(17, 12).inv(), Some(RootTwo::new(17, -12)));
+        assert_eq!(RootTwo::new(-1, 0).inv(), Some(RootTwo::new(-1, 0)));
+        assert!(!RootTwo::new(2, 1).is_unit());
+        assert_eq!(RootTwo::<i64>::zero().inv(), None);
+        assert_eq!(RootTwo::new(0, 1).inv(), None);
+        // the only units of ℤ[√-2] are ±1
+        assert!(RootMinusTwo::new(-1, 0).is_unit());
+        assert!(!RootMinusTwo::new(1, 1).is_unit());
+        let min = RootTwo::new(0, i64::MIN);
+        assert!(!min.is_unit());
+        assert_eq!(min.inv(), None);
+        assert_eq!(Quad::<i64, 5>::new(i64::MIN, i64::MIN).inv(), None);
+    }
+
+    #[test]
+    fn unit_decomposition_zroottwo() {
+        let lambda = RootTwo::lambda();
+        assert_eq!(lambda, RootTwo::new(1, 1));
+        assert_eq!(
+            RootTwo::<Dyadic>::lambda(),
+            RootTwo::new(Dyadic::one(), Dyadic::one())
+        );
+        for k in -50..=50i32 {
+            let u = lambda.pow(k).unwrap();
+            assert_eq!(u.unit_decomposition(), Some((1, k)));
+            assert_eq!((-u).unit_decomposition(), Some((-1, k)));
+        }
+        assert_eq!(RootTwo::new(1, 0).unit_decomposition(), Some((1, 0)));
+        assert_eq!(RootTwo::new(2, 1).unit_decomposition(), None);
+        assert_eq!(RootTwo::zero().unit_decomposition(), None);
+    }
+
+    #[test]
+    fn norm_and_trace() {
+        let x = RootTwo::new(3, -5);
+        assert_eq!(x.norm(), -41);
+        assert_eq!(x.trace(), 6);
+        assert_eq!(x * x.adj2(), RootTwo::from(x.norm()));
+        assert_eq!(x + x.adj2(), RootTwo::from(x.trace()));
+        let y = RootTwo::new(Dyadic(3, 1), Dyadic(-1, 2));
+        assert_eq!(y.norm(), Dyadic(17, 3));
+        assert_eq!(y.trace(), Dyadic(3, 0));
+        assert_eq!(y * y.adj2(), RootTwo::from(y.norm()));
+    }
+
+    #[test]
+    fn square_roots_zroottwo() {
+        for x in [
+            RootTwo::new(0, 0),
+            RootTwo::new(1, 0),
+            RootTwo::new(3, 2),
+            RootTwo::new(-5, 7),
+            RootTwo::new(0, 1),
+            RootTwo::new(12, -1),
+            RootTwo::new(1 << 20, 3),
+        ] {
+            let root = (x * x).sqrt().unwrap();
+            assert!(root == x || root == -x);
+            assert!(root.sign() >= 0);
+        }
+        assert_eq!(RootTwo::new(2, 0).sqrt(), Some(RootTwo::new(0, 1)));
+        assert_eq!(RootTwo::new(3, 0).sqrt(), None);
+        assert_eq!(RootTwo::new(-1, 0).sqrt(), None);
+        assert_eq!(RootTwo::new(1, 1).sqrt(), None);
+        // λ² is a square but λ is not, and λ⁻¹ = -1 + √2 is negative
+        assert!(RootTwo::new(3, 2).is_square());
+        assert!(!RootTwo::new(-1, 1).is_square());
+        assert_eq!(RootTwo::new(0, i64::MIN).sqrt(), None);
+        assert_eq!(RootTwo::new(i64::MIN, i64::MIN).sqrt(), None);
+    }
+
+    #[test]
+    fn exact_division_zroottwo() {
+        let x = RootTwo::new(3, -1);
+        let y = RootTwo::new(5, 7);
+        assert_eq!((x * y).try_div(y), Some(x));
+        assert_eq!((x * y).try_div(x), Some(y));
+        // 2 = (√2)², but √2 does not divide 1 + √2
+        assert_eq!(
+            RootTwo::new(2, 0).try_div(RootTwo::new(0, 1)),
+            Some(RootTwo::new(0, 1))
+        );
+        assert_eq!(RootTwo::new(1, 1).try_div(RootTwo::new(0, 1)), None);
+        assert_eq!(x.try_div(RootTwo::zero()), None);
+        let lambda = RootTwo::new(1, 1);
+        assert_eq!(RootTwo::one().try_div(lambda), Some(RootTwo::new(-1, 1)));
+        let z = RootMinusTwo::new(1, 1);
+        assert_eq!((z * z).try_div(z), Some(z));
+        let min = RootTwo::new(0, i64::MIN);
+        assert_eq!(min.try_div(min), Some(RootTwo::one()));
+        assert_eq!(RootTwo::new(1, 0).try_div(min), None);
+    }
+
+    #[test]
+    fn mul_other_radicands() {
+        let first: Quad<i64, 3> = Quad::new(1, 2);
+        let second = Quad::new(3, 4);
+        assert_eq!(first * second, Quad::new(27, 10));
+
+        // (1 + √-1)(1 - √-1) == 2
+        let gaussian: Quad<i64, -1> = Quad::new(1, 1);
+        assert_eq!(gaussian * gaussian.conj(), Quad::new(2, 0));
+    }
+
+    #[test]
+    fn root_minus_two() {
+        let first = RootMinusTwo::new(3, 4);
+        let second = RootMinusTwo::new(5, 6);
+        assert_eq!(first * second, RootMinusTwo::new(-33, 38));
+        assert_eq!(first.norm(), 41);
+        assert_eq!((first * second).norm(), first.norm() * second.norm());
+        assert_eq!(first * first.conj(), RootMinusTwo::new(41, 0));
+
+        let z: Complex<f64> = RootMinusTwo::new(Dyadic(1, 1), Dyadic(1, 0)).into();
+        assert_eq!(z, Complex::new(0.5, SQRT_2));
+    }
+
+    #[test]
+    fn qroottwo_division() {
+        let one = QRootTwo::from(RootTwo::new(1, 0));
+        let x = QRootTwo::from(RootTwo::new(3, 4));
+        let y = QRootTwo::from(RootTwo::new(Dyadic(-1, 1), Dyadic(5, 3)));
+        assert_eq!(x * x.recip(), one);
+        assert_eq!(x / y * y, x);
+        assert_eq!(
+            x.recip(),
+            RootTwo::new(Rational64::new(-3, 23), Rational64::new(4, 23))
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn qroottwo_recip_zero() {
+        QRootTwo::from(RootTwo::new(0, 0)).recip();
+    }
+
+    #[test]
+    fn order_zroottwo() {
+        let mut xs = vec![
+            RootTwo::new(1, 0),
+            RootTwo::new(3, -2),
+            RootTwo::new(0, 1),
+            RootTwo::new(-1, 1),
+            RootTwo::new(0, -1),
+            RootTwo::new(0, 0),
+        ];
+        xs.sort();
+        assert_eq!(
+            xs,
+            [(0, -1), (0, 0), (3, -2), (-1, 1), (1, 0), (0, 1)].map(|(a, b)| RootTwo::new(a, b))
+        );
+        // 665857² - 2·470832² = 1, so the two sides differ by about 10⁻⁶
+        assert!(RootTwo::new(665_857, 0) > RootTwo::new(0, 470_832));
+        assert!(RootTwo::new(-665_857, 0) < RootTwo::new(0, -470_832));
+        let (big, small) = (
+            RootTwo::new(i64::MIN, i64::MAX),
+            RootTwo::new(i64::MAX, i64::MIN),
+        );
+        assert!(small < big);
+        assert_eq!(big.max(small), big);
+        let map: std::collections::BTreeMap<_, _> = xs.iter().map(|&x| (x, f64::from(x))).collect();
+        let values: Vec<f64> = map.values().copied().collect();
+        assert!(values.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn order_droottwo() {
+        let tiny = RootTwo::new(Dyadic(0, 0), Dyadic(1, 100));
+        assert!(tiny > RootTwo::zero());
+        // 2⁻²⁰⁰ - 2⁻³⁰⁰√2 is positive, far beyond f64 resolution of either
+        let x = RootTwo::new(Dyadic(1, 200), Dyadic(-1, 300));
+        let y = RootTwo::new(Dyadic(1, 200), Dyadic(0, 0));
+        assert!(RootTwo::zero() < x && x < y);
+        let half = RootTwo::new(Dyadic(99, 1), Dyadic(0, 0));
+        assert!(half > RootTwo::new(Dyadic(0, 0), Dyadic(35, 0)));
+        assert!(half < RootTwo::new(Dyadic(0, 0), Dyadic(71, 1)));
+        assert_eq!(x.cmp(&x), Ordering::Equal);
+    }
+
+    #[test]
+    fn compare_roottwo_with_integers() {
+        assert_eq!(RootTwo::new(3, 0), 3);
+        assert_ne!(RootTwo::new(3, 1), 3);
+        assert!(RootTwo::new(3, -2) < 1 && RootTwo::new(3, -2) > 0);
+        assert!(2 > RootTwo::new(0, 1));
+        let x = RootTwo::new(Dyadic(4, 1), Dyadic(0, 3));
+        assert_eq!(x, 2);
+        assert!(RootTwo::new(Dyadic(0, 0), Dyadic(1, 1)) < 1);
+        assert!(-1 < RootTwo::new(Dyadic(0, 0), Dyadic(-1, 1)));
+    }
+
+    #[test]
+    fn compare_roottwo_with_floats() {
+        // the float nearest √2 is slightly larger
+        let root = RootTwo::new(0, 1);
+        assert_eq!(root.cmp_f64(SQRT_2), Ordering::Less);
+        assert_eq!(root.cmp_f64(SQRT_2.next_down()), Ordering::Greater);
+        assert_eq!(RootTwo::new(-3, 0).cmp_f64(-3.0), Ordering::Equal);
+        assert_eq!(
+            RootTwo::new(i64::MAX, i64::MAX).cmp_f64(1e300),
+            Ordering::Less
+        );
+        let half_root = RootTwo::new(Dyadic(0, 0), Dyadic(1, 1));
+        assert_eq!(half_root.cmp_f64(SQRT_2 / 2.0), Ordering::Less);
+        let x = RootTwo::new(Dyadic(1, 1100), Dyadic(0, 0));
+        assert_eq!(x.cmp_f64(f64::from_bits(1)), Ordering::Less);
+        assert_eq!(x.cmp_f64(0.0), Ordering::Greater);
+        assert_eq!(x.cmp_f64(f64::INFINITY), Ordering::Less);
+    }
+
+    #[test]
+    fn floor_ceil_round_zroottwo() {
+        // 5√2 ≈ 7.07 and -5√2 + 1 ≈ -6.07
+        assert_eq!(RootTwo::new(0, 5).floor(), 7);
+        assert_eq!(RootTwo::new(0, 5).ceil(), 8);
+        assert_eq!(RootTwo::new(0, 5).round(), 7);
+        assert_eq!(RootTwo::new(1, -5).floor(), -7);
+        assert_eq!(RootTwo::new(1, -5).ceil(), -6);
+        assert_eq!(RootTwo::new(1, -5).round(), -6);
+        assert_eq!(RootTwo::new(-4, 0).floor(), -4);
+        assert_eq!(RootTwo::new(-4, 0).ceil(), -4);
+        // 470832√2 ≈ 665857 - 7.5·10⁻⁷
+        assert_eq!(RootTwo::new(0, 470_832).floor(), 665_856);
+        assert_eq!(RootTwo::new(0, -470_832).ceil(), -665_856);
+        assert_eq!(RootTwo::new(0, 470_832).round(), 665_857);
+        let big = RootTwo::new(i64::MAX, i64::MIN / 2);
+        assert_eq!(big.floor(), big.ceil() - 1);
+        for (a, b) in [(3, 7), (-11, 4), (100, -71), (0, 1)] {
+            let x = RootTwo::new(a, b);
+            let value = f64::from(x);
+            assert_eq!(x.floor(), value.floor() as i64);
+            assert_eq!(x.ceil(), value.ceil() as i64);
+            assert_eq!(x.round(), value.round() as i64);
+        }
+    }
+
+    #[test]
+    fn floor_ceil_round_droottwo() {
+        let half = RootTwo::new(Dyadic(1, 1), Dyadic(0, 0));
+        assert_eq!((half.floor(), half.ceil(), half.round()), (0, 1, 1));
+        let minus_half = -half;
+        assert_eq!(
+            (minus_half.floor(), minus_half.ceil(), minus_half.round()),
+            (-1, 0, -1)
+        );
+        // √2/2 - 1/4 ≈ 0.457 and -√2/2^40 just below zero
+        let x = RootTwo::new(Dyadic(-1, 2), Dyadic(1, 1));
+        assert_eq!((x.floor(), x.ceil(), x.round()), (0, 1, 0));
+        let tiny = RootTwo::new(Dyadic(0, 0), Dyadic(-1, 40));
+        assert_eq!((tiny.floor(), tiny.ceil(), tiny.round()), (-1, 0, 0));
+        let y = RootTwo::new(Dyadic(7, 0), Dyadic(3, 3));
+        assert_eq!((y.floor(), y.ceil(), y.round()), (7, 8, 8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn floor_overflow() {
+        RootTwo::new(i64::MAX, i64::MAX).floor();
+    }
+
+    #[test]
+    fn split_droottwo() {
+        for x in [
+            RootTwo::new(Dyadic(7, 2), Dyadic(-1, 1)),
+            RootTwo::new(Dyadic(-3, 0), Dyadic(5, 3)),
+            RootTwo::new(Dyadic(0, 0), Dyadic(-1, 40)),
+            RootTwo::new(Dyadic(9, 0), Dyadic(0, 0)),
+        ] {
+            let (n, frac) = x.split();
+            assert_eq!(RootTwo::<Dyadic>::from(n) + frac, x);
+            assert!(frac >= RootTwo::zero() && frac < 1);
+        }
+        // 7/4 - √2/2 ≈ 1.04, while the coefficientwise parts are 1 - √2 and 3/4 + √2/2
+        let x = RootTwo::new(Dyadic(7, 2), Dyadic(-1, 1));
+        assert_eq!(x.split(), (1, RootTwo::new(Dyadic(3, 2), Dyadic(-1, 1))));
+    }
+
+    #[test]
+    fn signum_and_abs() {
+        // 3 - 2√2 ≈ 0.17 and its negative, then a pair near the limits
+        let x = RootTwo::new(3, -2);
+        assert_eq!((x.signum(), x.abs()), (RootTwo::new(1, 0), x));
+        assert_eq!(((-x).signum(), (-x).abs()), (RootTwo::new(-1, 0), x));
+        let y = RootTwo::new(i64::MIN + 1, 6_521_908_912_666_391_106);
+        assert_eq!(y.sign(), 1);
+        assert_eq!(
+            RootTwo::new(i64::MAX, -6_521_908_912_666_391_106).sign(),
+            -1
+        );
+        assert_eq!(RootTwo::<i64>::zero().signum(), RootTwo::zero());
+
+        let d = RootTwo::new(Dyadic(3, 10), Dyadic(-1, 9));
+        assert_eq!(d.sign(), 1);
+        let e = RootTwo::new(Dyadic(-99, 1), Dyadic(35, 0));
+        assert_eq!((e.sign(), e.signum()), (-1, RootTwo::from(-1)));
+        assert_eq!(e.abs(), -e);
+        assert_eq!(RootTwo::new(Dyadic(1, 200), Dyadic(-1, 300)).sign(), 1);
+        assert_eq!(RootTwo::<Dyadic>::zero().sign(), 0);
+        assert_eq!(RootTwo::new(Dyadic(0, 0), Dyadic(-5, 3)).abs().sign(), 1);
+    }
+
+    #[test]
+    fn normalize_droottwo() {
+        let x = RootTwo::new(Dyadic(3, 2), Dyadic(-1, 5));
+        assert_eq!(x.common_exponent(), 5);
+        assert_eq!(x.normalize(), Some((RootTwo::new(24, -1), 5)));
+        // the same value written non-canonically normalizes identically
+        let y = RootTwo::new(Dyadic(48, 6), Dyadic(-4, 7));
+        assert_eq!(y.normalize(), x.normalize());
+        assert_eq!(RootTwo::from_normalized(RootTwo::new(24, -1), 5), x);
+        let integral = RootTwo::new(Dyadic(6, 1), Dyadic(-2, 0));
+        assert_eq!(integral.normalize(), Some((RootTwo::new(3, -2), 0)));
+        assert_eq!(
+            RootTwo::<Dyadic>::zero().normalize(),
+            Some((RootTwo::zero(), 0))
+        );
+        let z = RootTwo::new(Dyadic(1, 0), Dyadic(1, 62));
+        assert_eq!(z.normalize(), Some((RootTwo::new(1 << 62, 1), 62)));
+        let w = RootTwo::new(Dyadic(0, 0), Dyadic(-1, 70));
+        assert_eq!(w.normalize(), Some((RootTwo::new(0, -1), 70)));
+        let wide = RootTwo::new(Dyadic(1, 0), Dyadic(1, 63));
+        assert_eq!(wide.normalize(), None);
+        let wide = RootTwo::new(Dyadic(-1, 0), Dyadic(1, 63));
+        assert_eq!(wide.normalize(), Some((RootTwo::new(i64::MIN, 1), 63)));
+        let mut seen = std::collections::HashSet::new();
+        assert!(seen.insert(x));
+        assert!(!seen.insert(y));
+    }
+
+    #[test]
+    fn mul_pow2_droottwo() {
+        let x = RootTwo::new(Dyadic(3, 2), Dyadic(-1, 5));
+        assert_eq!(
+            x.mul_pow2(5),
+            Some(RootTwo::new(Dyadic(24, 0), Dyadic(-1, 0)))
+        );
+        assert_eq!(
+            x.mul_pow2(-1),
+            Some(RootTwo::new(Dyadic(3, 3), Dyadic(-1, 6)))
+        );
+        assert_eq!(x.mul_pow2(3).and_then(|y| y.mul_pow2(-3)), Some(x));
+        assert_eq!(x.mul_pow2(2), Some(x * 4));
+        assert_eq!(x.mul_pow2(70), None);
+    }
+
+    /// Whether `x` is at least as close to `f64::from(x)` as to either of
+    /// its neighbours, by comparing it exactly with both midpoints.
+    fn correctly_rounded(x: RootTwo<Dyadic>) -> bool {
+        let y = f64::from(x);
+        let exact = |z: f64| <Dyadic>::try_from(z).unwrap();
+        [y.to_bits() - 1, y.to_bits() + 1].into_iter().all(|bits| {
+            let mid = exact(y) + (exact(f64::from_bits(bits)) - exact(y)) * Dyadic(1, 1);
+            let mid = RootTwo::new(mid, Dyadic::zero());
+            (x <= mid) == (exact(y) < mid.0)
+        })
+    }
+
+    #[test]
+    fn directed_rounding_beyond_f64() {
+        let one = BigInt::one();
+        let huge = BigInt::one() << 1100;
+        let round =
+            |a: &BigInt, rounding| round_to_f64_directed(a.clone(), BigInt::zero(), &one, rounding);
+        assert_eq!(round(&huge, Rounding::Nearest), f64::INFINITY);
+        assert_eq!(round(&huge, Rounding::Down), f64::MAX);
+        assert_eq!(round(&huge, Rounding::Up), f64::INFINITY);
+        assert_eq!(round(&-&huge, Rounding::Down), f64::NEG_INFINITY);
+        assert_eq!(round(&-&huge, Rounding::Up), f64::MIN);
+        let root = round_to_f64_directed(BigInt::zero(), huge, &one, Rounding::Down);
+        assert_eq!(root, f64::MAX);
+    }
+
+    #[test]
+    fn correctly_rounded_conversion() {
+        assert_eq!(f64::from(RootTwo::new(3, 0)), 3.0);
+        assert_eq!(f64::from(RootTwo::new(0, 1)), SQRT_2);
+        assert_eq!(
+            f64::from(RootTwo::new(Dyadic(-3, 4), Dyadic(0, 0))),
+            -0.1875
+        );
+        assert_eq!(f64::from(RootTwo::<i64>::zero()), 0.0);
+        // λ⁻⁴⁰ = a - b√2 with a, b near 10¹⁵, whose naive sum is pure noise
+        let tiny = RootTwo::<i64>::lambda().pow(-40i32).unwrap();
+        let value = 1.0 / f64::from(RootTwo::<i64>::lambda().pow(40i32).unwrap());
+        assert!((f64::from(tiny) - value).abs() < 1e-15 * value);
+        assert!((tiny.0 as f64 + tiny.1 as f64 * SQRT_2 - value).abs() > value);
+        for k in -49..=49i32 {
+            let unit = RootTwo::<i64>::lambda().pow(k).unwrap();
+            let unit = RootTwo::from_normalized(unit, 0);
+            assert!(correctly_rounded(unit));
+            assert!(correctly_rounded(-unit.mul_pow2(-k.abs()).unwrap()));
+        }
+        let x = RootTwo::new(Dyadic(-4_478_554_083, 3), Dyadic(3_166_815_962, 3));
+        assert!(correctly_rounded(x));
+        assert!(f64::from(x) < 0.0);
+        let q = QRootTwo::new(Rational64::new(-1, 3), Rational64::new(2, 7));
+        assert!((f64::from(q) - (-1.0 / 3.0 + 2.0 * SQRT_2 / 7.0)).abs() < 1e-16);
+    }
+}
@@ -0,0 +1,212 @@
+use std::ops;
+
+use num::{One, Zero};
+
+/// A quaternion `a + bi + cj + dk` whose components live in any of the
+/// crate's commutative rings, e.g. `Quaternion<RootTwo<Dyadic>>`. Unit
+/// quaternions over such rings describe exact rotations, with `q` and `-q`
+/// giving the same element of SO(3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct Quaternion<T>(pub(crate) T, pub(crate) T, pub(crate) T, pub(crate) T);
+
+impl<T> Quaternion<T> {
+    pub fn new(a: T, b: T, c: T, d: T) -> Quaternion<T> {
+        Quaternion(a, b, c, d)
+    }
+
+    /// The real part `a`.
+    pub fn scalar(self) -> T {
+        self.0
+    }
+
+    /// The imaginary part `(b, c, d)`.
+    pub fn vector(self) -> [T; 3] {
+        [self.1, self.2, self.3]
+    }
+
+    /// The quaternion conjugate. `conj(a + bi + cj + dk) == a - bi - cj - dk`
+    pub fn conj(self) -> Quaternion<T>
+    where
+        T: ops::Neg<Output = T>,
+    {
+        Quaternion(self.0, -self.1, -self.2, -self.3)
+    }
+
+    /// The reduced norm `a² + b² + c² + d²`, which equals `q·conj(q)` and is
+    /// multiplicative.
+    pub fn norm(self) -> T
+    where
+        T: ops::Mul<Output = T> + ops::Add<Output = T> + Copy,
+    {
+        self.0 * self.0 + self.1 * self.1 + self.2 * self.2 + self.3 * self.3
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####      Traits for Quaternion      ####
+// #######                           #######
+// #########################################
+
+impl<T: ops::Add<Output = T>> ops::Add for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn add(self, rhs: Quaternion<T>) -> Self::Output {
+        Quaternion(
+            self.0 + rhs.0,
+            self.1 + rhs.1,
+            self.2 + rhs.2,
+            self.3 + rhs.3,
+        )
+    }
+}
+
+impl<T: ops::Sub<Output = T>> ops::Sub for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn sub(self, rhs: Quaternion<T>) -> Self::Output {
+        Quaternion(
+            self.0 - rhs.0,
+            self.1 - rhs.1,
+            self.2 - rhs.2,
+            self.3 - rhs.3,
+        )
+    }
+}
+
+impl<T: ops::Neg<Output = T>> ops::Neg for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn neg(self) -> Self::Output {
+        Quaternion(-self.0, -self.1, -self.2, -self.3)
+    }
+}
+
+impl<T: ops::AddAssign> ops::AddAssign for Quaternion<T> {
+    fn add_assign(&mut self, rhs: Quaternion<T>) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+        self.2 += rhs.2;
+        self.3 += rhs.3;
+    }
+}
+
+impl<T: ops::SubAssign> ops::SubAssign for Quaternion<T> {
+    fn sub_assign(&mut self, rhs: Quaternion<T>) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+        self.2 -= rhs.2;
+        self.3 -= rhs.3;
+    }
+}
+
+impl<T> ops::Mul for Quaternion<T>
+where
+    T: ops::Mul<Output = T> + ops::Add<Output = T> + ops::Sub<Output = T> + Copy,
+{
+    type Output = Quaternion<T>;
+    /// The Hamilton product, following `i² = j² = k² = ijk = -1`.
+    fn mul(self, rhs: Quaternion<T>) -> Self::Output {
+        let Quaternion(a1, b1, c1, d1) = self;
+        let Quaternion(a2, b2, c2, d2) = rhs;
+        Quaternion(
+            a1 * a2 - b1 * b2 - c1 * c2 - d1 * d2,
+            a1 * b2 + b1 * a2 + c1 * d2 - d1 * c2,
+            a1 * c2 - b1 * d2 + c1 * a2 + d1 * b2,
+            a1 * d2 + b1 * c2 - c1 * b2 + d1 * a2,
+        )
+    }
+}
+
+impl<T> ops::MulAssign for Quaternion<T>
+where
+    Quaternion<T>: ops::Mul<Output = Quaternion<T>> + Copy,
+{
+    fn mul_assign(&mut self, rhs: Quaternion<T>) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: ops::Mul<Output = T> + Copy> ops::Mul<T> for Quaternion<T> {
+    type Output = Quaternion<T>;
+    fn mul(self, rhs: T) -> Self::Output {
+        Quaternion(self.0 * rhs, self.1 * rhs, self.2 * rhs, self.3 * rhs)
+    }
+}
+
+impl<T: Zero> Zero for Quaternion<T> {
+    fn zero() -> Self {
+        Quaternion(T::zero(), T::zero(), T::zero(), T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0.is_zero() && self.1.is_zero() && self.2.is_zero() && self.3.is_zero()
+    }
+}
+
+impl<T> One for Quaternion<T>
+where
+    T: Zero + One + PartialEq,
+    Quaternion<T>: ops::Mul<Output = Self>,
+{
+    fn one() -> Self {
+        Quaternion(T::one(), T::zero(), T::zero(), T::zero())
+    }
+
+    fn is_one(&self) -> bool {
+        self.0.is_one() && self.1.is_zero() && self.2.is_zero() && self.3.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod quaternion_tests {
+    use super::*;
+    use crate::{Dyadic, RootTwo};
+
+    #[test]
+    fn hamilton_relations() {
+        let one = Quaternion::<i64>::one();
+        let i = Quaternion(0, 1, 0, 0);
+        let j = Quaternion(0, 0, 1, 0);
+        let k = Quaternion(0, 0, 0, 1);
+        for x in [i, j, k, i * j * k] {
+            assert_eq!(x * x.conj(), one);
+        }
+        assert_eq!(i * i, -one);
+        assert_eq!(i * j * k, -one);
+        assert_eq!(i * j, k);
+        assert_eq!(j * i, -k);
+        assert_eq!(j * k, i);
+        assert_eq!(k * i, j);
+    }
+
+    #[test]
+    fn norm_is_multiplicative() {
+        let x = Quaternion(1, -2, 3, 4);
+        let y = Quaternion(-5, 0, 7, 2);
+        assert_eq!(x.norm(), 30);
+        assert_eq!((x * y).norm(), x.norm() * y.norm());
+        assert_eq!((x * y).conj(), y.conj() * x.conj());
+        assert_eq!(x * x.conj(), Quaternion(x.norm(), 0, 0, 0));
+    }
+
+    #[test]
+    fn dyadic_roottwo_components() {
+        // (1 + i)/√2 is the unit quaternion of a quarter turn about the x axis
+        let h = Dyadic(1, 1);
+        let s = RootTwo::new(Dyadic::zero(), h);
+        let zero = RootTwo::zero();
+        let q = Quaternion(s, s, zero, zero);
+        assert_eq!(q.norm(), RootTwo::one());
+        let square = q * q;
+        assert_eq!(square, Quaternion(zero, RootTwo::one(), zero, zero));
+        let d = Quaternion(Dyadic(1, 1), Dyadic(-3, 2), Dyadic(5, 0), Dyadic(0, 0));
+        let mut e = d;
+        e *= Quaternion(Dyadic(1, 0), Dyadic(1, 0), Dyadic(1, 1), Dyadic(-1, 3));
+        assert_eq!(e.norm(), d.norm() * Dyadic(145, 6));
+        assert_eq!(q.scalar(), s);
+        assert_eq!(d.vector(), [Dyadic(-3, 2), Dyadic(5, 0), Dyadic(0, 0)]);
+    }
+}
@@ -0,0 +1,253 @@
+//! Distributions over ring elements for the `rand` feature, for randomized
+//! tests and Monte Carlo experiments.
+
+use std::ops::RangeInclusive;
+
+use num::{pow::Pow, Integer};
+use rand::{
+    distr::{Distribution, Uniform},
+    Rng,
+};
+
+use crate::{Dyadic, Quad, RootTwo};
+
+/// The largest `|k|` for which `λᵏ` fits in `RootTwo<i64>`.
+const MAX_LAMBDA_POWER: i32 = 50;
+
+/// Uniform on the dyadics `n/2ᵏ` in `[0, 1)` for a fixed `k`. Samples are
+/// reduced, so their exponent is at most `k`.
+#[derive(Debug, Clone, Copy)]
+pub struct UniformDyadic {
+    k: u32,
+}
+
+impl UniformDyadic {
+    /// # Panics
+    ///
+    /// Panics if `k > 63`, where the numerators no longer fit in an `i64`.
+    pub fn new(k: u32) -> UniformDyadic {
+        assert!(k <= 63, "exponent {k} is too large for i64 numerators");
+        UniformDyadic { k }
+    }
+}
+
+impl Distribution<Dyadic> for UniformDyadic {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Dyadic {
+        let n = rng.random::<u64>().checked_shr(64 - self.k).unwrap_or(0);
+        Dyadic::new(n as i64, self.k)
+    }
+}
+
+/// `a + b√2` with `a` and `b` independent and uniform in their ranges.
+#[derive(Debug, Clone, Copy)]
+pub struct UniformRootTwo {
+    a: Uniform<i64>,
+    b: Uniform<i64>,
+}
+
+impl UniformRootTwo {
+    /// # Panics
+    ///
+    /// Panics if either range is empty.
+    pub fn new(a: RangeInclusive<i64>, b: RangeInclusive<i64>) -> UniformRootTwo {
+        UniformRootTwo {
+            a: Uniform::try_from(a).expect("empty range for a"),
+            b: Uniform::try_from(b).expect("empty range for b"),
+        }
+    }
+}
+
+impl Distribution<RootTwo<i64>> for UniformRootTwo {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RootTwo<i64> {
+        Quad(self.a.sample(rng), self.b.sample(rng))
+    }
+}
+
+/// The units `±λᵏ` of ℤ[√2], with the sign and `k` uniform.
+#[derive(Debug, Clone, Copy)]
+pub struct UnitRootTwo {
+    k: Uniform<i32>,
+}
+
+impl UnitRootTwo {
+    /// Units with `|k| ≤ max_power`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_power` is negative or over 50, where `λᵏ` overflows.
+    pub fn new(max_power: i32) -> UnitRootTwo {
+        assert!(
+            (0..=MAX_LAMBDA_POWER).contains(&max_power),
+            "λ^{max_power} does not fit in i64 coefficients"
+        );
+        UnitRootTwo {
+            k: Uniform::new_inclusive(-max_power, max_power).unwrap(),
+        }
+    }
+}
+
+impl Distribution<RootTwo<i64>> for UnitRootTwo {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RootTwo<i64> {
+        let unit = RootTwo::<i64>::lambda().pow(self.k.sample(rng)).unwrap();
+        if rng.random() {
+            unit
+        } else {
+            -unit
+        }
+    }
+}
+
+/// Nonzero elements of ℤ[√2] with `|a² - 2b²| ≤ n`.
+///
+/// Multiplying by a unit leaves the norm alone, so there are infinitely
+/// many such elements. Samples are drawn from the balanced associates, those
+/// with `x` and `x•` of similar size, which fill the box `|a| ≤ √(λn)`,
+/// `|b| ≤ √(λn/2)`.
+#[derive(Debug, Clone, Copy)]
+pub struct NormBoundedRootTwo {
+    n: i64,
+    a: Uniform<i64>,
+    b: Uniform<i64>,
+}
+
+impl NormBoundedRootTwo {
+    /// # Panics
+    ///
+    /// Panics unless `1 ≤ n ≤ 2⁶⁰`.
+    pub fn new(n: i64) -> NormBoundedRootTwo {
+        assert!((1..=1 << 60).contains(&n), "norm bound {n} out of range");
+        // a little over λn, so rounding never shrinks the box
+        let lambda_n = (2.5 * n as f64) as i64;
+        let a = lambda_n.isqrt();
+        let b = (lambda_n / 2).isqrt();
+        NormBoundedRootTwo {
+            n,
+            a: Uniform::new_inclusive(-a, a).unwrap(),
+            b: Uniform::new_inclusive(-b, b).unwrap(),
+        }
+    }
+}
+
+impl Distribution<RootTwo<i64>> for NormBoundedRootTwo {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RootTwo<i64> {
+        loop {
+            let x = Quad(self.a.sample(rng), self.b.sample(rng));
+            let norm = x.norm().abs();
+            if norm != 0 && norm <= self.n {
+                return x;
+            }
+        }
+    }
+}
+
+/// Elements of D[√2] with [`denomexp`](crate::DenomExp::denomexp) exactly
+/// `k`, namely `(c + d√2)/√2ᵏ` with `c` and `d` uniform in `[-bound, bound]`
+/// and `c` odd when `k > 0`.
+#[derive(Debug, Clone, Copy)]
+pub struct DenomExpRootTwo {
+    k: u32,
+    coeff: Uniform<i64>,
+}
+
+impl DenomExpRootTwo {
+    /// # Panics
+    ///
+    /// Panics if `bound` is negative, or zero when `k > 0`, leaving no odd
+    /// `c`.
+    pub fn new(k: u32, bound: i64) -> DenomExpRootTwo {
+        assert!(
+            bound > 0 || (bound == 0 && k == 0),
+            "no numerators in [-{bound}, {bound}]"
+        );
+        DenomExpRootTwo {
+            k,
+            coeff: Uniform::new_inclusive(-bound, bound).unwrap(),
+        }
+    }
+}
+
+impl Distribution<RootTwo<Dyadic>> for DenomExpRootTwo {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> RootTwo<Dyadic> {
+        let c = loop {
+            let c = self.coeff.sample(rng);
+            if self.k == 0 || c.is_odd() {
+                break c;
+            }
+        };
+        let d = self.coeff.sample(rng);
+        let m = self.k / 2;
+        if self.k.is_even() {
+            Quad(Dyadic::new(c, m), Dyadic::new(d, m))
+        } else {
+            // (c + d√2)/(2ᵐ√2) == d/2ᵐ + c√2/2ᵐ⁺¹
+            Quad(Dyadic::new(d, m), Dyadic::new(c, m + 1))
+        }
+    }
+}
+
+#[cfg(test)]
+mod random_tests {
+    use super::*;
+    use crate::DenomExp;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn dyadics_in_unit_interval() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for k in [0, 1, 5, 63] {
+            for x in UniformDyadic::new(k).sample_iter(&mut rng).take(200) {
+                assert!(x.1 <= k);
+                assert!(Dyadic(0, 0) <= x && x < Dyadic(1, 0));
+            }
+        }
+        // all eight values of n/2³ turn up
+        let mut seen = [false; 8];
+        for x in UniformDyadic::new(3).sample_iter(&mut rng).take(200) {
+            seen[(x.0 << (3 - x.1)) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+
+    #[test]
+    fn roottwo_coefficients_in_range() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let dist = UniformRootTwo::new(-3..=3, 0..=1);
+        let samples: Vec<_> = dist.sample_iter(&mut rng).take(500).collect();
+        assert!(samples
+            .iter()
+            .all(|x| (-3..=3).contains(&x.0) && (0..=1).contains(&x.1)));
+        assert!(samples.contains(&Quad(-3, 0)) && samples.contains(&Quad(3, 1)));
+        let extremes = UniformRootTwo::new(i64::MIN..=i64::MAX, 7..=7);
+        assert_eq!(extremes.sample(&mut rng).1, 7);
+    }
+
+    #[test]
+    fn units_and_bounded_norms() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let units: Vec<_> = UnitRootTwo::new(50)
+            .sample_iter(&mut rng)
+            .take(500)
+            .collect();
+        assert!(units.iter().all(|u| u.is_unit()));
+        assert!(units.iter().any(|u| u.0 < 0) && units.iter().any(|u| u.0 > 0));
+        assert!(units.iter().any(|u| u.1.abs() > 1 << 40));
+        for n in [1, 7, 1000] {
+            for x in NormBoundedRootTwo::new(n).sample_iter(&mut rng).take(200) {
+                assert!((1..=n).contains(&x.norm().abs()));
+            }
+        }
+    }
+
+    #[test]
+    fn exact_denominator_exponents() {
+        let mut rng = StdRng::seed_from_u64(4);
+        for k in 0..8 {
+            let dist = DenomExpRootTwo::new(k, 20);
+            for x in dist.sample_iter(&mut rng).take(100) {
+                assert_eq!(x.denomexp(), k);
+            }
+        }
+        let zero = DenomExpRootTwo::new(0, 0).sample(&mut rng);
+        assert_eq!(zero, RootTwo::<Dyadic>::new(Dyadic(0, 0), Dyadic(0, 0)));
+    }
+}
@@ -0,0 +1,211 @@
+//! A slow model of Q[√2] over pairs of `BigRational`s, for differential
+//! tests of the fixed-width arithmetic. Nothing here can overflow, and each
+//! operation is the textbook formula, so any disagreement with the fast
+//! paths is a bug in the latter.
+
+use std::{cmp::Ordering, ops};
+
+use num::{BigInt, BigRational, FromPrimitive, ToPrimitive, Zero};
+
+use crate::{Dyadic, Quad, RootTwo};
+
+/// `a + b√2` with arbitrary rational coefficients.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Ref(BigRational, BigRational);
+
+impl Ref {
+    pub(crate) fn from_int(x: RootTwo<i64>) -> Ref {
+        Ref(BigInt::from(x.0).into(), BigInt::from(x.1).into())
+    }
+
+    pub(crate) fn from_dyadic(x: RootTwo<Dyadic>) -> Ref {
+        let (a, b) = x.to_ratios();
+        Ref(a, b)
+    }
+
+    /// The same element with integer coefficients, if they fit.
+    pub(crate) fn to_int(&self) -> Option<RootTwo<i64>> {
+        if !self.0.is_integer() || !self.1.is_integer() {
+            return None;
+        }
+        Some(Quad(
+            self.0.to_integer().to_i64()?,
+            self.1.to_integer().to_i64()?,
+        ))
+    }
+
+    /// The same element with dyadic coefficients, if they fit.
+    pub(crate) fn to_dyadic(&self) -> Option<RootTwo<Dyadic>> {
+        RootTwo::try_from_ratios(self.0.clone(), self.1.clone()).ok()
+    }
+
+    pub(crate) fn adj2(&self) -> Ref {
+        Ref(self.0.clone(), -self.1.clone())
+    }
+
+    pub(crate) fn norm(&self) -> BigRational {
+        &self.0 * &self.0 - BigRational::from_integer(2.into()) * &self.1 * &self.1
+    }
+
+    /// The sign of `a + b√2` as a real number, comparing `a²` with `2b²`
+    /// when the signs of the coefficients differ.
+    pub(crate) fn sign(&self) -> Ordering {
+        let (sa, sb) = (self.0.cmp(&Zero::zero()), self.1.cmp(&Zero::zero()));
+        if sa == sb || sb == Ordering::Equal {
+            return sa;
+        }
+        if sa == Ordering::Equal {
+            return sb;
+        }
+        let two_b2 = BigRational::from_integer(2.into()) * &self.1 * &self.1;
+        let cmp = (&self.0 * &self.0).cmp(&two_b2);
+        if sa == Ordering::Greater {
+            cmp
+        } else {
+            cmp.reverse()
+        }
+    }
+
+    /// The greatest integer at most `self`, found by stepping from a float
+    /// estimate until the exact signs agree.
+    pub(crate) fn floor(&self) -> BigInt {
+        let estimate = self.0.to_f64().unwrap() + self.1.to_f64().unwrap() * 2f64.sqrt();
+        let mut n = BigInt::from_f64(estimate.floor()).unwrap();
+        let sign_from = |n: &BigInt| (self.clone() - Ref(n.clone().into(), Zero::zero())).sign();
+        while sign_from(&n) == Ordering::Less {
+            n -= 1;
+        }
+        while sign_from(&(&n + 1)) != Ordering::Less {
+            n += 1;
+        }
+        n
+    }
+
+    pub(crate) fn abs(&self) -> Ref {
+        if self.sign() == Ordering::Less {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+}
+
+impl ops::Add for Ref {
+    type Output = Ref;
+    fn add(self, rhs: Ref) -> Ref {
+        Ref(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl ops::Sub for Ref {
+    type Output = Ref;
+    fn sub(self, rhs: Ref) -> Ref {
+        Ref(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl ops::Neg for Ref {
+    type Output = Ref;
+    fn neg(self) -> Ref {
+        Ref(-self.0, -self.1)
+    }
+}
+
+impl ops::Mul for Ref {
+    type Output = Ref;
+    fn mul(self, rhs: Ref) -> Ref {
+        let two = BigRational::from_integer(2.into());
+        Ref(
+            &self.0 * &rhs.0 + two * &self.1 * &rhs.1,
+            self.0 * rhs.1 + self.1 * rhs.0,
+        )
+    }
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use super::*;
+    use num::One;
+    use proptest::prelude::*;
+
+    /// Dyadics of every size, including ones whose sums and products
+    /// overflow.
+    fn dyadic() -> impl Strategy<Value = Dyadic> + Clone {
+        prop_oneof![
+            (-1000..=1000i64, 0..=10u32),
+            (any::<i64>(), 0..=70u32),
+            (-(1i64 << 32)..=1 << 32, 0..=40u32),
+        ]
+        .prop_map(|(n, k)| Dyadic::new(n, k))
+    }
+
+    fn droottwo() -> impl Strategy<Value = RootTwo<Dyadic>> {
+        (dyadic(), dyadic()).prop_map(|(a, b)| Quad(a, b))
+    }
+
+    fn zroottwo() -> impl Strategy<Value = RootTwo<i64>> {
+        prop_oneof![
+            (-1000..=1000i64, -1000..=1000i64),
+            (any::<i64>(), any::<i64>()),
+            (-(1i64 << 31)..=1 << 31, -(1i64 << 31)..=1 << 31),
+        ]
+        .prop_map(|(a, b)| Quad(a, b))
+    }
+
+    proptest! {
+        #[test]
+        fn checked_droottwo_matches(x in droottwo(), y in droottwo()) {
+            let (rx, ry) = (Ref::from_dyadic(x), Ref::from_dyadic(y));
+            prop_assert_eq!(x.checked_add(y), (rx.clone() + ry.clone()).to_dyadic());
+            prop_assert_eq!(x.checked_sub(y), (rx.clone() - ry.clone()).to_dyadic());
+            prop_assert_eq!(x.checked_neg(), (-rx.clone()).to_dyadic());
+            prop_assert_eq!(x.checked_mul(y), (rx * ry).to_dyadic());
+        }
+
+        #[test]
+        fn checked_zroottwo_matches(x in zroottwo(), y in zroottwo()) {
+            let (rx, ry) = (Ref::from_int(x), Ref::from_int(y));
+            prop_assert_eq!(x.checked_add(y), (rx.clone() + ry.clone()).to_int());
+            prop_assert_eq!(x.checked_sub(y), (rx.clone() - ry.clone()).to_int());
+            prop_assert_eq!(x.checked_mul(y), (rx * ry).to_int());
+        }
+
+        #[test]
+        fn order_matches(x in droottwo(), y in droottwo()) {
+            let difference = Ref::from_dyadic(x) - Ref::from_dyadic(y);
+            prop_assert_eq!(x.cmp(&y), difference.sign());
+            prop_assert_eq!(x.abs(), Ref::from_dyadic(x).abs().to_dyadic().unwrap());
+        }
+
+        #[test]
+        fn rounding_matches(x in droottwo()) {
+            let r = Ref::from_dyadic(x);
+            let floor = r.floor();
+            if let Some(n) = floor.to_i64().filter(|n| *n < i64::MAX) {
+                prop_assert_eq!(x.floor(), n);
+                let exact = r.floor() == -(-r.clone()).floor();
+                prop_assert_eq!(x.ceil(), if exact { n } else { n + 1 });
+            }
+        }
+
+        #[test]
+        fn norm_and_adj2_match(x in (-(1i64 << 16)..=1 << 16, 0..=12u32, -(1i64 << 16)..=1 << 16, 0..=12u32)) {
+            let x = RootTwo::new(Dyadic::new(x.0, x.1), Dyadic::new(x.2, x.3));
+            let r = Ref::from_dyadic(x);
+            prop_assert_eq!(BigRational::from(x.norm()), r.norm());
+            prop_assert_eq!(Ref::from_dyadic(crate::Adj2::adj2(x)), r.adj2());
+        }
+    }
+
+    #[test]
+    fn reference_sign_and_floor() {
+        let one = BigRational::one();
+        // 1 - √2/2 > 0 while 1 - √2 < 0
+        let x = Ref(one.clone(), BigRational::new((-1).into(), 2.into()));
+        assert_eq!(x.sign(), Ordering::Greater);
+        assert_eq!(x.floor(), BigInt::zero());
+        let y = Ref(one.clone(), -one);
+        assert_eq!(y.sign(), Ordering::Less);
+        assert_eq!(y.floor(), BigInt::from(-1));
+    }
+}
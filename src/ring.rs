@@ -0,0 +1,838 @@
+use std::{f64::consts::FRAC_1_SQRT_2, ops};
+
+use num::{BigInt, Complex, One, Rational64, Signed, Zero};
+
+use crate::{
+    Adj, Cplx, Cyclotomic, Dyadic, Eisenstein, Golden, Matrix2, Omega, Quad, Quaternion, RootTwo,
+    ZOmega, ZI,
+};
+
+/// A ring with the usual arithmetic operators and identities. Multiplication
+/// is associative and distributes over addition, but need not commute.
+pub trait Ring:
+    Sized
+    + Clone
+    + PartialEq
+    + Zero
+    + One
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Neg<Output = Self>
+    + ops::Mul<Output = Self>
+{
+}
+
+/// A ring whose multiplication commutes.
+pub trait CommutativeRing: Ring {}
+
+/// An integral domain with division with remainder.
+pub trait EuclideanDomain: CommutativeRing {
+    /// The Euclidean function. Every nonzero remainder of a division by `b`
+    /// has a strictly smaller rank than `b`.
+    fn rank(&self) -> u64;
+
+    /// Returns `(q, r)` with `self == q * rhs + r` and `r.rank() < rhs.rank()`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    fn div_rem(self, rhs: Self) -> (Self, Self);
+
+    /// A greatest common divisor, by the Euclidean algorithm. It is only
+    /// determined up to a unit factor, and `gcd(0, 0) == 0`.
+    fn gcd(self, rhs: Self) -> Self {
+        let (mut a, mut b) = (self, rhs);
+        while !b.is_zero() {
+            let r = a.div_rem(b.clone()).1;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// Returns `(g, s, t)` with `g` a greatest common divisor of `self` and
+    /// `rhs`, and `g == s * self + t * rhs`.
+    fn ext_gcd(self, rhs: Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (self, rhs);
+        let (mut s0, mut s1) = (Self::one(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(r1.clone());
+            let s = s0 - q.clone() * s1.clone();
+            let t = t0 - q * t1.clone();
+            (r0, r1) = (r1, r);
+            (s0, s1) = (s1, s);
+            (t0, t1) = (t1, t);
+        }
+        (r0, s0, t0)
+    }
+}
+
+/// The integer nearest to `a / b`, rounding halves up.
+///
+/// # Panics
+///
+/// Panics if `b` is zero.
+pub(crate) fn rounddiv(a: BigInt, b: &BigInt) -> BigInt {
+    let (a, b) = if b.is_negative() {
+        (-a, -b)
+    } else {
+        (a, b.clone())
+    };
+    num::Integer::div_floor(&(a * 2 + &b), &(b * 2))
+}
+
+/// The square root of `n` if it is a perfect square.
+pub(crate) fn exact_sqrt(n: i128) -> Option<i128> {
+    if n < 0 {
+        return None;
+    }
+    let root = num::integer::Roots::sqrt(&n);
+    (root * root == n).then_some(root)
+}
+
+/// A commutative ring in which every nonzero element is invertible.
+pub trait Field: CommutativeRing + ops::Div<Output = Self> {}
+
+/// A ring containing `1/2`, and therefore every dyadic fraction.
+pub trait HalfRing: Ring {
+    /// The image of a dyadic fraction under the canonical embedding.
+    fn from_dyadic(x: Dyadic) -> Self;
+
+    /// The exact element `1/2`.
+    fn half() -> Self {
+        Self::from_dyadic(Dyadic(1, 1))
+    }
+}
+
+/// A ring containing `√2`.
+pub trait RootTwoRing: Ring {
+    /// The exact element `√2`.
+    fn roottwo() -> Self;
+}
+
+/// A ring containing `1/√2`. Any ring with both `√2` and `1/2` qualifies,
+/// since `1/√2 == √2/2`.
+pub trait RootHalfRing: RootTwoRing + HalfRing {
+    /// The exact element `1/√2`.
+    fn roothalf() -> Self {
+        Self::roottwo() * Self::half()
+    }
+}
+
+impl<T: RootTwoRing + HalfRing> RootHalfRing for T {}
+
+/// A ring containing the imaginary unit. Such rings are closed under complex
+/// conjugation, so [`Adj`] is a supertrait.
+pub trait ComplexRing: Ring + Adj {
+    /// The exact element `i`.
+    fn i() -> Self;
+}
+
+/// A ring containing the eighth root of unity `ω = e^(iπ/4) = (1 + i)/√2`.
+pub trait OmegaRing: Ring {
+    /// The exact (or, for floating point rings, nearest) element `ω`.
+    fn omega() -> Self;
+}
+
+/// A finite extension of a base ring, equipped with the field norm: the
+/// product of all Galois conjugates, e.g. `a² - 2b²` for `a + b√2`. The norm
+/// is multiplicative and vanishes only at zero.
+pub trait NormedRing: Ring {
+    type Base;
+    fn norm(self) -> Self::Base;
+}
+
+// #########################################
+// #######                           #######
+// ####       Impls for base rings      ####
+// #######                           #######
+// #########################################
+
+impl Ring for i64 {}
+impl CommutativeRing for i64 {}
+
+impl EuclideanDomain for i64 {
+    fn rank(&self) -> u64 {
+        self.unsigned_abs()
+    }
+
+    fn div_rem(self, rhs: i64) -> (i64, i64) {
+        (self / rhs, self % rhs)
+    }
+}
+
+impl Ring for Rational64 {}
+impl CommutativeRing for Rational64 {}
+impl Field for Rational64 {}
+
+impl HalfRing for Rational64 {
+    fn from_dyadic(x: Dyadic) -> Self {
+        x.into()
+    }
+}
+
+impl Adj for i64 {
+    fn adj(self) -> Self {
+        self
+    }
+}
+
+impl Adj for Rational64 {
+    fn adj(self) -> Self {
+        self
+    }
+}
+
+impl Ring for f64 {}
+impl CommutativeRing for f64 {}
+
+impl Adj for f64 {
+    fn adj(self) -> Self {
+        self
+    }
+}
+
+impl Ring for Complex<f64> {}
+impl CommutativeRing for Complex<f64> {}
+
+impl Adj for Complex<f64> {
+    fn adj(self) -> Self {
+        self.conj()
+    }
+}
+
+impl ComplexRing for Complex<f64> {
+    fn i() -> Self {
+        Complex::i()
+    }
+}
+
+impl OmegaRing for Complex<f64> {
+    fn omega() -> Self {
+        Complex::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+    }
+}
+
+impl<T> Ring for Dyadic<T> where
+    T: num::Integer + ops::Shl<u32, Output = T> + ops::Neg<Output = T> + Clone
+{
+}
+impl<T> CommutativeRing for Dyadic<T> where
+    T: num::Integer + ops::Shl<u32, Output = T> + ops::Neg<Output = T> + Clone
+{
+}
+
+impl EuclideanDomain for Dyadic {
+    /// Powers of two are units, so only the odd part of the numerator counts.
+    fn rank(&self) -> u64 {
+        if self.0 == 0 {
+            return 0;
+        }
+        (self.0 >> self.0.trailing_zeros()).unsigned_abs()
+    }
+
+    /// Writes `rhs = 2ᵉ·m` with `m` odd and divides `self·2⁻ᵉ` by `m` as
+    /// integers, after clearing its denominator.
+    fn div_rem(self, rhs: Dyadic) -> (Dyadic, Dyadic) {
+        if rhs.0 == 0 {
+            panic!("attempted to divide by zero");
+        }
+        let t = rhs.0.trailing_zeros();
+        let m = rhs.0 >> t;
+        let e = t as i64 - rhs.1 as i64;
+        // self·2⁻ᵉ == n / 2ᵏ
+        let k = self.1 as i64 + e;
+        let (n, k) = if k >= 0 {
+            (self.0, k as u32)
+        } else {
+            (self.0 << -k, 0)
+        };
+        let (q, r) = (n / m, n % m);
+        // self == (q / 2ᵏ)·rhs + r·2ᵉ⁻ᵏ
+        let exp = e - k as i64;
+        let rem = if exp >= 0 {
+            Dyadic(r << exp, 0)
+        } else {
+            Dyadic::simplify(r, -exp as u32)
+        };
+        (Dyadic::simplify(q, k), rem)
+    }
+}
+
+impl HalfRing for Dyadic {
+    fn from_dyadic(x: Dyadic) -> Self {
+        x
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####      Impls for extensions       ####
+// #######                           #######
+// #########################################
+
+impl<T, const D: i64> Ring for Quad<T, D>
+where
+    T: CommutativeRing,
+    Quad<T, D>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T, const D: i64> CommutativeRing for Quad<T, D>
+where
+    T: CommutativeRing,
+    Quad<T, D>: ops::Mul<Output = Self>,
+{
+}
+
+impl<const D: i64> Field for Quad<Rational64, D> {}
+
+impl<T> Ring for Omega<T>
+where
+    T: CommutativeRing,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T> CommutativeRing for Omega<T>
+where
+    T: CommutativeRing,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T> Ring for Cplx<T>
+where
+    T: CommutativeRing,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T> CommutativeRing for Cplx<T>
+where
+    T: CommutativeRing,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+}
+
+/// Quaternions only form a ring, since `ij == -ji`.
+impl<T> Ring for Quaternion<T>
+where
+    T: CommutativeRing,
+    Quaternion<T>: ops::Mul<Output = Self>,
+{
+}
+
+/// Matrices only form a ring, even over commutative entries.
+impl<T: Ring> Ring for Matrix2<T> {}
+
+impl<T> Ring for Golden<T>
+where
+    T: CommutativeRing,
+    Golden<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T> CommutativeRing for Golden<T>
+where
+    T: CommutativeRing,
+    Golden<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T> Ring for Eisenstein<T>
+where
+    T: CommutativeRing,
+    Eisenstein<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T> CommutativeRing for Eisenstein<T>
+where
+    T: CommutativeRing,
+    Eisenstein<T>: ops::Mul<Output = Self>,
+{
+}
+
+impl<T, const D: i64> HalfRing for Quad<T, D>
+where
+    T: HalfRing + CommutativeRing,
+    Quad<T, D>: ops::Mul<Output = Self>,
+{
+    fn from_dyadic(x: Dyadic) -> Self {
+        Quad(T::from_dyadic(x), T::zero())
+    }
+}
+
+impl<T> HalfRing for Omega<T>
+where
+    T: HalfRing + CommutativeRing,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+    fn from_dyadic(x: Dyadic) -> Self {
+        Omega(T::from_dyadic(x), T::zero(), T::zero(), T::zero())
+    }
+}
+
+impl<T> HalfRing for Cplx<T>
+where
+    T: HalfRing + CommutativeRing,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+    fn from_dyadic(x: Dyadic) -> Self {
+        Cplx(T::from_dyadic(x), T::zero())
+    }
+}
+
+impl<T> RootTwoRing for RootTwo<T>
+where
+    T: CommutativeRing,
+    RootTwo<T>: ops::Mul<Output = Self>,
+{
+    fn roottwo() -> Self {
+        Quad(T::zero(), T::one())
+    }
+}
+
+impl<T> RootTwoRing for Omega<T>
+where
+    T: CommutativeRing,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+    /// `√2 == ω - ω³`
+    fn roottwo() -> Self {
+        Omega(T::zero(), T::one(), T::zero(), -T::one())
+    }
+}
+
+impl<T> RootTwoRing for Cplx<T>
+where
+    T: RootTwoRing + CommutativeRing,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+    fn roottwo() -> Self {
+        Cplx(T::roottwo(), T::zero())
+    }
+}
+
+impl<T> ComplexRing for Omega<T>
+where
+    T: CommutativeRing + Adj,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+    fn i() -> Self {
+        Omega(T::zero(), T::zero(), T::one(), T::zero())
+    }
+}
+
+impl<T> ComplexRing for Cplx<T>
+where
+    T: CommutativeRing + Adj,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+    fn i() -> Self {
+        Cplx(T::zero(), T::one())
+    }
+}
+
+impl<T> OmegaRing for Omega<T>
+where
+    T: CommutativeRing,
+    Omega<T>: ops::Mul<Output = Self>,
+{
+    fn omega() -> Self {
+        Omega(T::zero(), T::one(), T::zero(), T::zero())
+    }
+}
+
+impl<T> OmegaRing for Cplx<T>
+where
+    T: RootHalfRing + CommutativeRing,
+    Cplx<T>: ops::Mul<Output = Self>,
+{
+    /// `ω == (1 + i)/√2`
+    fn omega() -> Self {
+        Cplx(T::roothalf(), T::roothalf())
+    }
+}
+
+impl EuclideanDomain for RootTwo<i64> {
+    fn rank(&self) -> u64 {
+        self.norm().unsigned_abs()
+    }
+
+    fn div_rem(self, rhs: RootTwo<i64>) -> (RootTwo<i64>, RootTwo<i64>) {
+        let q = self.rounddiv(rhs);
+        (q, self - q.strict_mul(rhs))
+    }
+}
+
+impl EuclideanDomain for ZOmega {
+    fn rank(&self) -> u64 {
+        self.norm().unsigned_abs()
+    }
+
+    /// Rounding leaves an error `e` with coordinates in `[-1/2, 1/2)`, and
+    /// `norm(e) < 1` since `|e|² + |e•|² ≤ 2`.
+    fn div_rem(self, rhs: ZOmega) -> (ZOmega, ZOmega) {
+        let q = self.rounddiv(rhs);
+        (q, self.sub_mul(q, rhs))
+    }
+}
+
+impl<T, const D: i64> NormedRing for Quad<T, D>
+where
+    T: CommutativeRing + ops::Mul<i64, Output = T> + Copy,
+{
+    type Base = T;
+    fn norm(self) -> T {
+        Quad::norm(self)
+    }
+}
+
+impl<T> NormedRing for Omega<T>
+where
+    T: CommutativeRing + Adj + ops::Mul<i64, Output = T> + Copy,
+{
+    type Base = T;
+    fn norm(self) -> T {
+        Omega::norm(self)
+    }
+}
+
+impl<T: CommutativeRing + Copy> NormedRing for Cplx<T> {
+    type Base = T;
+    fn norm(self) -> T {
+        self.norm_sqr()
+    }
+}
+
+impl<T: CommutativeRing + Copy> NormedRing for Golden<T> {
+    type Base = T;
+    fn norm(self) -> T {
+        Golden::norm(self)
+    }
+}
+
+impl<T: CommutativeRing + Copy> NormedRing for Eisenstein<T> {
+    type Base = T;
+    fn norm(self) -> T {
+        Eisenstein::norm(self)
+    }
+}
+
+impl Ring for ZI {}
+impl CommutativeRing for ZI {}
+
+impl NormedRing for ZI {
+    type Base = i64;
+    fn norm(self) -> i64 {
+        self.0 * self.0 + self.1 * self.1
+    }
+}
+
+impl ComplexRing for ZI {
+    fn i() -> Self {
+        ZI(0, 1)
+    }
+}
+
+impl<const N: usize> Ring for Cyclotomic<N> {}
+impl<const N: usize> CommutativeRing for Cyclotomic<N> {}
+
+impl<const N: usize> NormedRing for Cyclotomic<N> {
+    type Base = i64;
+    fn norm(self) -> i64 {
+        Cyclotomic::norm(self)
+    }
+}
+
+impl OmegaRing for Cyclotomic<8> {
+    fn omega() -> Self {
+        Cyclotomic::zeta()
+    }
+}
+
+#[cfg(test)]
+mod ring_tests {
+    use super::*;
+    use crate::{Adj2, DOmega, QRootTwo, RootMinusTwo, ZOmega};
+
+    fn square_minus<R: Ring>(x: R, y: R) -> R {
+        x.clone() * x - y
+    }
+
+    #[test]
+    fn generic_over_rings() {
+        assert_eq!(square_minus(3i64, 2), 7);
+        assert_eq!(square_minus(Dyadic(3, 1), Dyadic(1, 2)), Dyadic(2, 0));
+        assert_eq!(
+            square_minus(RootTwo::new(1, 1), RootTwo::new(3, 0)),
+            RootTwo::new(0, 2)
+        );
+        let omega: ZOmega = Omega(0, 1, 0, 0);
+        assert_eq!(square_minus(omega, omega), Omega(0, -1, 1, 0));
+    }
+
+    #[test]
+    fn half_rings() {
+        fn average<R: HalfRing>(x: R, y: R) -> R {
+            (x + y) * R::half()
+        }
+        assert_eq!(average(Dyadic(1, 0), Dyadic(1, 1)), Dyadic(3, 2));
+        assert_eq!(
+            average(RootTwo::new(Dyadic(1, 0), Dyadic(0, 0)), RootTwo::half()),
+            RootTwo::new(Dyadic(3, 2), Dyadic(0, 0))
+        );
+        assert_eq!(
+            Rational64::from_dyadic(Dyadic(-5, 3)),
+            Rational64::new(-5, 8)
+        );
+        let half: DOmega = HalfRing::half();
+        assert_eq!(
+            half * 2,
+            Omega(Dyadic(1, 0), Dyadic(0, 0), Dyadic(0, 0), Dyadic(0, 0))
+        );
+    }
+
+    #[test]
+    fn roottwo_and_roothalf() {
+        fn check<R: RootHalfRing + std::fmt::Debug>() {
+            let r2 = R::roottwo();
+            let rh = R::roothalf();
+            assert_eq!(
+                r2.clone() * r2.clone() * rh.clone() * rh.clone(),
+                R::from_dyadic(Dyadic(1, 0))
+            );
+            assert_eq!(rh.clone() * rh, R::half());
+            assert_eq!(r2.clone() * r2, R::from_dyadic(Dyadic(2, 0)));
+        }
+        check::<RootTwo<Dyadic>>();
+        check::<DOmega>();
+        check::<QRootTwo>();
+        check::<Cplx<RootTwo<Dyadic>>>();
+        assert_eq!(ZOmega::roottwo(), ZOmega::from(RootTwo::roottwo()));
+    }
+
+    #[test]
+    fn complex_rings() {
+        fn check<R: ComplexRing + std::fmt::Debug>(x: R) {
+            let i = R::i();
+            let one = i.clone() * i.clone().adj();
+            assert_eq!(i.clone() * i.clone(), -one);
+            assert_eq!(x.clone().adj().adj(), x.clone());
+            assert_eq!((x.clone() * i.clone()).adj(), x.adj() * i.adj());
+        }
+        check(ZI(3, -4));
+        check(Cplx(2, 5));
+        check(Omega(1, 2, 3, 4));
+        check(Omega(
+            Dyadic(1, 1),
+            Dyadic(0, 0),
+            Dyadic(-3, 2),
+            Dyadic(1, 0),
+        ));
+        check(Cplx(
+            RootTwo::new(Dyadic(1, 1), Dyadic(1, 0)),
+            RootTwo::half(),
+        ));
+
+        // ω⁻¹ == ω⁷ == -ω³
+        let omega: ZOmega = Omega(0, 1, 0, 0);
+        assert_eq!(omega.adj(), Omega(0, 0, 0, -1));
+        assert_eq!(omega * omega.adj(), Omega(1, 0, 0, 0));
+    }
+
+    #[test]
+    fn omega_rings() {
+        fn eighth_power<R: OmegaRing>() -> R {
+            let omega2 = R::omega() * R::omega();
+            let omega4 = omega2.clone() * omega2;
+            omega4.clone() * omega4
+        }
+        assert_eq!(eighth_power::<ZOmega>(), Omega(1, 0, 0, 0));
+        assert_eq!(eighth_power::<DOmega>(), DOmega::from_dyadic(Dyadic(1, 0)));
+        assert_eq!(
+            eighth_power::<Cplx<RootTwo<Dyadic>>>(),
+            Cplx::from_dyadic(Dyadic(1, 0))
+        );
+        assert_eq!(
+            Cplx::<RootTwo<Dyadic>>::omega(),
+            Cplx::from(DOmega::omega())
+        );
+        assert_eq!(ZOmega::from(Cyclotomic::<8>::omega()), ZOmega::omega());
+        assert!((eighth_power::<Complex<f64>>() - Complex::new(1.0, 0.0)).norm() < 1e-12);
+    }
+
+    #[test]
+    fn adj_composes_with_adj2() {
+        // the two conjugations commute, and together send ω to ω³
+        let x: ZOmega = Omega(1, 2, 3, 4);
+        assert_eq!(x.adj().adj2(), x.adj2().adj());
+        let omega = ZOmega::omega();
+        assert_eq!(omega.adj().adj2(), omega * omega * omega);
+
+        // real rings are fixed
+        assert_eq!(Dyadic(3, 2).adj(), Dyadic(3, 2));
+        assert_eq!(RootTwo::new(3, 4).adj(), RootTwo::new(3, 4));
+        assert_eq!(Golden::new(3, 4).adj(), Golden::new(3, 4));
+        // but not imaginary quadratic ones
+        assert_eq!(RootMinusTwo::new(3, 4).adj(), RootMinusTwo::new(3, -4));
+    }
+
+    #[test]
+    fn norms_are_multiplicative() {
+        fn check<R>(x: R, y: R)
+        where
+            R: NormedRing + Copy,
+            R::Base: Ring + std::fmt::Debug,
+        {
+            assert_eq!((x * y).norm(), x.norm() * y.norm());
+        }
+        check(RootTwo::new(3, 4), RootTwo::new(-1, 5));
+        check(RootMinusTwo::new(3, 4), RootMinusTwo::new(-1, 5));
+        check(Omega(1, 2, 3, 4), Omega(5, -6, 7, 8));
+        check(Golden::new(3, 4), Golden::new(-1, 5));
+        check(Eisenstein::new(3, 4), Eisenstein::new(-1, 5));
+        check(ZI(3, 4), ZI(-1, 5));
+        check(
+            Cplx(RootTwo::new(1, 1), RootTwo::new(0, 1)),
+            Cplx(RootTwo::new(2, 0), RootTwo::new(1, 1)),
+        );
+        check(
+            Cyclotomic::<5>::new([1, 2, 0, -1, 0]),
+            Cyclotomic::new([0, 1, 1, 0, 0]),
+        );
+        check(
+            Cyclotomic::<16>::zeta(),
+            Cyclotomic::new([2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]),
+        );
+    }
+
+    #[test]
+    fn known_norms() {
+        assert_eq!(NormedRing::norm(RootTwo::new(1, 1)), -1);
+        // 1 + ω divides √2, so its norm divides N(√2) = 4
+        assert_eq!(NormedRing::norm(Omega(1, 1, 0, 0)), 2);
+        assert_eq!(ZOmega::roottwo().norm(), 4);
+        assert_eq!(Cyclotomic::from(Omega(1, 1, 0, 0)).norm(), 2);
+        assert_eq!(Cyclotomic::<5>::new([2, 0, 0, 0, 0]).norm(), 16);
+    }
+
+    #[test]
+    fn exact_integer_roots() {
+        assert_eq!(exact_sqrt(0), Some(0));
+        assert_eq!(exact_sqrt(49), Some(7));
+        assert_eq!(exact_sqrt(50), None);
+        assert_eq!(exact_sqrt(-4), None);
+        assert_eq!(exact_sqrt((1 << 100) + (1 << 51) + 1), Some((1 << 50) + 1));
+    }
+
+    #[test]
+    fn rounddiv_rounds_to_nearest() {
+        for (a, b, q) in [(7, 2, 4), (-7, 2, -3), (7, -2, -3), (5, 3, 2), (-5, 3, -2)] {
+            assert_eq!(rounddiv(a.into(), &b.into()), q.into());
+        }
+    }
+
+    #[test]
+    fn generic_sums_and_products() {
+        fn sum_of_squares<R: Ring>(xs: &[R]) -> R {
+            xs.iter()
+                .fold(R::zero(), |acc, x| acc + x.clone() * x.clone())
+        }
+        assert_eq!(sum_of_squares::<RootTwo<i64>>(&[]), RootTwo::zero());
+        assert_eq!(
+            sum_of_squares(&[RootTwo::new(1, 1), RootTwo::one()]),
+            RootTwo::new(4, 2)
+        );
+        assert!(sum_of_squares(&[ZOmega::i(), ZOmega::one()]).is_zero());
+    }
+
+    #[test]
+    fn euclidean_integers() {
+        for (a, b) in [(7i64, 2i64), (-7, 2), (7, -2), (0, 5)] {
+            let (q, r) = a.div_rem(b);
+            assert_eq!(q * b + r, a);
+            assert!(r.rank() < b.rank());
+        }
+    }
+
+    #[test]
+    fn gcd_zroottwo() {
+        assert_eq!(12i64.gcd(18).abs(), 6);
+        let (g, s, t) = 240i64.ext_gcd(46);
+        assert_eq!(g.abs(), 2);
+        assert_eq!(s * 240 + t * 46, g);
+
+        // 7 = (3 + √2)(3 - √2) splits, and 1 + √2 is a unit
+        let p = RootTwo::new(3, 1);
+        let x = p * RootTwo::new(5, -2);
+        let y = p * RootTwo::new(5, 1) * RootTwo::new(1, 1);
+        let g = x.gcd(y);
+        assert_eq!(g.rank(), 7);
+        assert!(x.try_div(g).is_some() && y.try_div(g).is_some());
+        let (g, s, t) = x.ext_gcd(y);
+        assert_eq!(s * x + t * y, g);
+        assert_eq!(g.rank(), 7);
+
+        let (g, s, t) = RootTwo::new(0, 1).ext_gcd(RootTwo::new(3, 0));
+        assert_eq!(g.rank(), 1);
+        assert_eq!(s * RootTwo::new(0, 1) + t * RootTwo::new(3, 0), g);
+        assert_eq!(RootTwo::new(4, 2).gcd(RootTwo::zero()), RootTwo::new(4, 2));
+    }
+
+    #[test]
+    fn gcd_zomega() {
+        for x in [Omega(5, -3, 2, 7), Omega(1, 0, 0, 0), Omega(-4, 9, 0, 1)] {
+            for y in [Omega(1, 1, 0, 0), Omega(3, 0, -2, 1), Omega(0, 0, 7, 0)] {
+                let (q, r) = x.div_rem(y);
+                assert_eq!(q * y + r, x);
+                assert!(r.rank() < y.rank());
+            }
+        }
+
+        // δ = 1 + ω has norm 2 and divides both
+        let delta: ZOmega = Omega(1, 1, 0, 0);
+        let x = delta * Omega(2, 0, 1, 0);
+        let y = delta * Omega(3, -1, 0, 1);
+        let (g, s, t) = x.ext_gcd(y);
+        assert_eq!(s * x + t * y, g);
+        assert_eq!(g.rank(), 2);
+        assert_eq!(x.div_rem(g).1, Omega::zero());
+        assert_eq!(y.div_rem(g).1, Omega::zero());
+        assert_eq!(x.gcd(y).rank(), 2);
+
+        // norms far beyond an i128, like those of r + i for r near 10¹²
+        let p: ZOmega = Omega(1_000_000_000_039, 0, 0, 0);
+        let x: ZOmega = Omega(1_234_567_890_123, -987_654_321_987, 5, 0);
+        let (q, r) = x.div_rem(p);
+        assert_eq!(q, Omega(1, -1, 0, 0));
+        assert_eq!(r, Omega(234_567_890_084, 12_345_678_052, 5, 0));
+        let delta: ZOmega = Omega(1, 1, 0, 0);
+        let g = (p * delta).gcd(x * delta);
+        assert_eq!(delta.div_rem(g).1, Omega::zero());
+        assert_eq!(g.div_rem(delta).1, Omega::zero());
+    }
+
+    #[test]
+    #[should_panic]
+    fn rounddiv_zomega_by_zero() {
+        ZOmega::omega().rounddiv(Omega::zero());
+    }
+
+    #[test]
+    fn fields_divide() {
+        fn div_mul<F: Field>(x: F, y: F) -> F {
+            x / y.clone() * y
+        }
+        let x = QRootTwo::from(RootTwo::new(3, 4));
+        let y = QRootTwo::from(RootTwo::new(-1, 5));
+        assert_eq!(div_mul(x, y), x);
+        assert_eq!(
+            div_mul(Rational64::new(3, 4), Rational64::new(5, 7)),
+            Rational64::new(3, 4)
+        );
+    }
+}
@@ -0,0 +1,544 @@
+//! Serde support. Human-readable formats get the printed form of a dyadic,
+//! such as `"3/2^4"`, and named coefficients such as `{"a": 1, "b": 2}` for
+//! `1 + 2√2` or `{"re": 1, "im": 2}` for `1 + 2i`. Cyclotomic integers are
+//! a list of their `φ(N)` power-basis coefficients. Binary formats get plain
+//! tuples of the raw fields.
+
+use std::{fmt, marker::PhantomData};
+
+use num::BigInt;
+use serde::{
+    de,
+    ser::{SerializeSeq, SerializeTuple},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Cplx, Cyclotomic, Dyadic, Eisenstein, Golden, Omega, Quad, Quaternion, ZI};
+
+impl<T: Serialize + fmt::Display> Serialize for Dyadic<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (&self.0, self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Dyadic {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(de::Error::custom)
+        } else {
+            let (n, k) = <(i64, u32)>::deserialize(deserializer)?;
+            Ok(Dyadic::new(n, k))
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Dyadic<BigInt> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            s.parse().map_err(de::Error::custom)
+        } else {
+            let (n, k) = <(BigInt, u32)>::deserialize(deserializer)?;
+            Ok(Dyadic::new(n, k))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Quad")]
+struct QuadFields<T> {
+    a: T,
+    b: T,
+}
+
+impl<T: Serialize, const D: i64> Serialize for Quad<T, D> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            QuadFields {
+                a: &self.0,
+                b: &self.1,
+            }
+            .serialize(serializer)
+        } else {
+            (&self.0, &self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>, const D: i64> Deserialize<'de> for Quad<T, D> {
+    fn deserialize<R: Deserializer<'de>>(deserializer: R) -> Result<Self, R::Error> {
+        if deserializer.is_human_readable() {
+            let QuadFields { a, b } = QuadFields::deserialize(deserializer)?;
+            Ok(Quad(a, b))
+        } else {
+            let (a, b) = <(T, T)>::deserialize(deserializer)?;
+            Ok(Quad(a, b))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Omega")]
+struct OmegaFields<T> {
+    a: T,
+    b: T,
+    c: T,
+    d: T,
+}
+
+impl<T: Serialize> Serialize for Omega<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            OmegaFields {
+                a: &self.0,
+                b: &self.1,
+                c: &self.2,
+                d: &self.3,
+            }
+            .serialize(serializer)
+        } else {
+            (&self.0, &self.1, &self.2, &self.3).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Omega<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let OmegaFields { a, b, c, d } = OmegaFields::deserialize(deserializer)?;
+            Ok(Omega(a, b, c, d))
+        } else {
+            let (a, b, c, d) = <(T, T, T, T)>::deserialize(deserializer)?;
+            Ok(Omega(a, b, c, d))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ZI")]
+struct ZIFields {
+    re: i64,
+    im: i64,
+}
+
+impl Serialize for ZI {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            ZIFields {
+                re: self.0,
+                im: self.1,
+            }
+            .serialize(serializer)
+        } else {
+            (self.0, self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ZI {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let ZIFields { re, im } = ZIFields::deserialize(deserializer)?;
+            Ok(ZI(re, im))
+        } else {
+            let (re, im) = <(i64, i64)>::deserialize(deserializer)?;
+            Ok(ZI(re, im))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Cplx")]
+struct CplxFields<T> {
+    re: T,
+    im: T,
+}
+
+impl<T: Serialize> Serialize for Cplx<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            CplxFields {
+                re: &self.0,
+                im: &self.1,
+            }
+            .serialize(serializer)
+        } else {
+            (&self.0, &self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Cplx<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let CplxFields { re, im } = CplxFields::deserialize(deserializer)?;
+            Ok(Cplx(re, im))
+        } else {
+            let (re, im) = <(T, T)>::deserialize(deserializer)?;
+            Ok(Cplx(re, im))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Golden")]
+struct GoldenFields<T> {
+    a: T,
+    b: T,
+}
+
+impl<T: Serialize> Serialize for Golden<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            GoldenFields {
+                a: &self.0,
+                b: &self.1,
+            }
+            .serialize(serializer)
+        } else {
+            (&self.0, &self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Golden<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let GoldenFields { a, b } = GoldenFields::deserialize(deserializer)?;
+            Ok(Golden(a, b))
+        } else {
+            let (a, b) = <(T, T)>::deserialize(deserializer)?;
+            Ok(Golden(a, b))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Eisenstein")]
+struct EisensteinFields<T> {
+    a: T,
+    b: T,
+}
+
+impl<T: Serialize> Serialize for Eisenstein<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            EisensteinFields {
+                a: &self.0,
+                b: &self.1,
+            }
+            .serialize(serializer)
+        } else {
+            (&self.0, &self.1).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Eisenstein<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let EisensteinFields { a, b } = EisensteinFields::deserialize(deserializer)?;
+            Ok(Eisenstein(a, b))
+        } else {
+            let (a, b) = <(T, T)>::deserialize(deserializer)?;
+            Ok(Eisenstein(a, b))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Quaternion")]
+struct QuaternionFields<T> {
+    a: T,
+    b: T,
+    c: T,
+    d: T,
+}
+
+impl<T: Serialize> Serialize for Quaternion<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            QuaternionFields {
+                a: &self.0,
+                b: &self.1,
+                c: &self.2,
+                d: &self.3,
+            }
+            .serialize(serializer)
+        } else {
+            (&self.0, &self.1, &self.2, &self.3).serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Quaternion<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let QuaternionFields { a, b, c, d } = QuaternionFields::deserialize(deserializer)?;
+            Ok(Quaternion(a, b, c, d))
+        } else {
+            let (a, b, c, d) = <(T, T, T, T)>::deserialize(deserializer)?;
+            Ok(Quaternion(a, b, c, d))
+        }
+    }
+}
+
+impl<const N: usize> Serialize for Cyclotomic<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let degree = Cyclotomic::<N>::degree();
+            let mut seq = serializer.serialize_seq(Some(degree))?;
+            for c in &self.0[..degree] {
+                seq.serialize_element(c)?;
+            }
+            seq.end()
+        } else {
+            let mut tuple = serializer.serialize_tuple(N)?;
+            for c in &self.0 {
+                tuple.serialize_element(c)?;
+            }
+            tuple.end()
+        }
+    }
+}
+
+/// Reads at most `N` coefficients, padding with zeros. Any past `φ(N)` must be
+/// zero, so that the result is already in the power basis.
+struct CyclotomicVisitor<const N: usize>(PhantomData<[i64; N]>);
+
+impl<'de, const N: usize> de::Visitor<'de> for CyclotomicVisitor<N> {
+    type Value = Cyclotomic<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} power-basis coefficients", Cyclotomic::<N>::degree())
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let degree = Cyclotomic::<N>::degree();
+        let mut coeffs = [0; N];
+        let mut len = 0;
+        while let Some(c) = seq.next_element::<i64>()? {
+            if len == N || (len >= degree && c != 0) {
+                return Err(de::Error::invalid_length(len + 1, &self));
+            }
+            coeffs[len] = c;
+            len += 1;
+        }
+        Ok(Cyclotomic(coeffs))
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Cyclotomic<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let visitor = CyclotomicVisitor(PhantomData);
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_seq(visitor)
+        } else {
+            deserializer.deserialize_tuple(N, visitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod serialize_tests {
+    use super::*;
+    use crate::{DOmega, RootTwo, ZOmega, ZTau};
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    #[test]
+    fn dyadic_forms() {
+        assert_tokens(&Dyadic(3, 4).readable(), &[Token::Str("3/2^4")]);
+        assert_tokens(&Dyadic(-5, 0).readable(), &[Token::Str("-5")]);
+        assert_tokens(
+            &Dyadic(3, 4).compact(),
+            &[
+                Token::Tuple { len: 2 },
+                Token::I64(3),
+                Token::U32(4),
+                Token::TupleEnd,
+            ],
+        );
+        assert_de_tokens_error::<serde_test::Readable<Dyadic>>(
+            &[Token::Str("1/3")],
+            "denominator is not a power of two",
+        );
+    }
+
+    #[test]
+    fn roottwo_forms() {
+        let x = RootTwo::new(Dyadic(3, 1), Dyadic(-1, 0));
+        assert_tokens(
+            &x.readable(),
+            &[
+                Token::Struct {
+                    name: "Quad",
+                    len: 2,
+                },
+                Token::Str("a"),
+                Token::Str("3/2^1"),
+                Token::Str("b"),
+                Token::Str("-1"),
+                Token::StructEnd,
+            ],
+        );
+        assert_tokens(
+            &RootTwo::<i64>::new(1, 2).compact(),
+            &[
+                Token::Tuple { len: 2 },
+                Token::I64(1),
+                Token::I64(2),
+                Token::TupleEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn omega_forms() {
+        let x: DOmega = Omega(Dyadic(1, 1), Dyadic(0, 0), Dyadic(-3, 2), Dyadic(7, 0));
+        let mut tokens = vec![Token::Tuple { len: 4 }];
+        for (n, k) in [(1, 1), (0, 0), (-3, 2), (7, 0)] {
+            tokens.extend([
+                Token::Tuple { len: 2 },
+                Token::I64(n),
+                Token::U32(k),
+                Token::TupleEnd,
+            ]);
+        }
+        tokens.push(Token::TupleEnd);
+        assert_tokens(&x.compact(), &tokens);
+        let z: ZOmega = Omega(1, 2, 3, 4);
+        assert_tokens(
+            &z.readable(),
+            &[
+                Token::Struct {
+                    name: "Omega",
+                    len: 4,
+                },
+                Token::Str("a"),
+                Token::I64(1),
+                Token::Str("b"),
+                Token::I64(2),
+                Token::Str("c"),
+                Token::I64(3),
+                Token::Str("d"),
+                Token::I64(4),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    fn pair(name: &'static str, fields: [&'static str; 2], values: [i64; 2]) -> Vec<Token> {
+        let mut tokens = vec![Token::Struct { name, len: 2 }];
+        for (field, value) in fields.into_iter().zip(values) {
+            tokens.extend([Token::Str(field), Token::I64(value)]);
+        }
+        tokens.push(Token::StructEnd);
+        tokens
+    }
+
+    fn tuple(values: &[i64]) -> Vec<Token> {
+        let mut tokens = vec![Token::Tuple { len: values.len() }];
+        tokens.extend(values.iter().map(|&v| Token::I64(v)));
+        tokens.push(Token::TupleEnd);
+        tokens
+    }
+
+    #[test]
+    fn complex_forms() {
+        assert_tokens(&ZI(3, -4).readable(), &pair("ZI", ["re", "im"], [3, -4]));
+        assert_tokens(&ZI(3, -4).compact(), &tuple(&[3, -4]));
+        let z = Cplx::new(5i64, i64::MIN);
+        assert_tokens(&z.readable(), &pair("Cplx", ["re", "im"], [5, i64::MIN]));
+        assert_tokens(&z.compact(), &tuple(&[5, i64::MIN]));
+        let x = Cplx::new(Dyadic(1, 2), Dyadic(-3, 0));
+        assert_tokens(
+            &x.readable(),
+            &[
+                Token::Struct {
+                    name: "Cplx",
+                    len: 2,
+                },
+                Token::Str("re"),
+                Token::Str("1/2^2"),
+                Token::Str("im"),
+                Token::Str("-3"),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn golden_and_eisenstein_forms() {
+        let x: ZTau = Golden::new(2, -1);
+        assert_tokens(&x.readable(), &pair("Golden", ["a", "b"], [2, -1]));
+        assert_tokens(&x.compact(), &tuple(&[2, -1]));
+        let e = Eisenstein::new(-7i64, 8);
+        assert_tokens(&e.readable(), &pair("Eisenstein", ["a", "b"], [-7, 8]));
+        assert_tokens(&e.compact(), &tuple(&[-7, 8]));
+    }
+
+    #[test]
+    fn quaternion_forms() {
+        let q = Quaternion::new(1i64, -2, 3, -4);
+        let mut tokens = vec![Token::Struct {
+            name: "Quaternion",
+            len: 4,
+        }];
+        for (field, value) in ["a", "b", "c", "d"].into_iter().zip([1, -2, 3, -4]) {
+            tokens.extend([Token::Str(field), Token::I64(value)]);
+        }
+        tokens.push(Token::StructEnd);
+        assert_tokens(&q.readable(), &tokens);
+        assert_tokens(&q.compact(), &tuple(&[1, -2, 3, -4]));
+    }
+
+    #[test]
+    fn cyclotomic_forms() {
+        // φ(5) = 4, so ζ⁴ = -1 - ζ - ζ² - ζ³ is outside the power basis
+        let x = Cyclotomic::<5>::new([1, 0, -2, 3, 0]);
+        let mut tokens = vec![Token::Seq { len: Some(4) }];
+        tokens.extend([1, 0, -2, 3].map(Token::I64));
+        tokens.push(Token::SeqEnd);
+        assert_tokens(&x.readable(), &tokens);
+        assert_tokens(&x.compact(), &tuple(&[1, 0, -2, 3, 0]));
+        assert_de_tokens_error::<serde_test::Compact<Cyclotomic<5>>>(
+            &tuple(&[0, 0, 0, 0, 1]),
+            "invalid length 5, expected 4 power-basis coefficients",
+        );
+    }
+
+    #[test]
+    fn big_dyadic_forms() {
+        let n = BigInt::from(i64::MAX) * 4 + 1;
+        let x = Dyadic::new(n, 3);
+        assert_tokens(
+            &x.clone().readable(),
+            &[Token::Str("36893488147419103229/2^3")],
+        );
+        assert_tokens(
+            &x.compact(),
+            &[
+                Token::Tuple { len: 2 },
+                Token::Tuple { len: 2 },
+                Token::I8(1),
+                Token::Seq { len: Some(3) },
+                Token::U32(u32::MAX - 2),
+                Token::U32(u32::MAX),
+                Token::U32(1),
+                Token::SeqEnd,
+                Token::TupleEnd,
+                Token::U32(3),
+                Token::TupleEnd,
+            ],
+        );
+        assert_de_tokens_error::<serde_test::Readable<Dyadic<BigInt>>>(
+            &[Token::Str("1/6")],
+            "denominator is not a power of two",
+        );
+    }
+}
@@ -0,0 +1,221 @@
+use std::ops;
+
+use num::{One, Zero};
+
+use crate::{Adj, Cplx, DOmega, DenomExp, Dyadic, Matrix2, Quad, RootTwo};
+
+/// A rotation of three-space with entries in D[√2], stored as `m/√2ᵏ` for a
+/// matrix `m` over ℤ[√2] and the least such `k`. These are the images of the
+/// single-qubit Clifford+T unitaries under the Bloch sphere representation,
+/// where `k` tracks the T-count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SO3Matrix(pub(crate) [[RootTwo<i64>; 3]; 3], pub(crate) u32);
+
+impl SO3Matrix {
+    /// The matrix `m/√2ᵏ`, with common factors of `√2` cancelled.
+    pub fn new(m: [[RootTwo<i64>; 3]; 3], k: u32) -> SO3Matrix {
+        let (mut m, mut k) = (m, k);
+        // (a + b√2)/√2 == b + (a/2)√2, which is integral when a is even
+        while k > 0 && m.iter().flatten().all(|x| x.0 % 2 == 0) {
+            m = m.map(|row| row.map(|x| Quad(x.1, x.0 / 2)));
+            k -= 1;
+        }
+        SO3Matrix(m, k)
+    }
+
+    /// The numerators `m` of `m/√2ᵏ`.
+    pub fn numerators(&self) -> [[RootTwo<i64>; 3]; 3] {
+        self.0
+    }
+
+    /// The entry in row `i` and column `j`.
+    pub fn entry(&self, i: usize, j: usize) -> RootTwo<Dyadic> {
+        let Quad(a, b) = self.0[i][j];
+        let half = self.1 / 2;
+        if self.1.is_multiple_of(2) {
+            Quad(Dyadic::new(a, half), Dyadic::new(b, half))
+        } else {
+            // (a + b√2)/(2ʰ√2) == b/2ʰ + a√2/2ʰ⁺¹
+            Quad(Dyadic::new(b, half), Dyadic::new(a, half + 1))
+        }
+    }
+
+    /// The transpose, which is the inverse of a rotation.
+    pub fn transpose(&self) -> SO3Matrix {
+        let m = self.0;
+        SO3Matrix(
+            std::array::from_fn(|i| std::array::from_fn(|j| m[j][i])),
+            self.1,
+        )
+    }
+
+    /// The rotation `R` with `U σⱼ U† = Σᵢ Rᵢⱼ σᵢ` for the Pauli matrices
+    /// `σ = (X, Y, Z)`, so that `Rᵢⱼ = tr(σᵢ U σⱼ U†)/2`. This is a group
+    /// homomorphism from U(2) that forgets the global phase, and `u` must
+    /// be unitary for the result to be a rotation.
+    pub fn from_unitary(u: Matrix2<DOmega>) -> SO3Matrix {
+        let paulis = pauli_matrices();
+        let half = Dyadic(1, 1);
+        let entries: [[RootTwo<Dyadic>; 3]; 3] = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                let conjugated = u * paulis[j] * u.adj();
+                let Cplx(re, _) = Cplx::from((paulis[i] * conjugated).trace());
+                Quad(re.0 * half, re.1 * half)
+            })
+        });
+        let k = entries
+            .iter()
+            .flatten()
+            .map(|x| x.denomexp())
+            .max()
+            .unwrap();
+        // scale each entry by √2ᵏ, which leaves integral numerators
+        let m = entries.map(|row| {
+            row.map(|x| {
+                let Quad(a, b) = x.mul_pow2((k / 2) as i32).expect("numerator overflows i64");
+                let (a, b) = if k.is_multiple_of(2) {
+                    (a, b)
+                } else {
+                    (b + b, a)
+                };
+                Quad(integral(a), integral(b))
+            })
+        });
+        SO3Matrix(m, k)
+    }
+}
+
+fn integral(x: Dyadic) -> i64 {
+    debug_assert_eq!(x.1, 0);
+    x.0
+}
+
+/// `X`, `Y` and `Z` over D[ω].
+fn pauli_matrices() -> [Matrix2<DOmega>; 3] {
+    let (zero, one) = (DOmega::zero(), DOmega::one());
+    let i = crate::Omega(
+        Dyadic::zero(),
+        Dyadic::zero(),
+        Dyadic::one(),
+        Dyadic::zero(),
+    );
+    [
+        Matrix2(zero, one, one, zero),
+        Matrix2(zero, -i, i, zero),
+        Matrix2(one, zero, zero, -one),
+    ]
+}
+
+// #########################################
+// #######                           #######
+// ####      Traits for SO3Matrix       ####
+// #######                           #######
+// #########################################
+
+impl ops::Mul for SO3Matrix {
+    type Output = SO3Matrix;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: SO3Matrix) -> Self::Output {
+        let (a, b) = (self.0, rhs.0);
+        let m = std::array::from_fn(|i| {
+            std::array::from_fn(|j| (0..3).fold(RootTwo::zero(), |acc, k| acc + a[i][k] * b[k][j]))
+        });
+        SO3Matrix::new(m, self.1 + rhs.1)
+    }
+}
+
+impl One for SO3Matrix {
+    fn one() -> Self {
+        let m = std::array::from_fn(|i| {
+            std::array::from_fn(|j| {
+                if i == j {
+                    RootTwo::one()
+                } else {
+                    RootTwo::zero()
+                }
+            })
+        });
+        SO3Matrix(m, 0)
+    }
+}
+
+impl DenomExp for SO3Matrix {
+    fn denomexp(&self) -> u32 {
+        self.1
+    }
+}
+
+#[cfg(test)]
+mod so3_tests {
+    use super::*;
+    use crate::{OmegaRing, RootHalfRing};
+
+    fn hadamard() -> Matrix2<DOmega> {
+        let s = DOmega::roothalf();
+        Matrix2(s, s, s, -s)
+    }
+
+    fn t_gate() -> Matrix2<DOmega> {
+        Matrix2(
+            DOmega::one(),
+            DOmega::zero(),
+            DOmega::zero(),
+            DOmega::omega(),
+        )
+    }
+
+    fn ints(rows: [[(i64, i64); 3]; 3]) -> [[RootTwo<i64>; 3]; 3] {
+        rows.map(|row| row.map(|(a, b)| Quad(a, b)))
+    }
+
+    #[test]
+    fn clifford_t_rotations() {
+        // H swaps the x and z axes and reverses y
+        let h = SO3Matrix::from_unitary(hadamard());
+        let expected = ints([
+            [(0, 0), (0, 0), (1, 0)],
+            [(0, 0), (-1, 0), (0, 0)],
+            [(1, 0), (0, 0), (0, 0)],
+        ]);
+        assert_eq!(h, SO3Matrix(expected, 0));
+        // T turns by π/4 about z
+        let t = SO3Matrix::from_unitary(t_gate());
+        let expected = ints([
+            [(1, 0), (-1, 0), (0, 0)],
+            [(1, 0), (1, 0), (0, 0)],
+            [(0, 0), (0, 0), (0, 1)],
+        ]);
+        assert_eq!(t, SO3Matrix(expected, 1));
+        assert_eq!(t.denomexp(), 1);
+        assert_eq!(t.entry(0, 1), RootTwo::new(Dyadic(0, 0), Dyadic(-1, 1)));
+        assert_eq!(t.entry(2, 2), RootTwo::one());
+    }
+
+    #[test]
+    fn representation_is_a_homomorphism() {
+        let (h, t) = (hadamard(), t_gate());
+        let word = [h, t, h, t, t, h, t];
+        let u = word.iter().fold(Matrix2::one(), |acc, &g| acc * g);
+        let r = word
+            .iter()
+            .fold(SO3Matrix::one(), |acc, &g| acc * SO3Matrix::from_unitary(g));
+        assert_eq!(SO3Matrix::from_unitary(u), r);
+        assert_eq!(r * r.transpose(), SO3Matrix::one());
+        // global phases are invisible
+        assert_eq!(SO3Matrix::from_unitary(u.scale(DOmega::omega())), r);
+        // each HT adds one to the denominator exponent
+        let ht = SO3Matrix::from_unitary(h * t);
+        assert_eq!((ht * ht * ht).denomexp(), 3);
+    }
+
+    #[test]
+    fn new_cancels_roottwo() {
+        let two = ints([
+            [(2, 0), (0, 0), (0, 0)],
+            [(0, 0), (2, 0), (0, 0)],
+            [(0, 0), (0, 0), (2, 0)],
+        ]);
+        assert_eq!(SO3Matrix::new(two, 2), SO3Matrix::one());
+        assert_eq!(SO3Matrix::new(two, 1).numerators()[0][0], Quad(0, 1));
+    }
+}
@@ -0,0 +1,771 @@
+//! Exact synthesis of single-qubit unitaries over D[ω] into Clifford+T
+//! circuits, after Kliuchnikov, Maslov and Mosca.
+//!
+//! Every unitary with entries in D[ω] is exactly a product of `H`, `T` and
+//! the global phase `ω`. The algorithm peels off one `H·Tʲ` at a time, each
+//! lowering the smallest denominator exponent of `|u₀₀|²`, until only
+//! a monomial matrix of powers of `ω` is left.
+//!
+//! [`NormalForm`] gives each such operator its canonical Matsumoto–Amano
+//! circuit, which also has the fewest `T` gates, and
+//! [`synthesize_controlled`] extends the synthesis to `n` qubits.
+
+use std::{fmt, ops};
+
+use num::{One, Zero};
+
+use crate::{
+    Adj, Cplx, DOmega, DenomExp, Dyadic, Matrix, Matrix2, OmegaRing, RootHalfRing, RootTwo,
+};
+
+/// The gates of a Clifford+T circuit on one qubit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Gate {
+    /// The Hadamard gate `[[1, 1], [1, -1]]/√2`.
+    H,
+    /// The π/8 gate `diag(1, ω)`.
+    T,
+    /// The phase gate `diag(1, i) = T²`.
+    S,
+    /// The Pauli gate `[[0, 1], [1, 0]]`.
+    X,
+    /// The global phase `ω·I`.
+    W,
+}
+
+impl Gate {
+    /// The exact unitary for the gate.
+    pub fn matrix(self) -> Matrix2<DOmega> {
+        let (zero, one, omega) = (DOmega::zero(), DOmega::one(), DOmega::omega());
+        match self {
+            Gate::H => {
+                let s = DOmega::roothalf();
+                Matrix2(s, s, s, -s)
+            }
+            Gate::T => Matrix2(one, zero, zero, omega),
+            Gate::S => Matrix2(one, zero, zero, omega * omega),
+            Gate::X => Matrix2(zero, one, one, zero),
+            Gate::W => Matrix2(omega, zero, zero, omega),
+        }
+    }
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Gate::H => "H",
+            Gate::T => "T",
+            Gate::S => "S",
+            Gate::X => "X",
+            Gate::W => "W",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The unitary of a circuit given in the order the gates act, so that
+/// `[g₀, g₁, g₂]` is the matrix `g₂g₁g₀`.
+pub fn evaluate(gates: &[Gate]) -> Matrix2<DOmega> {
+    gates.iter().fold(Matrix2::one(), |acc, g| g.matrix() * acc)
+}
+
+/// The least denominator exponent of the first column.
+fn column_lde(u: &Matrix2<DOmega>) -> u32 {
+    u.0.denomexp().max(u.2.denomexp())
+}
+
+/// The smallest denominator exponent of `|u₀₀|²` in D[√2].
+fn sde(u: &Matrix2<DOmega>) -> u32 {
+    let Cplx(re, _): Cplx<RootTwo<Dyadic>> = Cplx::from(u.0 * u.0.adj());
+    re.denomexp()
+}
+
+/// Appends `Tᵐ` for `m` taken mod 8, as `S`s followed by at most one `T`.
+fn push_t_power(gates: &mut Vec<Gate>, m: i64) {
+    let m = m.rem_euclid(8);
+    gates.extend(std::iter::repeat_n(Gate::S, (m / 2) as usize));
+    if m % 2 == 1 {
+        gates.push(Gate::T);
+    }
+}
+
+/// The exponent `m` with `x == ωᵐ`, if `x` is a power of `ω`.
+fn omega_power(x: DOmega) -> Option<i64> {
+    let omega = DOmega::omega();
+    let mut y = DOmega::one();
+    for m in 0..8 {
+        if y == x {
+            return Some(m);
+        }
+        y *= omega;
+    }
+    None
+}
+
+/// A Clifford+T circuit realizing `u` exactly, listed in the order the gates
+/// act (see [`evaluate`]), or `None` if `u` is not unitary.
+///
+/// The circuit has at most one `H` for each unit of the smallest
+/// denominator exponent of `|u₀₀|²`, each preceded by at most one `T`, with
+/// the remaining phases written as `S`, `X` and `W`.
+pub fn synthesize(u: Matrix2<DOmega>) -> Option<Vec<Gate>> {
+    if !u.is_unitary() {
+        return None;
+    }
+    // reduce v = u† from the left, so that u = v†·(H·Tʲ)⋯ in positive powers
+    let mut v = u.adj();
+    let mut steps = Vec::new();
+    let mut s = sde(&v);
+    while column_lde(&v) > 0 {
+        // some H·Tʲ always lowers the exponent of |v₀₀|², where the exponent
+        // of the column itself can stall
+        let (j, next) = (0..4)
+            .map(|j| {
+                let mut w = v;
+                for _ in 0..j {
+                    w = Gate::T.matrix() * w;
+                }
+                (j, Gate::H.matrix() * w)
+            })
+            .min_by_key(|(_, w)| sde(w))
+            .unwrap();
+        let next_s = sde(&next);
+        assert!(next_s < s, "|u₀₀|² with exponent {s} did not reduce");
+        steps.push(j);
+        (v, s) = (next, next_s);
+    }
+    // v is now X^p·diag(ωᵃ, ωᵇ), and u = v†·H·T^jₙ⋯H·T^j₁
+    let swapped = v.0.is_zero();
+    let (a, b) = if swapped { (v.2, v.1) } else { (v.0, v.3) };
+    let (a, b) = (omega_power(a)?, omega_power(b)?);
+    let mut gates = Vec::new();
+    for &j in &steps {
+        push_t_power(&mut gates, j);
+        gates.push(Gate::H);
+    }
+    // v† = diag(ω⁻ᵃ, ω⁻ᵇ)·X^p = ω⁻ᵃ·T^(a-b)·X^p
+    if swapped {
+        gates.push(Gate::X);
+    }
+    push_t_power(&mut gates, a - b);
+    gates.extend(std::iter::repeat_n(Gate::W, (-a).rem_euclid(8) as usize));
+    Some(gates)
+}
+
+/// A single-qubit Clifford operator `E·Xˣ·Sˢ·ωʷ`, where `E` is `I`, `H` or
+/// `S·H` as the first field is 0, 1 or 2. Every Clifford operator, global
+/// phase included, has exactly one such form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Clifford(pub(crate) u8, pub(crate) bool, pub(crate) u8, pub(crate) u8);
+
+impl Clifford {
+    /// The identity.
+    pub fn identity() -> Clifford {
+        Clifford(0, false, 0, 0)
+    }
+
+    /// The Clifford gates `H`, `S`, `X` and `W`, or `None` for `T`.
+    pub fn from_gate(g: Gate) -> Option<Clifford> {
+        Some(match g {
+            Gate::H => Clifford(1, false, 0, 0),
+            Gate::S => Clifford(0, false, 1, 0),
+            Gate::X => Clifford(0, true, 0, 0),
+            Gate::W => Clifford(0, false, 0, 1),
+            Gate::T => return None,
+        })
+    }
+
+    /// The operator with matrix `u`, if it is Clifford.
+    pub fn from_matrix(u: Matrix2<DOmega>) -> Option<Clifford> {
+        (0..3).find_map(|e| {
+            let d = coset_matrix(e).adj() * u;
+            let x = d.0.is_zero();
+            let (p, q, r, s) = if x {
+                (d.2, d.1, d.0, d.3)
+            } else {
+                (d.0, d.3, d.1, d.2)
+            };
+            if !(r.is_zero() && s.is_zero()) {
+                return None;
+            }
+            let w = omega_power(p)?;
+            let s = (omega_power(q)? - w).rem_euclid(8);
+            (s % 2 == 0).then_some(Clifford(e, x, (s / 2) as u8, w as u8))
+        })
+    }
+
+    /// The exact unitary.
+    pub fn matrix(self) -> Matrix2<DOmega> {
+        let Clifford(e, x, s, w) = self;
+        let mut u = coset_matrix(e);
+        if x {
+            u = u * Gate::X.matrix();
+        }
+        for _ in 0..s {
+            u = u * Gate::S.matrix();
+        }
+        u.scale(num::pow(DOmega::omega(), w as usize))
+    }
+
+    /// The operator as a circuit of `H`, `S`, `X` and `W`, listed in the
+    /// order the gates act.
+    pub fn gates(self) -> Vec<Gate> {
+        let Clifford(e, x, s, w) = self;
+        let mut gates = vec![Gate::W; w as usize];
+        gates.extend(std::iter::repeat_n(Gate::S, s as usize));
+        if x {
+            gates.push(Gate::X);
+        }
+        gates.extend(match e {
+            0 => &[][..],
+            1 => &[Gate::H],
+            _ => &[Gate::H, Gate::S],
+        });
+        gates
+    }
+}
+
+/// The coset representative `I`, `H` or `S·H`.
+fn coset_matrix(e: u8) -> Matrix2<DOmega> {
+    match e {
+        0 => Matrix2::one(),
+        1 => Gate::H.matrix(),
+        _ => Gate::S.matrix() * Gate::H.matrix(),
+    }
+}
+
+impl ops::Mul for Clifford {
+    type Output = Clifford;
+
+    fn mul(self, rhs: Clifford) -> Clifford {
+        Clifford::from_matrix(self.matrix() * rhs.matrix()).expect("Cliffords form a group")
+    }
+}
+
+/// A syllable of a [`NormalForm`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Syllable {
+    /// `H·T`
+    HT,
+    /// `S·H·T`
+    SHT,
+}
+
+/// The Matsumoto–Amano normal form `(T | ε)(HT | SHT)*·C` of a Clifford+T
+/// operator, with `C` a [`Clifford`], as an operator product read left to
+/// right.
+///
+/// Each operator has exactly one normal form, so two circuits are equal
+/// exactly when their normal forms are, and the number of `T`s in it is the
+/// least of any circuit for the operator.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NormalForm {
+    pub(crate) leading_t: bool,
+    pub(crate) syllables: Vec<Syllable>,
+    pub(crate) clifford: Clifford,
+}
+
+impl NormalForm {
+    /// The normal form of the identity.
+    pub fn identity() -> NormalForm {
+        NormalForm {
+            leading_t: false,
+            syllables: Vec::new(),
+            clifford: Clifford::identity(),
+        }
+    }
+
+    /// The normal form of a circuit, listed in the order the gates act.
+    pub fn from_gates(gates: &[Gate]) -> NormalForm {
+        let mut nf = NormalForm::identity();
+        for &g in gates.iter().rev() {
+            nf.push_gate(g);
+        }
+        nf
+    }
+
+    /// The normal form of `u`, or `None` if `u` is not unitary.
+    pub fn from_matrix(u: Matrix2<DOmega>) -> Option<NormalForm> {
+        synthesize(u).map(|gates| NormalForm::from_gates(&gates))
+    }
+
+    /// Whether the form starts with a bare `T`.
+    pub fn has_leading_t(&self) -> bool {
+        self.leading_t
+    }
+
+    pub fn syllables(&self) -> &[Syllable] {
+        &self.syllables
+    }
+
+    pub fn clifford(&self) -> Clifford {
+        self.clifford
+    }
+
+    /// The number of `T` gates, which is optimal for the operator.
+    pub fn t_count(&self) -> usize {
+        usize::from(self.leading_t) + self.syllables.len()
+    }
+
+    /// The form as a circuit, listed in the order the gates act.
+    pub fn gates(&self) -> Vec<Gate> {
+        let mut gates = self.clifford.gates();
+        for syllable in self.syllables.iter().rev() {
+            gates.push(Gate::T);
+            gates.push(Gate::H);
+            if *syllable == Syllable::SHT {
+                gates.push(Gate::S);
+            }
+        }
+        if self.leading_t {
+            gates.push(Gate::T);
+        }
+        gates
+    }
+
+    /// The exact unitary.
+    pub fn matrix(&self) -> Matrix2<DOmega> {
+        evaluate(&self.gates())
+    }
+
+    /// Replaces the form of `N` by that of `N·g`.
+    fn push_gate(&mut self, g: Gate) {
+        let Some(t_free) = Clifford::from_gate(g) else {
+            return self.push_t();
+        };
+        self.clifford = self.clifford * t_free;
+    }
+
+    /// Replaces the form of `N` by that of `N·T`, writing `C = E·D` with
+    /// `D` in the group generated by `X`, `S` and `ω` and `D·T = T·D'`.
+    fn push_t(&mut self) {
+        let Clifford(e, x, s, w) = self.clifford;
+        let t = Gate::T.matrix();
+        let d = Clifford::from_matrix(t.adj() * Clifford(0, x, s, w).matrix() * t)
+            .expect("X, S and ω normalize T up to Cliffords");
+        self.clifford = d;
+        match e {
+            1 => self.syllables.push(Syllable::HT),
+            2 => self.syllables.push(Syllable::SHT),
+            // a trailing T·T is S, which folds into the Clifford
+            _ => match self.syllables.pop() {
+                Some(syllable) => {
+                    let hs = Clifford(1, false, 1, 0);
+                    self.clifford = match syllable {
+                        Syllable::HT => hs * d,
+                        Syllable::SHT => Clifford::from_gate(Gate::S).unwrap() * hs * d,
+                    };
+                }
+                None if self.leading_t => {
+                    self.leading_t = false;
+                    self.clifford = Clifford::from_gate(Gate::S).unwrap() * d;
+                }
+                None => self.leading_t = true,
+            },
+        }
+    }
+}
+
+impl fmt::Display for NormalForm {
+    /// The operator product, such as `THTSHTHS`, with the Clifford written as
+    /// `E·Xˣ·Sˢ·ωʷ`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for g in self.gates().iter().rev() {
+            write!(f, "{g}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A gate of an `n`-qubit circuit, acting as `gate` on the qubit `target`
+/// wherever each control qubit holds the given value. Qubit 0 is the most
+/// significant bit of a basis index, as in [`Matrix::kron`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ControlledGate {
+    pub gate: Gate,
+    pub target: usize,
+    pub controls: Vec<(usize, bool)>,
+}
+
+/// The unitary on `n` qubits of a circuit given in the order the gates act.
+pub fn evaluate_controlled(n: usize, gates: &[ControlledGate]) -> Matrix<DOmega> {
+    let mut u = Matrix::identity(1 << n);
+    for g in gates {
+        let bit = |q: usize| 1 << (n - 1 - q);
+        let m = g.gate.matrix();
+        for i in 0..1 << n {
+            let controlled = g.controls.iter().all(|&(q, v)| (i & bit(q) != 0) == v);
+            if i & bit(g.target) == 0 && controlled {
+                mix_rows(&mut u, m, i, i | bit(g.target));
+            }
+        }
+    }
+    u
+}
+
+/// Replaces rows `p` and `q` of `u` by their images under `m`.
+fn mix_rows(u: &mut Matrix<DOmega>, m: Matrix2<DOmega>, p: usize, q: usize) {
+    for j in 0..u.dim() {
+        let (x, y) = (u[(p, j)], u[(q, j)]);
+        u[(p, j)] = m.0 * x + m.1 * y;
+        u[(q, j)] = m.2 * x + m.3 * y;
+    }
+}
+
+/// An operator acting on at most two basis states.
+#[derive(Debug, Clone, Copy)]
+enum TwoLevel {
+    /// `H` on the states `p` and `q`, in that order.
+    H(usize, usize),
+    /// `X` on the states `p` and `q`.
+    X(usize, usize),
+    /// The phase `ωᵐ` on the state `p`.
+    Phase(usize, i64),
+}
+
+impl TwoLevel {
+    /// The operator `H` on states `a` and `b`, up to the sign of one of
+    /// them, ordered so that the highest differing bit is clear in the
+    /// first.
+    fn h(a: usize, b: usize) -> TwoLevel {
+        let t = usize::BITS - 1 - (a ^ b).leading_zeros();
+        if a & 1 << t == 0 {
+            TwoLevel::H(a, b)
+        } else {
+            TwoLevel::H(b, a)
+        }
+    }
+
+    fn apply(self, u: &mut Matrix<DOmega>) {
+        match self {
+            TwoLevel::H(p, q) => mix_rows(u, Gate::H.matrix(), p, q),
+            TwoLevel::X(p, q) => mix_rows(u, Gate::X.matrix(), p, q),
+            TwoLevel::Phase(p, m) => {
+                let phase = num::pow(DOmega::omega(), m.rem_euclid(8) as usize);
+                for j in 0..u.dim() {
+                    u[(p, j)] = phase * u[(p, j)];
+                }
+            }
+        }
+    }
+
+    fn inverse(self) -> TwoLevel {
+        match self {
+            TwoLevel::Phase(p, m) => TwoLevel::Phase(p, -m),
+            op => op,
+        }
+    }
+
+    /// The operator as gates on `n` qubits: a Gray code of fully controlled
+    /// `X`s brings `p` next to `q`, and a fully controlled gate then acts on
+    /// the one qubit where they differ.
+    fn controlled_gates(self, n: usize, gates: &mut Vec<ControlledGate>) {
+        let qubit = |b: u32| n - 1 - b as usize;
+        let controls = |i: usize, target: usize| -> Vec<(usize, bool)> {
+            (0..n)
+                .filter(|&q| q != target)
+                .map(|q| (q, i & 1 << (n - 1 - q) != 0))
+                .collect()
+        };
+        let (p, q, gate) = match self {
+            TwoLevel::H(p, q) => (p, q, Gate::H),
+            TwoLevel::X(p, q) => (p, q, Gate::X),
+            TwoLevel::Phase(_, m) if m.rem_euclid(8) == 0 => return,
+            TwoLevel::Phase(p, m) => {
+                // ωᵐ on p is Tᵐ on its last qubit, sandwiched by X if clear
+                let target = n - 1;
+                let flip = p & 1 == 0;
+                let x = ControlledGate {
+                    gate: Gate::X,
+                    target,
+                    controls: controls(p, target),
+                };
+                if flip {
+                    gates.push(x.clone());
+                }
+                let mut phases = Vec::new();
+                push_t_power(&mut phases, m);
+                gates.extend(phases.into_iter().map(|gate| ControlledGate {
+                    gate,
+                    target,
+                    controls: controls(p, target),
+                }));
+                if flip {
+                    gates.push(x);
+                }
+                return;
+            }
+        };
+        let t = usize::BITS - 1 - (p ^ q).leading_zeros();
+        let mut moves = Vec::new();
+        let mut cur = p;
+        for b in (0..t).filter(|&b| (p ^ q) & 1 << b != 0) {
+            moves.push(ControlledGate {
+                gate: Gate::X,
+                target: qubit(b),
+                controls: controls(cur, qubit(b)),
+            });
+            cur ^= 1 << b;
+        }
+        gates.extend(moves.iter().cloned());
+        gates.push(ControlledGate {
+            gate,
+            target: qubit(t),
+            controls: controls(q, qubit(t)),
+        });
+        gates.extend(moves.into_iter().rev());
+    }
+}
+
+/// Phases `m` such that `depth` rounds of `ωᵐ` on `y` then `H` on `(x, y)`
+/// bring both below exponent `k`, where `flipped` orders `H` as `(y, x)`.
+fn reduce_pair(x: DOmega, y: DOmega, flipped: bool, k: u32, depth: usize) -> Option<Vec<i64>> {
+    if depth == 0 {
+        return (x.denomexp() < k && y.denomexp() < k).then(Vec::new);
+    }
+    let s = DOmega::roothalf();
+    (0..8).find_map(|m| {
+        let y = num::pow(DOmega::omega(), m) * y;
+        let (x, y) = if flipped {
+            (s * (y - x), s * (y + x))
+        } else {
+            (s * (x + y), s * (x - y))
+        };
+        let mut ms = reduce_pair(x, y, flipped, k, depth - 1)?;
+        ms.insert(0, m as i64);
+        Some(ms)
+    })
+}
+
+/// A circuit of controlled Clifford+T gates realizing the `n`-qubit `u`
+/// exactly, listed in the order the gates act (see
+/// [`evaluate_controlled`]), or `None` if `u` is not unitary or its
+/// dimension is not `2ⁿ` for some `n ≥ 1`. After Giles and Selinger.
+///
+/// Each column in turn is reduced to a basis vector by operators on one or
+/// two basis states, pairing the entries of largest denominator exponent so
+/// that a few rounds of phase and `H` lower both, and each such operator
+/// becomes a Gray code of controlled `X`s around one controlled gate. The
+/// controls are left for the caller to expand. The function never panics:
+/// should some column fail to reduce, the result is `None` as well.
+pub fn synthesize_controlled(u: &Matrix<DOmega>) -> Option<Vec<ControlledGate>> {
+    let dim = u.dim();
+    if dim < 2 || !dim.is_power_of_two() || !u.is_unitary() {
+        return None;
+    }
+    let n = dim.trailing_zeros() as usize;
+    let mut v = u.clone();
+    let mut ops = Vec::new();
+    let mut apply = |op: TwoLevel, v: &mut Matrix<DOmega>| {
+        op.apply(v);
+        ops.push(op);
+    };
+    for j in 0..dim {
+        loop {
+            let k = (j..dim).map(|i| v[(i, j)].denomexp()).max().unwrap();
+            if k == 0 {
+                break;
+            }
+            let rows: Vec<usize> = (j..dim).filter(|&i| v[(i, j)].denomexp() == k).collect();
+            // the entries of exponent k pair up, and each pair falls below k
+            // after a few rounds of phase and H, a single one when x ≡ ωᵐy
+            // mod 2
+            let a = rows[0];
+            let (b, phases) = (1..=3).find_map(|depth| {
+                rows[1..].iter().find_map(|&b| {
+                    reduce_pair(v[(a, j)], v[(b, j)], a > b, k, depth).map(|ms| (b, ms))
+                })
+            })?;
+            for m in phases {
+                apply(TwoLevel::Phase(b, m), &mut v);
+                apply(TwoLevel::h(a, b), &mut v);
+            }
+        }
+        // the column is now ωᵖ times a basis vector
+        let r = (j..dim).find(|&i| !v[(i, j)].is_zero())?;
+        let p = omega_power(v[(r, j)])?;
+        if r != j {
+            apply(TwoLevel::X(j, r), &mut v);
+        }
+        apply(TwoLevel::Phase(j, -p), &mut v);
+    }
+    // the ops multiply u to the identity, so u is their inverses in reverse
+    let mut gates = Vec::new();
+    for op in ops.into_iter().rev() {
+        op.inverse().controlled_gates(n, &mut gates);
+    }
+    Some(gates)
+}
+
+#[cfg(test)]
+mod synthesis_tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn single_gates() {
+        assert_eq!(synthesize(Gate::H.matrix()), Some(vec![Gate::H]));
+        assert_eq!(
+            synthesize(Gate::T.matrix()).map(|g| evaluate(&g)),
+            Some(Gate::T.matrix())
+        );
+        assert_eq!(synthesize(Matrix2::one()), Some(vec![]));
+        for g in [Gate::S, Gate::X, Gate::W] {
+            assert_eq!(evaluate(&synthesize(g.matrix()).unwrap()), g.matrix());
+        }
+        assert_eq!(synthesize(Gate::H.matrix() + Gate::T.matrix()), None);
+    }
+
+    #[test]
+    fn h_count_bounded_by_sde() {
+        let word = [
+            Gate::T,
+            Gate::H,
+            Gate::T,
+            Gate::H,
+            Gate::S,
+            Gate::T,
+            Gate::H,
+            Gate::T,
+            Gate::H,
+        ];
+        let u = evaluate(&word);
+        let gates = synthesize(u).unwrap();
+        assert_eq!(evaluate(&gates), u);
+        let hs = gates.iter().filter(|&&g| g == Gate::H).count();
+        assert!(hs as u32 <= sde(&u));
+        assert_eq!(
+            gates
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<String>()
+                .len(),
+            gates.len()
+        );
+    }
+
+    #[test]
+    fn clifford_forms_are_unique() {
+        let all: Vec<Clifford> = (0..3)
+            .flat_map(|e| [false, true].map(|x| (e, x)))
+            .flat_map(|(e, x)| (0..4).flat_map(move |s| (0..8).map(move |w| Clifford(e, x, s, w))))
+            .collect();
+        assert_eq!(all.len(), 192);
+        for (i, c) in all.iter().enumerate() {
+            assert_eq!(Clifford::from_matrix(c.matrix()), Some(*c));
+            assert_eq!(evaluate(&c.gates()), c.matrix());
+            assert!(all[..i].iter().all(|d| d.matrix() != c.matrix()));
+        }
+        assert_eq!(Clifford::from_matrix(Gate::T.matrix()), None);
+    }
+
+    #[test]
+    fn normal_form_examples() {
+        let nf = NormalForm::from_gates(&[Gate::T, Gate::T]);
+        assert_eq!(nf.t_count(), 0);
+        assert_eq!(nf.clifford(), Clifford::from_gate(Gate::S).unwrap());
+        let nf = NormalForm::from_gates(&[Gate::H, Gate::T, Gate::H, Gate::T]);
+        assert_eq!(nf.t_count(), 2);
+        assert!(nf.has_leading_t());
+        assert_eq!(nf.to_string(), "THTH");
+        assert_eq!(nf.syllables(), [Syllable::HT]);
+        assert_eq!(
+            NormalForm::from_matrix(Matrix2::one()),
+            Some(NormalForm::identity())
+        );
+    }
+
+    #[test]
+    fn controlled_gates_on_qubits() {
+        let h = Matrix::from(Gate::H.matrix());
+        let cnot = ControlledGate {
+            gate: Gate::X,
+            target: 1,
+            controls: vec![(0, true)],
+        };
+        let on_first = ControlledGate {
+            gate: Gate::H,
+            target: 0,
+            controls: vec![],
+        };
+        assert_eq!(
+            evaluate_controlled(2, &[on_first]),
+            h.kron(&Matrix::identity(2))
+        );
+        let (zero, one) = (DOmega::zero(), DOmega::one());
+        let permutation = Matrix::from_fn(4, |i, j| if [0, 1, 3, 2][j] == i { one } else { zero });
+        assert_eq!(
+            evaluate_controlled(2, std::slice::from_ref(&cnot)),
+            permutation
+        );
+        let gates = synthesize_controlled(&permutation).unwrap();
+        assert_eq!(evaluate_controlled(2, &gates), permutation);
+        assert_eq!(
+            synthesize_controlled(&Matrix::identity(4)),
+            Some(Vec::new())
+        );
+        assert_eq!(synthesize_controlled(&Matrix::identity(3)), None);
+        assert_eq!(synthesize_controlled(&(h.clone() + h)), None);
+    }
+
+    fn gate() -> impl Strategy<Value = Gate> {
+        prop_oneof![
+            Just(Gate::H),
+            Just(Gate::T),
+            Just(Gate::S),
+            Just(Gate::X),
+            Just(Gate::W)
+        ]
+    }
+
+    fn controlled_gate(n: usize) -> impl Strategy<Value = ControlledGate> {
+        (
+            gate(),
+            0..n,
+            prop::collection::vec(prop::option::of(any::<bool>()), n),
+        )
+            .prop_map(|(gate, target, controls)| ControlledGate {
+                gate,
+                target,
+                controls: (0..controls.len())
+                    .filter(|&q| q != target)
+                    .filter_map(|q| controls[q].map(|v| (q, v)))
+                    .collect(),
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn synthesis_is_exact(word in prop::collection::vec(gate(), 0..60)) {
+            let u = evaluate(&word);
+            let gates = synthesize(u).unwrap();
+            prop_assert_eq!(evaluate(&gates), u);
+            let ts = gates.iter().filter(|&&g| g == Gate::T).count();
+            let hs = gates.iter().filter(|&&g| g == Gate::H).count();
+            prop_assert!(ts <= hs + 1);
+            prop_assert!(hs as u32 <= sde(&u));
+        }
+
+        #[test]
+        fn normal_forms_are_canonical(word in prop::collection::vec(gate(), 0..60)) {
+            let u = evaluate(&word);
+            let nf = NormalForm::from_gates(&word);
+            prop_assert_eq!(nf.matrix(), u);
+            prop_assert_eq!(NormalForm::from_gates(&nf.gates()), nf.clone());
+            prop_assert_eq!(NormalForm::from_matrix(u), Some(nf.clone()));
+            let ts = word.iter().filter(|&&g| g == Gate::T).count();
+            prop_assert!(nf.t_count() <= ts);
+        }
+
+        #[test]
+        fn controlled_synthesis_is_exact(
+            (n, word) in (1..4usize).prop_flat_map(|n| {
+                (Just(n), prop::collection::vec(controlled_gate(n), 0..30))
+            })
+        ) {
+            let u = evaluate_controlled(n, &word);
+            let gates = synthesize_controlled(&u).unwrap();
+            prop_assert_eq!(evaluate_controlled(n, &gates), u);
+        }
+    }
+}
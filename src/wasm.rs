@@ -0,0 +1,250 @@
+//! JavaScript bindings via wasm-bindgen, for builds to
+//! `wasm32-unknown-unknown` with the `wasm` feature.
+//!
+//! The exports are the classes `Dyadic` and `RootTwo`, the latter holding
+//! dyadic coefficients, and the functions `approximate`,
+//! `solveNormEquation` and `synthesize`. Integers cross the boundary as
+//! JavaScript `BigInt`s so that no precision is lost, and operations that
+//! would overflow throw instead.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    approx::approximable, diophantine, newsynth::Newsynth, synthesis::NormalForm, DOmega, Dyadic,
+    Matrix2, Quad, RootTwo,
+};
+
+#[wasm_bindgen(js_name = Dyadic)]
+#[derive(Clone)]
+pub struct WasmDyadic(Dyadic);
+
+#[wasm_bindgen(js_name = RootTwo)]
+#[derive(Clone)]
+pub struct WasmRootTwo(RootTwo<Dyadic>);
+
+fn overflow<T>(x: Option<T>) -> Result<T, JsError> {
+    x.ok_or_else(|| JsError::new("coefficient overflow"))
+}
+
+#[wasm_bindgen(js_class = Dyadic)]
+impl WasmDyadic {
+    /// `n/2ᵏ`
+    #[wasm_bindgen(constructor)]
+    pub fn new(n: i64, k: u32) -> WasmDyadic {
+        WasmDyadic(Dyadic::new(n, k))
+    }
+
+    /// Parses the printed form, such as `"3/2^4"` or `"3/16"`.
+    pub fn parse(s: &str) -> Result<WasmDyadic, JsError> {
+        s.parse().map(WasmDyadic).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn numer(&self) -> i64 {
+        self.0 .0
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn exponent(&self) -> u32 {
+        self.0 .1
+    }
+
+    pub fn add(&self, other: &WasmDyadic) -> Result<WasmDyadic, JsError> {
+        overflow(self.0.checked_add(other.0)).map(WasmDyadic)
+    }
+
+    pub fn sub(&self, other: &WasmDyadic) -> Result<WasmDyadic, JsError> {
+        overflow(self.0.checked_sub(other.0)).map(WasmDyadic)
+    }
+
+    pub fn mul(&self, other: &WasmDyadic) -> Result<WasmDyadic, JsError> {
+        overflow(self.0.checked_mul(other.0)).map(WasmDyadic)
+    }
+
+    pub fn neg(&self) -> Result<WasmDyadic, JsError> {
+        overflow(self.0.checked_neg()).map(WasmDyadic)
+    }
+
+    /// `-1`, `0` or `1` as `self` is less than, equal to or greater than
+    /// `other`.
+    pub fn compare(&self, other: &WasmDyadic) -> i32 {
+        self.0.cmp(&other.0) as i32
+    }
+
+    pub fn equals(&self, other: &WasmDyadic) -> bool {
+        self.0 == other.0
+    }
+
+    /// The nearest `number`.
+    #[wasm_bindgen(js_name = toNumber)]
+    pub fn to_number(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[wasm_bindgen(js_class = RootTwo)]
+impl WasmRootTwo {
+    /// `a + b√2`
+    #[wasm_bindgen(constructor)]
+    pub fn new(a: &WasmDyadic, b: &WasmDyadic) -> WasmRootTwo {
+        WasmRootTwo(Quad(a.0, b.0))
+    }
+
+    /// `a + b√2` for integers `a` and `b`.
+    #[wasm_bindgen(js_name = fromIntegers)]
+    pub fn from_integers(a: i64, b: i64) -> WasmRootTwo {
+        WasmRootTwo(Quad(Dyadic::new(a, 0), Dyadic::new(b, 0)))
+    }
+
+    /// Parses the printed form, such as `"1/2^1 - 3√2"`.
+    pub fn parse(s: &str) -> Result<WasmRootTwo, JsError> {
+        s.parse().map(WasmRootTwo).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn a(&self) -> WasmDyadic {
+        WasmDyadic(self.0 .0)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn b(&self) -> WasmDyadic {
+        WasmDyadic(self.0 .1)
+    }
+
+    /// `a - b√2`
+    pub fn conj(&self) -> Result<WasmRootTwo, JsError> {
+        let b = overflow(self.0 .1.checked_neg())?;
+        Ok(WasmRootTwo(Quad(self.0 .0, b)))
+    }
+
+    pub fn add(&self, other: &WasmRootTwo) -> Result<WasmRootTwo, JsError> {
+        overflow(self.0.checked_add(other.0)).map(WasmRootTwo)
+    }
+
+    pub fn sub(&self, other: &WasmRootTwo) -> Result<WasmRootTwo, JsError> {
+        overflow(self.0.checked_sub(other.0)).map(WasmRootTwo)
+    }
+
+    pub fn mul(&self, other: &WasmRootTwo) -> Result<WasmRootTwo, JsError> {
+        overflow(self.0.checked_mul(other.0)).map(WasmRootTwo)
+    }
+
+    pub fn neg(&self) -> Result<WasmRootTwo, JsError> {
+        overflow(self.0.checked_neg()).map(WasmRootTwo)
+    }
+
+    /// `-1`, `0` or `1` as `self` is less than, equal to or greater than
+    /// `other`, decided exactly.
+    pub fn compare(&self, other: &WasmRootTwo) -> i32 {
+        self.0.cmp(&other.0) as i32
+    }
+
+    pub fn equals(&self, other: &WasmRootTwo) -> bool {
+        self.0 == other.0
+    }
+
+    /// The correctly rounded `number`.
+    #[wasm_bindgen(js_name = toNumber)]
+    pub fn to_number(&self) -> f64 {
+        f64::from(self.0)
+    }
+
+    #[allow(clippy::inherent_to_string)]
+    #[wasm_bindgen(js_name = toString)]
+    pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+/// The result of [`approximate`], a point `u` and the error `x - u`.
+#[wasm_bindgen]
+pub struct Approximation {
+    u: RootTwo<Dyadic>,
+    err: RootTwo<Dyadic>,
+}
+
+#[wasm_bindgen]
+impl Approximation {
+    #[wasm_bindgen(getter)]
+    pub fn u(&self) -> WasmRootTwo {
+        WasmRootTwo(self.u)
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn err(&self) -> WasmRootTwo {
+        WasmRootTwo(self.err)
+    }
+}
+
+/// The closest `u = (a + b√2)/2ᵏ` to `x` with `|u•| ≤ 1`, as in
+/// [`RootTwo::<Dyadic>::approximate`].
+#[wasm_bindgen]
+pub fn approximate(x: f64, k: u32) -> Result<Approximation, JsError> {
+    if !approximable(x, k) {
+        return Err(JsError::new(
+            "approximation needs a finite x, k ≤ 40 and |x|·2ᵏ < 2⁶²",
+        ));
+    }
+    let (u, err) = RootTwo::<Dyadic>::approximate(x, k);
+    Ok(Approximation { u, err })
+}
+
+/// The coefficients `[a, b, c, d]` of some `t = a + bω + cω² + dω³` with
+/// `t†t = a + b√2`, or `undefined`, as in
+/// [`diophantine::solve_norm_equation`].
+#[wasm_bindgen(js_name = solveNormEquation)]
+pub fn solve_norm_equation(a: i64, b: i64) -> Option<Vec<i64>> {
+    diophantine::solve_norm_equation(Quad(a, b)).map(|t| vec![t.0, t.1, t.2, t.3])
+}
+
+/// A Clifford+T circuit for the unitary with the given entries, each in
+/// `newsynth`'s syntax such as `"roottwo/2"` or `"1/2*omega^3"`. The
+/// circuit is the operator product in its normal form, as in
+/// [`NormalForm`], so `"HT"` applies `T` first and the identity is `""`.
+#[wasm_bindgen]
+pub fn synthesize(u00: &str, u01: &str, u10: &str, u11: &str) -> Result<String, JsError> {
+    let [a, b, c, d] = [u00, u01, u10, u11].map(DOmega::from_newsynth);
+    let u = Matrix2(a?, b?, c?, d?);
+    NormalForm::from_matrix(u)
+        .map(|n| n.to_string())
+        .ok_or_else(|| JsError::new("the matrix is not unitary"))
+}
+
+#[cfg(test)]
+mod wasm_tests {
+    use super::*;
+
+    #[test]
+    fn exact_arithmetic() {
+        let x = WasmRootTwo::from_integers(1, 1);
+        let unit = x.mul(&x.conj().unwrap()).unwrap();
+        assert!(unit.equals(&WasmRootTwo::from_integers(-1, 0)));
+        let half = WasmDyadic::new(1, 1);
+        let y = WasmRootTwo::new(&half, &WasmDyadic::new(0, 0));
+        assert_eq!(y.add(&x).unwrap().to_string(), "3/2^1 + √2");
+        assert_eq!(x.sub(&y).unwrap().compare(&x), -1);
+        assert_eq!(half.mul(&half).unwrap().to_number(), 0.25);
+        assert_eq!(half.neg().unwrap().add(&half).unwrap().numer(), 0);
+        let approx = approximate(0.3, 3).unwrap();
+        assert_eq!(approx.u().add(&approx.err()).unwrap().to_number(), 0.3);
+        assert!(solve_norm_equation(5, 0).is_some());
+        assert_eq!(solve_norm_equation(7, 0), None);
+        assert_eq!(solve_norm_equation(i64::MAX, 1), None);
+    }
+
+    #[test]
+    fn synthesis() {
+        let h = "roottwo/2";
+        assert_eq!(synthesize(h, h, h, &format!("-{h}")).unwrap(), "H");
+        assert_eq!(synthesize("1", "0", "0", "omega").unwrap(), "T");
+        assert_eq!(synthesize("1", "0", "0", "1").unwrap(), "");
+        let ht = synthesize(h, &format!("{h}*omega"), h, &format!("-{h}*omega")).unwrap();
+        assert_eq!(ht, "HT");
+    }
+}
@@ -0,0 +1,230 @@
+use std::ops;
+
+use num::{Complex, One, Zero};
+
+use crate::{Adj, Adj2, DOmega, Dyadic, Omega, Rounding, ZOmega};
+
+/// A Gaussian integer `a + bi`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "rkyv",
+    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize),
+    rkyv(compare(PartialEq))
+)]
+pub struct ZI(pub(crate) i64, pub(crate) i64);
+
+impl ZI {
+    pub fn new(re: i64, im: i64) -> ZI {
+        ZI(re, im)
+    }
+
+    pub fn re(self) -> i64 {
+        self.0
+    }
+
+    pub fn im(self) -> i64 {
+        self.1
+    }
+
+    /// The complex conjugate. `conj(a + bi) == a - bi`
+    pub fn conj(self) -> ZI {
+        ZI(self.0, -self.1)
+    }
+
+    /// The real and imaginary parts as `f64`s rounded in the given
+    /// direction, like [`DOmega::to_complex_rounded`].
+    pub fn to_complex_rounded(self, rounding: Rounding) -> Complex<f64> {
+        DOmega::from(self).to_complex_rounded(rounding)
+    }
+
+    /// Whether `self` is one of `±1, ±i`.
+    pub fn is_unit(self) -> bool {
+        self.0.abs() + self.1.abs() == 1
+    }
+
+    /// The inverse of a unit, which is its conjugate.
+    pub fn inv(self) -> Option<ZI> {
+        if self.is_unit() {
+            Some(self.conj())
+        } else {
+            None
+        }
+    }
+}
+
+// #########################################
+// #######                           #######
+// ####           Traits for ZI         ####
+// #######                           #######
+// #########################################
+
+impl ops::Add for ZI {
+    type Output = ZI;
+    fn add(self, rhs: ZI) -> Self::Output {
+        ZI(self.0 + rhs.0, self.1 + rhs.1)
+    }
+}
+
+impl ops::Sub for ZI {
+    type Output = ZI;
+    fn sub(self, rhs: ZI) -> Self::Output {
+        ZI(self.0 - rhs.0, self.1 - rhs.1)
+    }
+}
+
+impl ops::Neg for ZI {
+    type Output = ZI;
+    fn neg(self) -> Self::Output {
+        ZI(-self.0, -self.1)
+    }
+}
+
+impl ops::AddAssign for ZI {
+    fn add_assign(&mut self, rhs: ZI) {
+        self.0 += rhs.0;
+        self.1 += rhs.1;
+    }
+}
+
+impl ops::SubAssign for ZI {
+    fn sub_assign(&mut self, rhs: ZI) {
+        self.0 -= rhs.0;
+        self.1 -= rhs.1;
+    }
+}
+
+impl ops::Mul for ZI {
+    type Output = ZI;
+    fn mul(self, rhs: ZI) -> Self::Output {
+        ZI(
+            self.0 * rhs.0 - self.1 * rhs.1,
+            self.0 * rhs.1 + self.1 * rhs.0,
+        )
+    }
+}
+
+impl ops::Mul<i64> for ZI {
+    type Output = ZI;
+    fn mul(self, rhs: i64) -> Self::Output {
+        ZI(self.0 * rhs, self.1 * rhs)
+    }
+}
+
+impl ops::MulAssign for ZI {
+    fn mul_assign(&mut self, rhs: ZI) {
+        *self = *self * rhs;
+    }
+}
+
+impl ops::MulAssign<i64> for ZI {
+    fn mul_assign(&mut self, rhs: i64) {
+        self.0 *= rhs;
+        self.1 *= rhs;
+    }
+}
+
+impl ops::Mul<ZI> for i64 {
+    type Output = ZI;
+    fn mul(self, rhs: ZI) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Zero for ZI {
+    fn zero() -> Self {
+        ZI(0, 0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == 0 && self.1 == 0
+    }
+}
+
+impl One for ZI {
+    fn one() -> Self {
+        ZI(1, 0)
+    }
+
+    fn is_one(&self) -> bool {
+        self.0 == 1 && self.1 == 0
+    }
+}
+
+impl Adj2 for ZI {
+    /// ℤ[i] contains no `√2`, so this is the identity.
+    fn adj2(self) -> Self {
+        self
+    }
+}
+
+impl Adj for ZI {
+    fn adj(self) -> Self {
+        self.conj()
+    }
+}
+
+impl From<ZI> for Complex<f64> {
+    fn from(val: ZI) -> Self {
+        Complex::new(val.0 as f64, val.1 as f64)
+    }
+}
+
+impl From<ZI> for ZOmega {
+    /// Embeds `a + bi` using `i = ω²`.
+    fn from(val: ZI) -> Self {
+        Omega(val.0, 0, val.1, 0)
+    }
+}
+
+impl From<ZI> for DOmega {
+    /// Embeds `a + bi` using `i = ω²`.
+    fn from(val: ZI) -> Self {
+        Omega(
+            Dyadic::from(val.0),
+            Dyadic::zero(),
+            Dyadic::from(val.1),
+            Dyadic::zero(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod zi_tests {
+    use super::*;
+    #[test]
+    fn units_zi() {
+        for u in [ZI(1, 0), ZI(0, 1), ZI(-1, 0), ZI(0, -1)] {
+            assert!(u.is_unit());
+            assert_eq!(u * u.inv().unwrap(), ZI(1, 0));
+        }
+        assert!(!ZI(1, 1).is_unit());
+        assert_eq!(ZI(0, 0).inv(), None);
+    }
+
+    #[test]
+    fn basic_arithmetic_zi() {
+        let first = ZI(1, 2);
+        let second = ZI(3, -4);
+        assert_eq!(first + second, ZI(4, -2));
+        assert_eq!(first - second, ZI(-2, 6));
+        assert_eq!(first * second, ZI(11, 2));
+        assert_eq!(ZI(0, 1) * ZI(0, 1), ZI(-1, 0));
+    }
+
+    #[test]
+    fn conj_gives_norm() {
+        let x = ZI(3, 4);
+        assert_eq!(x.conj(), ZI(3, -4));
+        assert_eq!(x * x.conj(), ZI(25, 0));
+    }
+
+    #[test]
+    fn into_zomega_is_homomorphism() {
+        let first = ZI(1, 2);
+        let second = ZI(3, -4);
+        assert_eq!(
+            ZOmega::from(first) * ZOmega::from(second),
+            ZOmega::from(first * second)
+        );
+    }
+}